@@ -33,14 +33,9 @@ fn execute() -> Result<String> {
     )
     .add_to(&mut commit_summary);
 
-    let mut opening_paren = StaticText::new("(");
-    let mut closing_paren = StaticText::new(")");
-
-    let mut scope_input = TextInput::new("Enter the commit's scope.", true);
-
-    let empty_scope = scope_input.set_evaluation(Evaluation::IsEmpty);
-    opening_paren.set_dependency(empty_scope, Action::Hide);
-    closing_paren.set_dependency(empty_scope, Action::Hide);
+    let opening_paren = commit_summary.add(StaticText::new("("));
+    let scope_input = commit_summary.add(TextInput::new("Enter the commit's scope.", true));
+    let closing_paren = commit_summary.add(StaticText::new(")"));
 
     let mut breaking_bang = StaticText::new("!");
     let colon = StaticText::new(": ");
@@ -62,9 +57,6 @@ fn execute() -> Result<String> {
     let breaking_change = breaking_step.set_evaluation(Evaluation::Equal("Yes".to_string()));
     breaking_bang.set_dependency(breaking_change, Action::Show);
 
-    opening_paren.add_to(&mut commit_summary);
-    scope_input.add_to(&mut commit_summary);
-    closing_paren.add_to(&mut commit_summary);
     breaking_bang.add_to(&mut commit_summary);
     colon.add_to(&mut commit_summary);
     description.add_to(&mut commit_summary);
@@ -73,6 +65,19 @@ fn execute() -> Result<String> {
     trailers.add_to(&mut form);
     breaking_step.add_to(&mut form);
 
+    form.depend(
+        &opening_paren,
+        &scope_input,
+        Evaluation::IsEmpty,
+        Action::Hide,
+    )?;
+    form.depend(
+        &closing_paren,
+        &scope_input,
+        Evaluation::IsEmpty,
+        Action::Hide,
+    )?;
+
     let mut stdout = stdout();
     let mut stdin = StdinDevice;
 
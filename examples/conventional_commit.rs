@@ -0,0 +1,36 @@
+use std::io::stdout;
+
+use tty_form::{
+    device::StdinDevice,
+    prefab::{ConventionalCommit, ConventionalCommitConfig},
+    Error, Result,
+};
+use tty_interface::Interface;
+
+fn main() {
+    match execute().expect("executes conventional commit example") {
+        Some(commit) => {
+            println!("Result:");
+            println!("{}", commit.message());
+        }
+        None => println!("Form canceled."),
+    }
+}
+
+fn execute() -> Result<Option<ConventionalCommit>> {
+    let config = ConventionalCommitConfig::new();
+
+    let mut stdout = stdout();
+    let mut stdin = StdinDevice;
+
+    let mut interface = Interface::new_relative(&mut stdout)?;
+
+    let result = ConventionalCommit::execute(&config, &mut interface, &mut stdin);
+    interface.exit()?;
+
+    match result {
+        Ok(commit) => Ok(Some(commit)),
+        Err(Error::Canceled) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
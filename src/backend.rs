@@ -0,0 +1,611 @@
+//! Rendering backends which forms and their steps draw to.
+
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+
+use tty_interface::{pos, Interface, Position, Style};
+use unicode_width::UnicodeWidthChar;
+
+use crate::Result;
+
+/// A rendering target for forms. Implemented for [tty_interface::Interface] by default, but may be
+/// implemented to render forms to alternate outputs.
+pub trait RenderBackend {
+    /// Update the text at the specified position.
+    fn set(&mut self, position: Position, text: &str);
+
+    /// Update the text at the specified position with the specified styling.
+    fn set_styled(&mut self, position: Position, text: &str, style: Style);
+
+    /// Clear the specified line.
+    fn clear_line(&mut self, line: u16);
+
+    /// Clear the remainder of a line starting from the specified position.
+    fn clear_rest_of_line(&mut self, from: Position);
+
+    /// Update the cursor's position, or hide it if unspecified.
+    fn set_cursor(&mut self, position: Option<Position>);
+
+    /// Apply any staged changes to the output.
+    fn apply(&mut self) -> Result<()>;
+
+    /// The output's width in columns, if known, to which content may be wrapped.
+    fn width(&self) -> Option<u16> {
+        None
+    }
+
+    /// The output's height in rows, if known.
+    fn height(&self) -> Option<u16> {
+        None
+    }
+
+    /// Note the output's new size in columns and rows, such as reported by a terminal resize
+    /// event. Ignored by outputs which don't track their size.
+    fn resize(&mut self, _columns: u16, _rows: u16) {}
+
+    /// How long until a deferred frame may be applied, if the last applied changes were deferred.
+    fn frame_delay(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Update the terminal cursor's style, or restore the terminal's default if unspecified.
+    /// Ignored by outputs without a terminal cursor, including [Interface], which doesn't expose
+    /// its device to write the style to.
+    fn set_cursor_style(&mut self, _style: Option<CursorStyle>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Ring the terminal bell, such as to signal rejected input. Ignored by outputs without a
+    /// bell, including [Interface], which doesn't expose its device to write the bell to.
+    fn bell(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// The shape of the terminal's cursor.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum CursorShape {
+    /// A block covering the character cell.
+    #[default]
+    Block,
+    /// A vertical bar before the character.
+    Bar,
+    /// A line beneath the character.
+    Underline,
+}
+
+/// The terminal cursor's shape and whether it blinks.
+///
+/// # Examples
+/// ```
+/// use tty_form::{
+///     Form,
+///     backend::{CursorShape, CursorStyle},
+/// };
+///
+/// let mut form = Form::new();
+/// form.set_cursor_style(CursorStyle::new(CursorShape::Bar, false));
+/// ```
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct CursorStyle {
+    shape: CursorShape,
+    blinking: bool,
+}
+
+impl CursorStyle {
+    /// Create a new cursor style with the specified shape and blinking.
+    pub fn new(shape: CursorShape, blinking: bool) -> Self {
+        Self { shape, blinking }
+    }
+
+    /// The cursor's shape.
+    pub fn shape(&self) -> CursorShape {
+        self.shape
+    }
+
+    /// Whether the cursor blinks.
+    pub fn blinking(&self) -> bool {
+        self.blinking
+    }
+
+    /// The DECSCUSR escape sequence selecting this style, supported by most terminal emulators. May
+    /// be written by backends to their device to apply the style.
+    pub fn escape_sequence(&self) -> String {
+        let code = match self.shape {
+            CursorShape::Block => 1,
+            CursorShape::Underline => 3,
+            CursorShape::Bar => 5,
+        };
+
+        // Each shape's steady variant follows its blinking variant
+        let code = if self.blinking { code } else { code + 1 };
+        format!("\x1b[{} q", code)
+    }
+}
+
+/// The DECSCUSR escape sequence restoring the terminal's default cursor style.
+pub const DEFAULT_CURSOR_STYLE: &str = "\x1b[0 q";
+
+impl RenderBackend for Interface<'_> {
+    fn set(&mut self, position: Position, text: &str) {
+        Interface::set(self, position, text);
+    }
+
+    fn set_styled(&mut self, position: Position, text: &str, style: Style) {
+        Interface::set_styled(self, position, text, style);
+    }
+
+    fn clear_line(&mut self, line: u16) {
+        Interface::clear_line(self, line);
+    }
+
+    fn clear_rest_of_line(&mut self, from: Position) {
+        Interface::clear_rest_of_line(self, from);
+    }
+
+    fn set_cursor(&mut self, position: Option<Position>) {
+        Interface::set_cursor(self, position);
+    }
+
+    fn apply(&mut self) -> Result<()> {
+        Interface::apply(self)?;
+        Ok(())
+    }
+}
+
+/// A rendering backend wrapper reporting the size of the terminal its inner backend writes to,
+/// such as an [Interface], which can't determine the size of its device. The size is updated as
+/// the form receives resize events, and determines where content wraps and whether the form's
+/// minimum size is met.
+///
+/// # Examples
+/// ```
+/// # use tty_interface::{Interface, test::VirtualDevice};
+/// # use tty_form::{Error, test::VirtualInputDevice};
+/// # let mut device = VirtualDevice::new();
+/// # let mut stdin = VirtualInputDevice;
+/// use tty_form::{
+///     Form,
+///     backend::TerminalSizeBackend,
+///     step::{Step, CompoundStep},
+///     control::{Control, StaticText},
+/// };
+///
+/// let mut backend = TerminalSizeBackend::new(Interface::new_relative(&mut device)?, (80, 24));
+///
+/// let mut form = Form::new();
+/// let mut step = CompoundStep::new();
+/// StaticText::new("Hello, world!").add_to(&mut step);
+/// step.add_to(&mut form);
+///
+/// form.execute(&mut backend, &mut stdin)?;
+/// # Ok::<(), Error>(())
+/// ```
+pub struct TerminalSizeBackend<B: RenderBackend> {
+    inner: B,
+    columns: u16,
+    rows: u16,
+}
+
+impl<B: RenderBackend> TerminalSizeBackend<B> {
+    /// Create a new backend writing to the specified inner backend, whose terminal has the
+    /// specified size in columns and rows.
+    pub fn new(inner: B, (columns, rows): (u16, u16)) -> Self {
+        Self {
+            inner,
+            columns,
+            rows,
+        }
+    }
+
+    /// The inner backend, such as to exit its interface once the form completes.
+    pub fn inner_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+}
+
+impl<B: RenderBackend> RenderBackend for TerminalSizeBackend<B> {
+    fn set(&mut self, position: Position, text: &str) {
+        self.inner.set(position, text);
+    }
+
+    fn set_styled(&mut self, position: Position, text: &str, style: Style) {
+        self.inner.set_styled(position, text, style);
+    }
+
+    fn clear_line(&mut self, line: u16) {
+        self.inner.clear_line(line);
+    }
+
+    fn clear_rest_of_line(&mut self, from: Position) {
+        self.inner.clear_rest_of_line(from);
+    }
+
+    fn set_cursor(&mut self, position: Option<Position>) {
+        self.inner.set_cursor(position);
+    }
+
+    fn apply(&mut self) -> Result<()> {
+        self.inner.apply()
+    }
+
+    fn width(&self) -> Option<u16> {
+        Some(self.columns)
+    }
+
+    fn height(&self) -> Option<u16> {
+        Some(self.rows)
+    }
+
+    fn resize(&mut self, columns: u16, rows: u16) {
+        (self.columns, self.rows) = (columns, rows);
+    }
+
+    fn set_cursor_style(&mut self, style: Option<CursorStyle>) -> Result<()> {
+        self.inner.set_cursor_style(style)
+    }
+
+    fn bell(&mut self) -> Result<()> {
+        self.inner.bell()
+    }
+
+    fn frame_delay(&self) -> Option<Duration> {
+        self.inner.frame_delay()
+    }
+}
+
+/// A staged or applied cell: its character and styling. A wide character's trailing cells hold no
+/// character.
+#[derive(Clone, Copy, PartialEq)]
+struct Cell {
+    character: Option<char>,
+    style: Option<Style>,
+}
+
+/// A rendering backend wrapper which minimizes the output written to its inner backend, for forms
+/// used over high-latency connections such as SSH sessions. Changes are staged and diffed against
+/// the last applied frame cell-by-cell, so only changed cells are written and lines are never
+/// cleared in full. Frames may additionally be capped to a maximum rate, deferring changes applied
+/// too soon after the last frame until the form next re-renders.
+///
+/// # Examples
+/// ```
+/// # use tty_interface::{Interface, test::VirtualDevice};
+/// # use tty_form::{Error, test::VirtualInputDevice};
+/// # let mut device = VirtualDevice::new();
+/// # let mut stdin = VirtualInputDevice;
+/// use tty_form::{
+///     Form,
+///     backend::LowBandwidthBackend,
+///     step::{Step, CompoundStep},
+///     control::{Control, StaticText},
+/// };
+///
+/// let mut backend = LowBandwidthBackend::new(Interface::new_relative(&mut device)?);
+/// backend.set_max_frame_rate(20);
+///
+/// let mut form = Form::new();
+/// let mut step = CompoundStep::new();
+/// StaticText::new("Hello, world!").add_to(&mut step);
+/// step.add_to(&mut form);
+///
+/// form.execute(&mut backend, &mut stdin)?;
+/// # Ok::<(), Error>(())
+/// ```
+pub struct LowBandwidthBackend<B: RenderBackend> {
+    inner: B,
+    staged: BTreeMap<u16, Vec<Cell>>,
+    applied: BTreeMap<u16, Vec<Cell>>,
+    cursor: Option<Position>,
+    min_frame_interval: Option<Duration>,
+    last_frame: Option<Instant>,
+    deferred: bool,
+}
+
+impl<B: RenderBackend> LowBandwidthBackend<B> {
+    /// Create a new low-bandwidth backend writing to the specified inner backend.
+    pub fn new(inner: B) -> Self {
+        Self {
+            inner,
+            staged: BTreeMap::new(),
+            applied: BTreeMap::new(),
+            cursor: None,
+            min_frame_interval: None,
+            last_frame: None,
+            deferred: false,
+        }
+    }
+
+    /// Cap the rate at which frames are written to the inner backend. Changes applied sooner are
+    /// deferred until the form next re-renders, or until this backend is flushed or dropped.
+    pub fn set_max_frame_rate(&mut self, frames_per_second: u32) {
+        self.min_frame_interval = Some(Duration::from_secs(1) / frames_per_second.max(1));
+    }
+
+    /// The inner backend, such as to exit its interface once the form completes.
+    pub fn inner_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Write any staged changes to the inner backend, regardless of the frame rate cap.
+    pub fn flush(&mut self) -> Result<()> {
+        for (&line, cells) in &self.staged {
+            let previous = self
+                .applied
+                .get(&line)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            write_changes(&mut self.inner, line, previous, cells);
+        }
+
+        // Lines no longer staged are cleared, having been entirely removed
+        for &line in self.applied.keys() {
+            if !self.staged.contains_key(&line) {
+                self.inner.clear_line(line);
+            }
+        }
+
+        self.inner.set_cursor(self.cursor);
+        self.inner.apply()?;
+
+        self.applied = self.staged.clone();
+        self.last_frame = Some(Instant::now());
+        self.deferred = false;
+
+        Ok(())
+    }
+
+    /// The staged cells for the specified line, created if it has none.
+    fn staged_line(&mut self, line: u16) -> &mut Vec<Cell> {
+        self.staged.entry(line).or_default()
+    }
+
+    /// Stage the specified text's cells from the specified position.
+    fn set_cells(&mut self, position: Position, text: &str, style: Option<Style>) {
+        let cells = self.staged_line(position.y());
+        let mut column = usize::from(position.x());
+        for character in text.chars() {
+            let width = character.width().unwrap_or_default();
+            if width == 0 {
+                continue;
+            }
+
+            let required = column + width;
+            if cells.len() < required {
+                cells.resize(
+                    required,
+                    Cell {
+                        character: Some(' '),
+                        style: None,
+                    },
+                );
+            }
+
+            cells[column] = Cell {
+                character: Some(character),
+                style,
+            };
+            for trailing in &mut cells[column + 1..required] {
+                *trailing = Cell {
+                    character: None,
+                    style,
+                };
+            }
+
+            column = required;
+        }
+    }
+
+    /// How long until the frame rate cap permits the next frame, if it's capped.
+    fn until_next_frame(&self) -> Option<Duration> {
+        let interval = self.min_frame_interval?;
+        let elapsed = self.last_frame.map_or(interval, |frame| frame.elapsed());
+        Some(interval.saturating_sub(elapsed))
+    }
+
+    /// Whether the frame rate cap doesn't yet permit another frame.
+    fn is_too_soon(&self) -> bool {
+        self.until_next_frame()
+            .is_some_and(|delay| !delay.is_zero())
+    }
+}
+
+/// Write the changed runs of cells in a line to the specified backend, clearing the remainder of
+/// the line only if the previous content was longer.
+fn write_changes<B: RenderBackend>(backend: &mut B, line: u16, previous: &[Cell], cells: &[Cell]) {
+    let mut column = 0;
+    while column < cells.len() {
+        if previous.get(column) == Some(&cells[column]) {
+            column += 1;
+            continue;
+        }
+
+        // Gather a run of changed cells sharing the same styling
+        let start = column;
+        let style = cells[start].style;
+        let mut text = String::new();
+        while column < cells.len()
+            && previous.get(column) != Some(&cells[column])
+            && cells[column].style == style
+        {
+            text.extend(cells[column].character);
+            column += 1;
+        }
+
+        let position = pos!(start as u16, line);
+        match style {
+            Some(style) => backend.set_styled(position, &text, style),
+            None => backend.set(position, &text),
+        }
+    }
+
+    if previous.len() > cells.len() {
+        backend.clear_rest_of_line(pos!(cells.len() as u16, line));
+    }
+}
+
+impl<B: RenderBackend> RenderBackend for LowBandwidthBackend<B> {
+    fn set(&mut self, position: Position, text: &str) {
+        self.set_cells(position, text, None);
+    }
+
+    fn set_styled(&mut self, position: Position, text: &str, style: Style) {
+        self.set_cells(position, text, Some(style));
+    }
+
+    fn clear_line(&mut self, line: u16) {
+        self.staged.remove(&line);
+    }
+
+    fn clear_rest_of_line(&mut self, from: Position) {
+        if let Some(cells) = self.staged.get_mut(&from.y()) {
+            cells.truncate(from.x().into());
+        }
+    }
+
+    fn set_cursor(&mut self, position: Option<Position>) {
+        self.cursor = position;
+    }
+
+    fn apply(&mut self) -> Result<()> {
+        if self.is_too_soon() {
+            self.deferred = true;
+            return Ok(());
+        }
+
+        self.flush()
+    }
+
+    fn width(&self) -> Option<u16> {
+        self.inner.width()
+    }
+
+    fn height(&self) -> Option<u16> {
+        self.inner.height()
+    }
+
+    fn resize(&mut self, columns: u16, rows: u16) {
+        self.inner.resize(columns, rows);
+    }
+
+    fn set_cursor_style(&mut self, style: Option<CursorStyle>) -> Result<()> {
+        self.inner.set_cursor_style(style)
+    }
+
+    fn bell(&mut self) -> Result<()> {
+        self.inner.bell()
+    }
+
+    fn frame_delay(&self) -> Option<Duration> {
+        if !self.deferred {
+            return None;
+        }
+
+        self.until_next_frame()
+    }
+}
+
+impl<B: RenderBackend> Drop for LowBandwidthBackend<B> {
+    fn drop(&mut self) {
+        // Deferred changes are written so the final frame isn't lost
+        if self.deferred {
+            let _ = self.flush();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tty_interface::{pos, Position, Style};
+
+    use super::{
+        CursorShape, CursorStyle, LowBandwidthBackend, RenderBackend, DEFAULT_CURSOR_STYLE,
+    };
+
+    #[test]
+    fn test_low_bandwidth_backend() {
+        /// A backend logging the operations written to it.
+        #[derive(Default)]
+        struct LoggingBackend(Vec<String>);
+
+        impl RenderBackend for LoggingBackend {
+            fn set(&mut self, position: Position, text: &str) {
+                self.0
+                    .push(format!("set {},{} {}", position.x(), position.y(), text));
+            }
+
+            fn set_styled(&mut self, position: Position, text: &str, _style: Style) {
+                self.set(position, text);
+            }
+
+            fn clear_line(&mut self, line: u16) {
+                self.0.push(format!("clear {}", line));
+            }
+
+            fn clear_rest_of_line(&mut self, from: Position) {
+                self.0.push(format!("clear {},{}", from.x(), from.y()));
+            }
+
+            fn set_cursor(&mut self, _position: Option<Position>) {}
+
+            fn apply(&mut self) -> crate::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut backend = LowBandwidthBackend::new(LoggingBackend::default());
+        backend.set(pos!(0, 0), "Hello, world!");
+        backend.set(pos!(0, 1), "Goodbye");
+        backend.apply().unwrap();
+
+        // Only the changed cells are written, and lines are only cleared where they've shrunk
+        backend.clear_line(0);
+        backend.set(pos!(0, 0), "Hello, there");
+        backend.clear_line(1);
+        backend.apply().unwrap();
+
+        assert_eq!(
+            vec![
+                "set 0,0 Hello, world!",
+                "set 0,1 Goodbye",
+                "set 7,0 there",
+                "clear 12,0",
+                "clear 1",
+            ],
+            backend.inner_mut().0
+        );
+
+        // Frames applied too soon after the last are deferred
+        backend.set_max_frame_rate(1);
+        backend.set(pos!(0, 0), "Hi");
+        backend.apply().unwrap();
+        assert_eq!(5, backend.inner_mut().0.len());
+        assert!(backend.frame_delay().is_some());
+
+        backend.flush().unwrap();
+        assert_eq!("set 1,0 i", backend.inner_mut().0[5]);
+    }
+
+    #[test]
+    fn test_cursor_style_escape_sequences() {
+        let sequences: Vec<String> = [
+            (CursorShape::Block, true),
+            (CursorShape::Block, false),
+            (CursorShape::Underline, true),
+            (CursorShape::Underline, false),
+            (CursorShape::Bar, true),
+            (CursorShape::Bar, false),
+        ]
+        .into_iter()
+        .map(|(shape, blinking)| CursorStyle::new(shape, blinking).escape_sequence())
+        .collect();
+
+        assert_eq!(
+            vec!["\x1b[1 q", "\x1b[2 q", "\x1b[3 q", "\x1b[4 q", "\x1b[5 q", "\x1b[6 q"],
+            sequences
+        );
+        assert_eq!("\x1b[0 q", DEFAULT_CURSOR_STYLE);
+    }
+}
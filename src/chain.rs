@@ -0,0 +1,236 @@
+//! Sequences of forms executed one after another, each built from the results of those before it.
+
+use tty_interface::{pos, Position};
+
+use crate::{
+    backend::RenderBackend, device::InputDevice, style::muted_style, Error, Form, FormResult,
+    FormStatus, Result,
+};
+
+/// The row at which a chain's forms are rendered, beneath its progress.
+const FORM_ROW: u16 = 1;
+
+/// Builds a chain's next form from the results of the forms completed before it.
+type FormBuilder = Box<dyn FnMut(&[FormResult]) -> Form>;
+
+/// A sequence of forms executed one after another. Each form is built when it's reached from the
+/// results of the forms before it, and retreating from a form's first step returns to the
+/// previous form.
+///
+/// # Examples
+/// ```
+/// # use tty_interface::{Interface, test::VirtualDevice};
+/// # use tty_form::{Error, test::VirtualInputDevice};
+/// # let mut device = VirtualDevice::new();
+/// # let mut interface = Interface::new_relative(&mut device)?;
+/// # let mut stdin = VirtualInputDevice;
+/// use tty_form::{
+///     Form, FormChain,
+///     step::{Step, CompoundStep, TextBlockStep},
+///     control::TextInput,
+/// };
+///
+/// let mut chain = FormChain::new();
+///
+/// chain.add(|_| {
+///     let mut form = Form::new();
+///
+///     let mut step = CompoundStep::new();
+///     let mut name = TextInput::new("Enter a name:", false);
+///     name.set_name("name");
+///     step.add(name);
+///     step.add_to(&mut form);
+///
+///     form
+/// });
+///
+/// chain.add(|completed| {
+///     let name = completed[0].values().get_text("name").unwrap_or_default();
+///
+///     let mut form = Form::new();
+///     TextBlockStep::new(&format!("Describe {}:", name)).add_to(&mut form);
+///     form
+/// });
+///
+/// let results = chain.execute(&mut interface, &mut stdin)?;
+/// # Ok::<(), Error>(())
+/// ```
+#[derive(Default)]
+pub struct FormChain {
+    builders: Vec<FormBuilder>,
+}
+
+impl FormChain {
+    /// Create a new, empty chain of forms.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a form to this chain, built from the results of the forms completed before it.
+    pub fn add(&mut self, builder: impl FnMut(&[FormResult]) -> Form + 'static) {
+        self.builders.push(Box::new(builder));
+    }
+
+    /// Execute this chain's forms in sequence and return each form's final results.
+    pub fn execute<B: RenderBackend, D: InputDevice>(
+        mut self,
+        interface: &mut B,
+        input_device: &mut D,
+    ) -> Result<Vec<FormResult>> {
+        if self.builders.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut forms = vec![self.build(0, &[], interface, input_device)?];
+
+        loop {
+            self.render_progress(interface, forms.len());
+
+            // Each form is driven by its own input loop, beneath the chain's progress
+            let current_form = forms.len() - 1;
+            match forms[current_form].drive(interface, input_device)? {
+                FormStatus::Submitted => {
+                    if forms.len() == self.builders.len() {
+                        break;
+                    }
+
+                    forms[current_form].clear_region(interface, pos!(0, FORM_ROW));
+
+                    let completed = complete(&forms);
+                    let form = self.build(forms.len(), &completed, interface, input_device)?;
+                    forms.push(form);
+                }
+                _ => {
+                    // Retreating from a form's first step returns to the previous form
+                    if !forms[current_form].retreated() || forms.len() == 1 {
+                        forms[current_form].cancel_form(interface)?;
+                        return Err(Error::Canceled);
+                    }
+
+                    forms[current_form].clear_region(interface, pos!(0, FORM_ROW));
+                    forms.pop();
+                }
+            }
+        }
+
+        let last_form = forms.len() - 1;
+        for form in &mut forms[..last_form] {
+            form.complete()?;
+        }
+        forms[last_form].finish(interface)?;
+
+        Ok(complete(&forms))
+    }
+
+    /// Build the chain's form at the specified index from the results of those before it, offering
+    /// to restore its autosaved state, if any.
+    fn build<B: RenderBackend, D: InputDevice>(
+        &mut self,
+        index: usize,
+        completed: &[FormResult],
+        interface: &mut B,
+        input_device: &mut D,
+    ) -> Result<Form> {
+        let mut form = (self.builders[index])(completed);
+        form.set_origin(pos!(0, FORM_ROW));
+        form.offer_autosave_restore(interface, input_device)?;
+
+        Ok(form)
+    }
+
+    /// Render the chain's progress above its current form.
+    fn render_progress(&self, interface: &mut dyn RenderBackend, part: usize) {
+        let progress = format!("Part {} of {}", part, self.builders.len());
+        interface.clear_line(0);
+        interface.set_styled(pos!(0, 0), &progress, muted_style());
+    }
+}
+
+/// Collect the final results of the specified forms.
+fn complete(forms: &[Form]) -> Vec<FormResult> {
+    forms.iter().map(Form::form_result).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        control::{Control, TextInput},
+        device::ChannelInputDevice,
+        input::{InputEvent, KeyCode, KeyEvent, KeyModifiers},
+        step::{CompoundStep, Step},
+        test::SizedBackend,
+        Form,
+    };
+
+    use super::FormChain;
+
+    fn text_form(name: &str) -> Form {
+        let mut form = Form::new();
+        let mut step = CompoundStep::new();
+        let mut input = TextInput::new("Enter a value.", false);
+        input.set_name(name);
+        input.add_to(&mut step);
+        step.add_to(&mut form);
+        form
+    }
+
+    fn key(code: KeyCode) -> InputEvent {
+        InputEvent::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn test_chain_retreats_to_previous_form() {
+        let mut chain = FormChain::new();
+        chain.add(|_| text_form("first"));
+        chain.add(|_| text_form("second"));
+
+        let (sender, mut device) = ChannelInputDevice::channel();
+        let codes = [
+            KeyCode::Char('a'),
+            KeyCode::Enter,
+            KeyCode::Esc,
+            KeyCode::Char('b'),
+            KeyCode::Enter,
+            KeyCode::Char('c'),
+            KeyCode::Enter,
+        ];
+        for code in codes {
+            sender.send(key(code)).unwrap();
+        }
+
+        let mut backend = SizedBackend::new(80, 24);
+        let results = chain.execute(&mut backend, &mut device).unwrap();
+
+        assert_eq!(Some("ab"), results[0].values().get_text("first"));
+        assert_eq!(Some("c"), results[1].values().get_text("second"));
+        assert_eq!("Part 2 of 2", backend.row(0));
+    }
+
+    #[test]
+    fn test_chain_forms_tick() {
+        // Each form is completed only by events injected as it ticks
+        let mut chain = FormChain::new();
+        chain.add(|_| {
+            let mut form = text_form("first");
+            for code in [KeyCode::Char('a'), KeyCode::Enter] {
+                form.inject_event(key(code));
+            }
+            form
+        });
+        chain.add(|completed| {
+            let mut form = text_form("second");
+            let first = completed[0].values().get_text("first").unwrap_or_default();
+            for ch in first.chars() {
+                form.inject_event(key(KeyCode::Char(ch)));
+            }
+            form.inject_event(key(KeyCode::Enter));
+            form
+        });
+
+        let (_sender, mut device) = ChannelInputDevice::channel();
+        let mut backend = SizedBackend::new(80, 24);
+        let results = chain.execute(&mut backend, &mut device).unwrap();
+
+        assert_eq!(Some("a"), results[1].values().get_text("second"));
+    }
+}
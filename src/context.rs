@@ -0,0 +1,46 @@
+//! Application data shared with a form's steps and controls, such as configuration, caches, or
+//! API clients.
+
+use std::{any::Any, rc::Rc};
+
+/// A form's application-provided data, available to its steps and controls as they're initialized.
+/// Cloning a context is cheap and shares its data, so steps and controls may keep a clone to reach
+/// the data while updating or rendering.
+///
+/// # Examples
+/// ```
+/// use tty_form::{context::Context, Form};
+///
+/// struct Config {
+///     scopes: Vec<String>,
+/// }
+///
+/// let mut form = Form::new();
+/// form.set_context(Config { scopes: vec!["api".to_string()] });
+///
+/// let config = form.context().get::<Config>().unwrap();
+/// assert_eq!(1, config.scopes.len());
+/// ```
+#[derive(Clone, Default)]
+pub struct Context {
+    data: Option<Rc<dyn Any>>,
+}
+
+impl Context {
+    /// Create a new context without any data.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a context with the specified data.
+    pub fn with<T: Any>(data: T) -> Self {
+        Self {
+            data: Some(Rc::new(data)),
+        }
+    }
+
+    /// This context's data, if it has data of the specified type.
+    pub fn get<T: Any>(&self) -> Option<&T> {
+        self.data.as_ref()?.downcast_ref()
+    }
+}
@@ -1,11 +1,29 @@
-use crossterm::event::KeyEvent;
+use std::{
+    any::Any,
+    marker::PhantomData,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
 
 use crate::{
+    context::Context,
     dependency::{Action, DependencyId, Evaluation},
+    history::History,
+    input::KeyEvent,
     step::CompoundStep,
     text::{DrawerContents, Segment},
+    value::ControlValue,
 };
 
+mod filter;
+pub use filter::*;
+
+mod functional;
+pub use functional::*;
+
+mod meta;
+pub use meta::*;
+
 mod selectinput;
 pub use selectinput::*;
 
@@ -15,23 +33,164 @@ pub use statictext::*;
 mod textinput;
 pub use textinput::*;
 
+mod transform;
+pub use transform::*;
+
+/// The placeholder standing in for sensitive values outside the form.
+pub(crate) const REDACTION: &str = "***";
+
 /// An element of a [CompoundStep] which may be a focusable input.
-pub trait Control {
+pub trait Control: Any {
+    /// This control's name, if specified, by which it may be retrieved from its step.
+    fn name(&self) -> Option<&str> {
+        None
+    }
+
+    /// Metadata identifying this control, such as its name and kind.
+    fn meta(&self) -> ControlMeta {
+        ControlMeta::new(ControlKind::Custom).with_name(self.name())
+    }
+
+    /// This control's current value, if it has one, as exposed to the form's value store.
+    fn form_value(&self) -> Option<ControlValue> {
+        None
+    }
+
+    /// Replace this control's value with the specified value, returning whether it was applied.
+    fn set_form_value(&mut self, _value: &ControlValue) -> bool {
+        false
+    }
+
+    /// Lock this control's value against editing, such as one provided by a command-line
+    /// override, so that focus passes over it. Returns whether the control supports locking.
+    fn lock(&mut self) -> bool {
+        false
+    }
+
     /// Whether this control is a focusable input.
     fn focusable(&self) -> bool;
 
+    /// Whether this control accepts typed text, such that the form's read-only cancel keys don't
+    /// apply while its step is active.
+    fn accepts_text(&self) -> bool {
+        false
+    }
+
+    /// Whether this control consumes Up and Down as input, such as to choose an option. If not,
+    /// they move focus between steps in a form's batch navigation mode.
+    fn captures_arrows(&self) -> bool {
+        false
+    }
+
+    /// Whether this control consumes Enter as input, such as for multi-line editing. If so, only
+    /// Tab advances past it.
+    fn captures_enter(&self) -> bool {
+        false
+    }
+
+    /// Perform any initialization actions for this control as its form begins. The form's context
+    /// is provided, which may be cloned and kept to reach application data.
+    fn initialize(&mut self, _context: &Context) {}
+
+    /// Restore this control's state from the values chosen in its form's previous executions,
+    /// such as to preselect its last choice. Called as the form begins, if it has history enabled.
+    fn recall(&mut self, _history: &History) {}
+
+    /// Record this control's value in its form's history, called once the form is submitted if it
+    /// has history enabled.
+    fn remember(&self, _history: &mut History) {}
+
     /// Updates the control's state from the given input event.
     fn update(&mut self, input: KeyEvent);
 
+    /// Called as this control loses focus, such as to normalize its value.
+    fn blur(&mut self) {}
+
+    /// Whether this control rejected input since last asked, such as a disallowed character,
+    /// clearing the rejection. The form may signal rejections to the user.
+    fn take_rejection(&mut self) -> bool {
+        false
+    }
+
+    /// Whether this control may be changed from outside its form through a handle, such that the
+    /// form periodically re-renders to reflect changes.
+    fn has_handle(&self) -> bool {
+        false
+    }
+
+    /// Updates the control's state as time passes, called each tick of its form's interval while
+    /// its step is focused.
+    fn tick(&mut self) {}
+
+    /// Signal cancellation of this control's running background task, if any, returning whether
+    /// its acknowledgement must be awaited before the step may retreat.
+    fn cancel(&mut self) -> bool {
+        false
+    }
+
+    /// Whether this control's background task has been cancelled but not yet acknowledged.
+    fn is_cancelling(&self) -> bool {
+        false
+    }
+
     /// This control's descriptive help text, if available.
     fn help(&self) -> Option<Segment>;
 
+    /// This control's extended documentation, if available, shown in a drawer when toggled.
+    fn long_help(&self) -> Option<DrawerContents> {
+        None
+    }
+
+    /// How often this control should be ticked and re-rendered without input, such as to rotate
+    /// example hints or collect validation results, if it should be.
+    fn refresh_interval(&self) -> Option<Duration> {
+        None
+    }
+
     /// This control's rendered contents and an optional offset for the cursor.
     fn text(&self) -> (Segment, Option<u16>);
 
+    /// An indicator rendered after this control's contents but excluded from its step's result,
+    /// such as its validation state.
+    fn indicator(&self) -> Option<Segment> {
+        None
+    }
+
+    /// An error preventing this control from being advanced past, such as a failed validation.
+    fn validation_error(&self) -> Option<String> {
+        None
+    }
+
+    /// This control's contribution to its step's result. Defaults to its rendered contents, but may
+    /// differ if they're masked, such as for sensitive values.
+    fn result_text(&self) -> String {
+        let (segment, _) = self.text();
+        segment.iter().map(|text| text.content()).collect()
+    }
+
+    /// This control's contribution to its step's result with any sensitive value masked, safe to
+    /// share outside the form such as in drafts.
+    fn redacted_result_text(&self) -> String {
+        let text = self.result_text();
+        if self.meta().sensitive() && !text.is_empty() {
+            return REDACTION.to_string();
+        }
+
+        text
+    }
+
     /// This control's drawer contents, if available.
     fn drawer(&self) -> Option<DrawerContents>;
 
+    /// Close this control's drawer if it's open, returning whether it was. An open drawer is closed
+    /// by Esc before the step may retreat.
+    fn close_drawer(&mut self) -> bool {
+        false
+    }
+
+    /// Re-open this control's drawer, if it has one, as the control regains focus.
+    fn open_drawer(&mut self) {}
+
     /// This control's dependency evaluation which other controls may react to.
     fn evaluation(&self) -> Option<(DependencyId, Evaluation)>;
 
@@ -44,3 +203,59 @@ pub trait Control {
     /// Finish configuration and add this control to the specified form step.
     fn add_to(self, step: &mut CompoundStep);
 }
+
+impl dyn Control {
+    /// Retrieve this control as its concrete type, if it is of the specified type.
+    pub fn downcast_ref<T: Control>(&self) -> Option<&T> {
+        (self as &dyn Any).downcast_ref()
+    }
+
+    /// Retrieve this control mutably as its concrete type, if it is of the specified type.
+    pub fn downcast_mut<T: Control>(&mut self) -> Option<&mut T> {
+        (self as &mut dyn Any).downcast_mut()
+    }
+}
+
+/// A unique control identifier.
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+pub struct ControlId(usize);
+
+/// The greatest control identifier provisioned thus far.
+static ID_VALUE: AtomicUsize = AtomicUsize::new(0);
+
+impl ControlId {
+    /// Create a new, unique control identifier.
+    pub(crate) fn new() -> Self {
+        Self(ID_VALUE.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// A typed reference to a control which has been added to a [CompoundStep], by which the control
+/// may be retrieved and updated after it's been added.
+pub struct ControlHandle<T: Control> {
+    id: ControlId,
+    control_type: PhantomData<fn() -> T>,
+}
+
+impl<T: Control> ControlHandle<T> {
+    /// Create a new handle for the control with the specified identifier.
+    pub(crate) fn new(id: ControlId) -> Self {
+        Self {
+            id,
+            control_type: PhantomData,
+        }
+    }
+
+    /// The referenced control's unique identifier.
+    pub fn id(&self) -> ControlId {
+        self.id
+    }
+}
+
+impl<T: Control> Clone for ControlHandle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Control> Copy for ControlHandle<T> {}
@@ -0,0 +1,145 @@
+use std::{fmt, sync::Arc};
+
+/// A predicate deciding whether a character is allowed.
+type CharPredicate = Arc<dyn Fn(char) -> bool + Send + Sync>;
+
+/// The characters a [super::TextInput] accepts. Disallowed characters are dropped as they're typed.
+///
+/// # Examples
+/// ```
+/// use tty_form::control::CharFilter;
+///
+/// let filter = CharFilter::class("a-z0-9_-");
+/// assert!(filter.allows('q') && filter.allows('-'));
+/// assert!(!filter.allows('Q'));
+///
+/// let filter = CharFilter::custom(|ch| ch != ' ');
+/// assert!(!filter.allows(' '));
+/// ```
+#[derive(Clone, Default)]
+pub enum CharFilter {
+    /// Any character.
+    #[default]
+    Any,
+    /// Letters and digits in any script.
+    Alphanumeric,
+    /// Letters and digits in any script, and underscores.
+    Identifier,
+    /// The ASCII digits 0 through 9.
+    Digits,
+    /// Characters within a regex-style character class.
+    Class(CharClass),
+    /// Characters accepted by an application-defined predicate.
+    Custom(CharPredicate),
+}
+
+impl CharFilter {
+    /// A filter accepting the characters of a regex-style character class, excluding its brackets,
+    /// such as `a-z0-9_` or `^ ` for anything but spaces.
+    pub fn class(class: &str) -> Self {
+        Self::Class(CharClass::parse(class))
+    }
+
+    /// A filter accepting the characters for which the predicate returns true.
+    pub fn custom(predicate: impl Fn(char) -> bool + Send + Sync + 'static) -> Self {
+        Self::Custom(Arc::new(predicate))
+    }
+
+    /// Whether this filter accepts the specified character.
+    pub fn allows(&self, ch: char) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Alphanumeric => ch.is_alphanumeric(),
+            Self::Identifier => ch.is_alphanumeric() || ch == '_',
+            Self::Digits => ch.is_ascii_digit(),
+            Self::Class(class) => class.contains(ch),
+            Self::Custom(predicate) => predicate(ch),
+        }
+    }
+}
+
+impl fmt::Debug for CharFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Any => write!(f, "Any"),
+            Self::Alphanumeric => write!(f, "Alphanumeric"),
+            Self::Identifier => write!(f, "Identifier"),
+            Self::Digits => write!(f, "Digits"),
+            Self::Class(class) => f.debug_tuple("Class").field(class).finish(),
+            Self::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+/// A parsed regex-style character class: a set of characters and inclusive ranges, optionally
+/// negated by a leading `^`. A `-` at either end of the class, or escaped, is a literal.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CharClass {
+    negated: bool,
+    ranges: Vec<(char, char)>,
+}
+
+impl CharClass {
+    /// Parse the specified class, excluding its brackets.
+    pub fn parse(class: &str) -> Self {
+        let mut chars = class.chars().peekable();
+        let negated = chars.next_if_eq(&'^').is_some();
+
+        let mut ranges = Vec::new();
+        while let Some(mut start) = chars.next() {
+            if start == '\\' {
+                start = chars.next().unwrap_or('\\');
+            }
+
+            let mut end = start;
+            if chars.next_if_eq(&'-').is_some() {
+                match chars.next() {
+                    Some('\\') => end = chars.next().unwrap_or('\\'),
+                    Some(ch) => end = ch,
+                    None => ranges.push(('-', '-')),
+                }
+            }
+
+            ranges.push((start.min(end), start.max(end)));
+        }
+
+        Self { negated, ranges }
+    }
+
+    /// Whether this class contains the specified character.
+    pub fn contains(&self, ch: char) -> bool {
+        let listed = self
+            .ranges
+            .iter()
+            .any(|&(start, end)| (start..=end).contains(&ch));
+
+        listed != self.negated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        control::{Control, TextInput},
+        input::{KeyCode, KeyEvent, KeyModifiers},
+    };
+
+    use super::CharFilter;
+
+    #[test]
+    fn test_allowed_chars() {
+        let mut input = TextInput::new("Enter a version.", true);
+        input.set_allowed_chars(CharFilter::class("a-z0-9.\\-"));
+        for ch in "V1.2-Beta 3!".chars() {
+            input.update(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+        }
+        assert_eq!("v1.2-beta3", input.value());
+
+        let class = CharFilter::class("^ -");
+        assert!(class.allows('a'));
+        assert!(!class.allows(' ') && !class.allows('-'));
+
+        assert!(CharFilter::Identifier.allows('é') && CharFilter::Identifier.allows('_'));
+        assert!(!CharFilter::Digits.allows('a'));
+    }
+}
@@ -0,0 +1,232 @@
+use crate::{
+    dependency::{evaluate_value, Action, DependencyId, Evaluation},
+    input::KeyEvent,
+    step::CompoundStep,
+    style::help_style,
+    text::{DrawerContents, Segment, Text},
+    value::ControlValue,
+};
+
+use super::Control;
+
+/// Renders a functional control's state as its contents and an optional cursor offset.
+pub type RenderFn<S> = Box<dyn Fn(&S) -> (Segment, Option<u16>)>;
+
+/// Updates a functional control's state from an input event.
+pub type UpdateFn<S> = Box<dyn FnMut(&mut S, KeyEvent)>;
+
+/// Derives a functional control's form value from its state.
+pub type ValueFn<S> = Box<dyn Fn(&S) -> Option<ControlValue>>;
+
+/// A one-off custom control defined inline from closures over its state, rather than by
+/// implementing [Control]. The control is focusable if it has an update closure.
+///
+/// # Examples
+/// ```
+/// use tty_form::{
+///     step::CompoundStep,
+///     control::{Control, FunctionalControl},
+///     input::KeyCode,
+///     text::Text,
+///     value::ControlValue,
+/// };
+///
+/// let mut counter = FunctionalControl::new(0, |count: &i32| {
+///     (Text::new(format!("Count: {}", count)).as_segment(), None)
+/// });
+/// counter.set_update_fn(|count, input| match input.code {
+///     KeyCode::Up => *count += 1,
+///     KeyCode::Down => *count -= 1,
+///     _ => {}
+/// });
+/// counter.set_value_fn(|count| Some(ControlValue::Text(count.to_string())));
+/// counter.set_name("count");
+///
+/// let mut step = CompoundStep::new();
+/// counter.add_to(&mut step);
+/// ```
+pub struct FunctionalControl<S: 'static> {
+    name: Option<String>,
+    help: Option<String>,
+    state: S,
+    render_fn: RenderFn<S>,
+    update_fn: Option<UpdateFn<S>>,
+    value_fn: Option<ValueFn<S>>,
+}
+
+impl<S: 'static> FunctionalControl<S> {
+    /// Create a new control with the specified initial state, rendered by the specified closure.
+    pub fn new(state: S, render_fn: impl Fn(&S) -> (Segment, Option<u16>) + 'static) -> Self {
+        Self {
+            name: None,
+            help: None,
+            state,
+            render_fn: Box::new(render_fn),
+            update_fn: None,
+            value_fn: None,
+        }
+    }
+
+    /// Set this control's name, by which it may be retrieved from its step or form.
+    pub fn set_name(&mut self, name: &str) {
+        self.name = Some(name.to_string());
+    }
+
+    /// Set this control's help text.
+    pub fn set_help(&mut self, help: &str) {
+        self.help = Some(help.to_string());
+    }
+
+    /// Set the closure updating this control's state from input, making it focusable.
+    pub fn set_update_fn(&mut self, update_fn: impl FnMut(&mut S, KeyEvent) + 'static) {
+        self.update_fn = Some(Box::new(update_fn));
+    }
+
+    /// Set the closure deriving this control's form value from its state.
+    pub fn set_value_fn(&mut self, value_fn: impl Fn(&S) -> Option<ControlValue> + 'static) {
+        self.value_fn = Some(Box::new(value_fn));
+    }
+
+    /// This control's current state.
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    /// This control's current state, mutably.
+    pub fn state_mut(&mut self) -> &mut S {
+        &mut self.state
+    }
+
+    /// This control's textual form value, if it has one.
+    fn text_value(&self) -> Option<String> {
+        self.form_value()
+            .and_then(|value| value.as_text().map(str::to_string))
+    }
+}
+
+impl<S: 'static> Control for FunctionalControl<S> {
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    fn form_value(&self) -> Option<ControlValue> {
+        self.value_fn
+            .as_ref()
+            .and_then(|value_fn| value_fn(&self.state))
+    }
+
+    fn focusable(&self) -> bool {
+        self.update_fn.is_some()
+    }
+
+    fn captures_arrows(&self) -> bool {
+        self.update_fn.is_some()
+    }
+
+    fn accepts_text(&self) -> bool {
+        self.update_fn.is_some()
+    }
+
+    fn update(&mut self, input: KeyEvent) {
+        if let Some(update_fn) = &mut self.update_fn {
+            update_fn(&mut self.state, input);
+        }
+    }
+
+    fn help(&self) -> Option<Segment> {
+        let help = self.help.clone()?;
+        Some(Text::new_styled(help, help_style()).as_segment())
+    }
+
+    fn text(&self) -> (Segment, Option<u16>) {
+        (self.render_fn)(&self.state)
+    }
+
+    fn drawer(&self) -> Option<DrawerContents> {
+        None
+    }
+
+    fn evaluation(&self) -> Option<(DependencyId, Evaluation)> {
+        None
+    }
+
+    fn dependency(&self) -> Option<(DependencyId, Action)> {
+        None
+    }
+
+    fn evaluate(&self, evaluation: &Evaluation) -> bool {
+        evaluate_value(&self.text_value().unwrap_or_default(), evaluation)
+    }
+
+    fn add_to(self, step: &mut CompoundStep) {
+        step.add_control(Box::new(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        control::{Control, TextInput},
+        input::KeyCode,
+        step::{CompoundStep, Step},
+        test::{press, type_text},
+        text::Text,
+        value::ControlValue,
+        Form,
+    };
+
+    use super::FunctionalControl;
+
+    fn counter() -> FunctionalControl<i32> {
+        let mut counter = FunctionalControl::new(0, |count: &i32| {
+            (Text::new(format!("Count: {}", count)).as_segment(), None)
+        });
+        counter.set_update_fn(|count, input| match input.code {
+            KeyCode::Up => *count += 1,
+            KeyCode::Down => *count -= 1,
+            _ => {}
+        });
+        counter.set_value_fn(|count| Some(ControlValue::Text(count.to_string())));
+        counter.set_name("count");
+        counter
+    }
+
+    #[test]
+    fn test_functional_control() {
+        let mut form = Form::new();
+        let mut step = CompoundStep::new();
+        counter().add_to(&mut step);
+        step.add_to(&mut form);
+
+        assert_eq!("Count: 0", form.result());
+        assert_eq!(Some("0"), form.values().get_text("count"));
+
+        press(&mut form, KeyCode::Up);
+        press(&mut form, KeyCode::Up);
+        press(&mut form, KeyCode::Down);
+        assert_eq!("Count: 1", form.result());
+        assert_eq!(Some("1"), form.values().get_text("count"));
+    }
+
+    #[test]
+    fn test_functional_control_without_update() {
+        let mut label = FunctionalControl::new(String::from("Label: "), |label: &String| {
+            (Text::new(label.clone()).as_segment(), None)
+        });
+        assert!(!label.focusable());
+        assert_eq!(None, label.form_value());
+
+        label.state_mut().push_str("Name: ");
+        assert_eq!("Label: Name: ", label.state());
+
+        // Without an update closure, the control is skipped as focus moves through its step
+        let mut form = Form::new();
+        let mut step = CompoundStep::new();
+        label.add_to(&mut step);
+        TextInput::new("Enter a name.", false).add_to(&mut step);
+        step.add_to(&mut form);
+
+        type_text(&mut form, "Daniel");
+        assert_eq!("Label: Name: Daniel", form.result());
+    }
+}
@@ -0,0 +1,86 @@
+/// The kind of a control, identifying how its value is entered.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum ControlKind {
+    /// Free text entry, such as a [super::TextInput].
+    Text,
+    /// Selection of one of several options, such as a [super::SelectInput].
+    Select,
+    /// Unfocusable display content, such as a [super::StaticText].
+    Static,
+    /// An application-defined control.
+    #[default]
+    Custom,
+}
+
+/// Metadata identifying a control consistently across integrations, such as structured results
+/// and the debug overlay.
+///
+/// # Examples
+/// ```
+/// use tty_form::control::{Control, ControlKind, ControlMeta, TextInput};
+///
+/// let mut input = TextInput::new("Enter your password:", false);
+/// input.set_name("password");
+/// input.set_required(true);
+/// input.set_sensitive(true);
+///
+/// let meta = input.meta();
+/// assert_eq!(Some("password"), meta.name());
+/// assert_eq!(ControlKind::Text, meta.kind());
+/// assert!(meta.required() && meta.sensitive());
+/// ```
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct ControlMeta {
+    name: Option<String>,
+    kind: ControlKind,
+    required: bool,
+    sensitive: bool,
+}
+
+impl ControlMeta {
+    /// Create metadata for an unnamed, optional, non-sensitive control of the specified kind.
+    pub fn new(kind: ControlKind) -> Self {
+        Self {
+            kind,
+            ..Self::default()
+        }
+    }
+
+    /// This metadata with the control's name replaced.
+    pub fn with_name(mut self, name: Option<&str>) -> Self {
+        self.name = name.map(str::to_string);
+        self
+    }
+
+    /// This metadata with whether the control requires a value replaced.
+    pub fn with_required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
+    /// This metadata with whether the control's value is secret replaced.
+    pub fn with_sensitive(mut self, sensitive: bool) -> Self {
+        self.sensitive = sensitive;
+        self
+    }
+
+    /// The control's name, if it has one.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// The control's kind.
+    pub fn kind(&self) -> ControlKind {
+        self.kind
+    }
+
+    /// Whether the control requires a value before it may be advanced past.
+    pub fn required(&self) -> bool {
+        self.required
+    }
+
+    /// Whether the control's value is secret and should be redacted outside the form.
+    pub fn sensitive(&self) -> bool {
+        self.sensitive
+    }
+}
@@ -1,13 +1,37 @@
-use crossterm::event::{KeyCode, KeyEvent};
+use std::{
+    sync::mpsc::{channel, Receiver, TryRecvError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use unicode_width::UnicodeWidthStr;
 
 use crate::{
-    dependency::{Action, DependencyId, Evaluation},
+    dependency::{evaluate_value, Action, DependencyId, Evaluation},
+    history::History,
+    input::{KeyCode, KeyEvent},
     step::CompoundStep,
-    style::{drawer_selected_style, drawer_style, help_style},
-    text::{DrawerContents, Segment, Text},
+    style::{drawer_selected_style, drawer_style, help_style, muted_style},
+    text::{join_columns, DrawerContents, Segment, Text},
+    value::ControlValue,
 };
 
-use super::Control;
+use super::{Control, ControlKind, ControlMeta};
+
+/// The maximum interval between navigation events for them to be considered a held key's repeats.
+const REPEAT_INTERVAL: Duration = Duration::from_millis(80);
+
+/// The number of consecutive repeats after which each navigation skips one additional option.
+const REPEATS_PER_ACCELERATION: usize = 5;
+
+/// The most options a single accelerated navigation event may skip.
+const MAX_NAVIGATION_STEP: usize = 10;
+
+/// The default glyphs separating each drawer option's value from its description.
+const DEFAULT_SEPARATOR: &str = " - ";
+
+/// How often options loading in the background are checked for.
+const LOADING_POLL_INTERVAL: Duration = Duration::from_millis(50);
 
 /// An option selection field.
 ///
@@ -28,21 +52,84 @@ use super::Control;
 /// ]).add_to(&mut step);
 /// ```
 pub struct SelectInput {
+    name: Option<String>,
     prompt: String,
+    long_help: Option<DrawerContents>,
     options: Vec<SelectInputOption>,
     selected_option: usize,
+    drawer_open: bool,
+    last_navigation: Option<(KeyCode, Instant)>,
+    repeat_count: usize,
+    loading: Option<Receiver<Vec<SelectInputOption>>>,
+    pending_value: Option<String>,
+    detail_layout: DetailLayout,
+    sticky: bool,
+    locked: bool,
+    ordering: OptionOrdering,
+    recent_values: Vec<String>,
+    recent_count: usize,
+    separator: String,
+    item_renderer: Option<ItemRenderer>,
 }
 
 impl SelectInput {
     /// Create a new option-selection input with the specified prompt and options.
     pub fn new(prompt: &str, options: Vec<(&str, &str)>) -> Self {
         Self {
+            name: None,
             prompt: prompt.to_string(),
+            long_help: None,
             options: options
                 .iter()
                 .map(|(value, description)| SelectInputOption::new(value, description))
                 .collect(),
             selected_option: 0,
+            drawer_open: true,
+            last_navigation: None,
+            repeat_count: 0,
+            loading: None,
+            pending_value: None,
+            detail_layout: DetailLayout::default(),
+            sticky: true,
+            locked: false,
+            ordering: OptionOrdering::default(),
+            recent_values: Vec::new(),
+            recent_count: 0,
+            separator: DEFAULT_SEPARATOR.to_string(),
+            item_renderer: None,
+        }
+    }
+
+    /// Set this input's name, by which it may be retrieved from its step or form.
+    pub fn set_name(&mut self, name: &str) {
+        self.name = Some(name.to_string());
+    }
+
+    /// Set this input's extended documentation, such as examples, shown in a drawer when the
+    /// form's help key is pressed.
+    pub fn set_long_help(&mut self, long_help: Vec<Segment>) {
+        self.long_help = Some(long_help);
+    }
+
+    /// The currently-selected option's value.
+    pub fn value(&self) -> &str {
+        self.selected_option_value()
+    }
+
+    /// Select the option with the specified value. Returns whether a matching option was found. If
+    /// options are still loading, the value is selected once they load if it matches one.
+    pub fn set_value(&mut self, value: &str) -> bool {
+        if self.is_loading() {
+            self.pending_value = Some(value.to_string());
+            return true;
+        }
+
+        match self.options.iter().position(|option| option.value == value) {
+            Some(option_index) => {
+                self.selected_option = option_index;
+                true
+            }
+            None => false,
         }
     }
 
@@ -61,28 +148,237 @@ impl SelectInput {
         self.options = options;
     }
 
+    /// Set where the highlighted option's details, if it has any, are shown relative to the list.
+    pub fn set_detail_layout(&mut self, layout: DetailLayout) {
+        self.detail_layout = layout;
+    }
+
+    /// Set whether this input opens with the option last chosen for it preselected, if its form has
+    /// history enabled and it's named. Enabled by default.
+    pub fn set_sticky(&mut self, sticky: bool) {
+        self.sticky = sticky;
+    }
+
+    /// Set the glyphs separating each option's value from its description in the drawer, such as
+    /// `" │ "`. Defaults to `" - "`.
+    pub fn set_separator(&mut self, separator: &str) {
+        self.separator = separator.to_string();
+    }
+
+    /// Render each of this input's drawer rows with the specified function, given the option,
+    /// whether it's selected, and its index in the list, in place of the default value and
+    /// description row.
+    ///
+    /// # Examples
+    /// ```
+    /// use tty_form::{control::SelectInput, style::{style, Color}, text::Text};
+    ///
+    /// let mut select = SelectInput::new("Select a branch:", vec![("main", ""), ("dev", "")]);
+    /// select.set_item_renderer(|option, is_selected, _index| {
+    ///     let marker = if is_selected { " ● " } else { " ○ " };
+    ///     vec![
+    ///         Text::new_styled(marker.to_string(), style().fg(Color::Green).build()),
+    ///         Text::new(option.value().to_string()),
+    ///     ]
+    /// });
+    /// ```
+    pub fn set_item_renderer(
+        &mut self,
+        renderer: impl Fn(&SelectInputOption, bool, usize) -> Segment + 'static,
+    ) {
+        self.item_renderer = Some(Box::new(renderer));
+    }
+
+    /// Set how this input's options are ordered by the choices made in its form's previous
+    /// executions, if its form has history enabled and it's named.
+    pub fn set_ordering(&mut self, ordering: OptionOrdering) {
+        self.ordering = ordering;
+    }
+
+    /// Load this input's options by running the specified provider on a background thread, such as
+    /// to fetch them remotely. Until they're loaded, the input's drawer indicates that it's loading
+    /// and it may not be advanced past.
+    pub fn load_options(
+        &mut self,
+        provider: impl FnOnce() -> Vec<SelectInputOption> + Send + 'static,
+    ) {
+        let (sender, receiver) = channel();
+        thread::spawn(move || {
+            let _ = sender.send(provider());
+        });
+
+        self.options.clear();
+        self.selected_option = 0;
+        self.recent_count = 0;
+        self.loading = Some(receiver);
+    }
+
+    /// Whether this input's options are still loading in the background.
+    pub fn is_loading(&self) -> bool {
+        self.loading.is_some()
+    }
+
+    /// Swap in this input's options if they've finished loading.
+    fn receive_options(&mut self) {
+        let options = match &self.loading {
+            Some(receiver) => match receiver.try_recv() {
+                Ok(options) => options,
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => Vec::new(),
+            },
+            None => return,
+        };
+
+        self.loading = None;
+        self.options = options;
+        self.selected_option = 0;
+        self.order_options();
+
+        if let Some(value) = self.pending_value.take() {
+            self.set_value(&value);
+        }
+    }
+
+    /// Move the options previously chosen, per this input's ordering, ahead of the rest.
+    fn order_options(&mut self) {
+        let mut recent = Vec::new();
+        for value in &self.recent_values {
+            if let Some(index) = self
+                .options
+                .iter()
+                .position(|option| &option.value == value)
+            {
+                recent.push(self.options.remove(index));
+            }
+        }
+
+        self.recent_count = recent.len();
+        self.options.splice(0..0, recent);
+    }
+
+    /// The number of options to move for a navigation event, accelerating as the key is held.
+    fn navigation_step(&mut self, code: KeyCode) -> usize {
+        let now = Instant::now();
+
+        let is_repeat = match self.last_navigation {
+            Some((last_code, last_time)) => {
+                last_code == code && now.duration_since(last_time) <= REPEAT_INTERVAL
+            }
+            None => false,
+        };
+
+        self.repeat_count = if is_repeat { self.repeat_count + 1 } else { 0 };
+        self.last_navigation = Some((code, now));
+
+        (1 + self.repeat_count / REPEATS_PER_ACCELERATION).min(MAX_NAVIGATION_STEP)
+    }
+
     /// The currently-selected option's value.
     fn selected_option_value(&self) -> &str {
-        &self.options[self.selected_option].value
+        self.options
+            .get(self.selected_option)
+            .map_or("", |option| option.value.as_str())
     }
 }
 
 impl Control for SelectInput {
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    fn meta(&self) -> ControlMeta {
+        ControlMeta::new(ControlKind::Select).with_name(self.name())
+    }
+
+    fn form_value(&self) -> Option<ControlValue> {
+        Some(ControlValue::Text(self.value().to_string()))
+    }
+
+    fn set_form_value(&mut self, value: &ControlValue) -> bool {
+        match value.as_text() {
+            Some(text) => self.set_value(text),
+            None => false,
+        }
+    }
+
+    fn lock(&mut self) -> bool {
+        self.locked = true;
+        true
+    }
+
+    fn recall(&mut self, history: &History) {
+        let name = match &self.name {
+            Some(name) => name.clone(),
+            None => return,
+        };
+
+        let mut entries = history.entries(&name).to_vec();
+        let limit = match self.ordering {
+            OptionOrdering::Declared => 0,
+            OptionOrdering::MostRecent(limit) => limit,
+            OptionOrdering::MostFrequent(limit) => {
+                // Ties keep their recency order, since the sort is stable
+                entries.sort_by_key(|entry| std::cmp::Reverse(entry.count()));
+                limit
+            }
+        };
+
+        self.recent_values = entries
+            .iter()
+            .take(limit)
+            .map(|entry| entry.value().to_string())
+            .collect();
+        self.order_options();
+
+        if let Some(value) = history.last(&name).filter(|_| self.sticky) {
+            self.set_value(value);
+        }
+    }
+
+    fn remember(&self, history: &mut History) {
+        if let Some(name) = self.name() {
+            if self.sticky && !self.is_loading() {
+                history.record(name, self.value());
+            }
+        }
+    }
+
     fn focusable(&self) -> bool {
+        !self.locked
+    }
+
+    fn captures_arrows(&self) -> bool {
         true
     }
 
     fn update(&mut self, input: KeyEvent) {
+        if input.code == KeyCode::Up || input.code == KeyCode::Down {
+            self.drawer_open = true;
+        }
+
+        if self.options.is_empty() {
+            return;
+        }
+
         match input.code {
             KeyCode::Up => {
-                if self.selected_option == 0 {
+                let step = self.navigation_step(input.code);
+                if step > 1 {
+                    // Accelerated navigation stops at the first option rather than wrapping
+                    self.selected_option = self.selected_option.saturating_sub(step);
+                } else if self.selected_option == 0 {
                     self.selected_option = self.options.len() - 1;
                 } else {
                     self.selected_option -= 1;
                 }
             }
             KeyCode::Down => {
-                if self.selected_option + 1 == self.options.len() {
+                let step = self.navigation_step(input.code);
+                if step > 1 {
+                    // Accelerated navigation stops at the last option rather than wrapping
+                    self.selected_option =
+                        (self.selected_option + step).min(self.options.len() - 1);
+                } else if self.selected_option + 1 == self.options.len() {
                     self.selected_option = 0;
                 } else {
                     self.selected_option += 1;
@@ -96,6 +392,23 @@ impl Control for SelectInput {
         Some(Text::new_styled(self.prompt.clone(), help_style()).as_segment())
     }
 
+    fn long_help(&self) -> Option<DrawerContents> {
+        self.long_help.clone()
+    }
+
+    fn tick(&mut self) {
+        self.receive_options();
+    }
+
+    fn refresh_interval(&self) -> Option<Duration> {
+        self.is_loading().then_some(LOADING_POLL_INTERVAL)
+    }
+
+    fn validation_error(&self) -> Option<String> {
+        self.is_loading()
+            .then(|| "Waiting for options to load…".to_string())
+    }
+
     fn text(&self) -> (Segment, Option<u16>) {
         let value = self.selected_option_value();
         let segment = Text::new(value.to_string()).as_segment();
@@ -104,13 +417,46 @@ impl Control for SelectInput {
     }
 
     fn drawer(&self) -> Option<DrawerContents> {
+        if !self.drawer_open {
+            return None;
+        }
+
+        if self.is_loading() {
+            let text = Text::new_styled("   loading…".to_string(), muted_style());
+            return Some(vec![text.as_segment()]);
+        }
+
         let mut items = Vec::new();
 
+        // Values are padded to the widest so descriptions align in a column
+        let value_width = self
+            .options
+            .iter()
+            .map(|option| option.value.width())
+            .max()
+            .unwrap_or(0);
+
         for (option_index, option) in self.options.iter().enumerate() {
-            let mut text = format!("   {} - {}", option.value, option.description);
+            // Previously-chosen options are separated from the rest
+            if option_index == self.recent_count && option_index > 0 {
+                let divider = Text::new_styled("   ───".to_string(), muted_style());
+                items.push(divider.as_segment());
+            }
+
+            let is_selected = option_index == self.selected_option;
+            if let Some(renderer) = &self.item_renderer {
+                items.push(renderer(option, is_selected, option_index));
+                continue;
+            }
+
+            let padding = " ".repeat(value_width - option.value.width());
+            let mut text = format!(
+                "   {}{}{}{}",
+                option.value, padding, self.separator, option.description
+            );
             let mut style = drawer_style();
 
-            if option_index == self.selected_option {
+            if is_selected {
                 style = drawer_selected_style();
                 text.replace_range(1..2, ">");
             }
@@ -118,9 +464,43 @@ impl Control for SelectInput {
             items.push(Text::new_styled(text, style).as_segment());
         }
 
+        // Show the highlighted option's details in a panel alongside the list
+        let details = self
+            .options
+            .get(self.selected_option)
+            .and_then(|option| option.details.as_ref());
+
+        if let Some(details) = details {
+            match self.detail_layout {
+                DetailLayout::Beside => {
+                    let divider = Text::new_styled(" │ ".to_string(), muted_style());
+                    items = join_columns(&items, details, divider);
+                }
+                DetailLayout::Below => {
+                    let divider = Text::new_styled("   ───".to_string(), muted_style());
+                    items.push(divider.as_segment());
+                    items.extend(details.iter().map(|line| {
+                        let mut indented = Text::new("   ".to_string()).as_segment();
+                        indented.extend(line.iter().cloned());
+                        indented
+                    }));
+                }
+            }
+        }
+
         Some(items)
     }
 
+    fn close_drawer(&mut self) -> bool {
+        let was_open = self.drawer_open;
+        self.drawer_open = false;
+        was_open
+    }
+
+    fn open_drawer(&mut self) {
+        self.drawer_open = true;
+    }
+
     fn evaluation(&self) -> Option<(DependencyId, Evaluation)> {
         None
     }
@@ -130,11 +510,7 @@ impl Control for SelectInput {
     }
 
     fn evaluate(&self, evaluation: &Evaluation) -> bool {
-        match evaluation {
-            Evaluation::Equal(value) => self.selected_option_value() == value,
-            Evaluation::NotEqual(value) => self.selected_option_value() != value,
-            Evaluation::IsEmpty => false,
-        }
+        evaluate_value(self.selected_option_value(), evaluation)
     }
 
     fn add_to(self, step: &mut CompoundStep) {
@@ -142,10 +518,52 @@ impl Control for SelectInput {
     }
 }
 
+/// A function rendering a select input's drawer row for an option, given whether it's selected and
+/// its index in the list.
+type ItemRenderer = Box<dyn Fn(&SelectInputOption, bool, usize) -> Segment>;
+
+/// Where a select input's highlighted option details are shown relative to its option list.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum DetailLayout {
+    /// In a panel to the right of the option list.
+    #[default]
+    Beside,
+    /// In a panel below the option list.
+    Below,
+}
+
+/// How a select input's options are ordered by the choices made in its form's previous executions.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum OptionOrdering {
+    /// In the order they were declared.
+    #[default]
+    Declared,
+    /// Up to the specified number of most-recently chosen options first, separated from the rest.
+    MostRecent(usize),
+    /// Up to the specified number of most-frequently chosen options first, separated from the
+    /// rest.
+    MostFrequent(usize),
+}
+
 /// A option for an option selection input.
+///
+/// # Examples
+/// ```
+/// use tty_form::{
+///     control::SelectInputOption,
+///     text::Text,
+/// };
+///
+/// let mut option = SelectInputOption::new("axum", "A web service.");
+/// option.set_details(vec![
+///     Text::new("Includes a router and a health check.".to_string()).as_segment(),
+///     Text::new("Requires a Tokio runtime.".to_string()).as_segment(),
+/// ]);
+/// ```
 pub struct SelectInputOption {
     value: String,
     description: String,
+    details: Option<Vec<Segment>>,
 }
 
 impl SelectInputOption {
@@ -154,9 +572,20 @@ impl SelectInputOption {
         Self {
             value: value.to_string(),
             description: description.to_string(),
+            details: None,
         }
     }
 
+    /// Set this option's multi-line, styled details, shown in a panel while it's highlighted.
+    pub fn set_details(&mut self, details: Vec<Segment>) {
+        self.details = Some(details);
+    }
+
+    /// This option's details, if it has any.
+    pub fn details(&self) -> Option<&[Segment]> {
+        self.details.as_deref()
+    }
+
     /// This option's value.
     pub fn value(&self) -> &str {
         &self.value
@@ -167,3 +596,275 @@ impl SelectInputOption {
         &self.description
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::mpsc::channel,
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use tty_interface::Interface;
+    use unicode_width::UnicodeWidthStr;
+
+    use crate::{
+        control::{Control, TextInput},
+        history::History,
+        input::{InputEvent, KeyCode, KeyEvent, KeyModifiers},
+        step::{CompoundStep, Step},
+        storage::{FileStorage, MemoryStorage, StorageBackend},
+        test::{press, render, type_text, VirtualInputDevice, VirtualTerminal},
+        text::Text,
+        Form, FormStatus,
+    };
+
+    use super::{OptionOrdering, SelectInput, SelectInputOption};
+
+    #[test]
+    fn test_sticky_select_history() {
+        let directory = std::env::temp_dir().join("tty-form-sticky-select-test");
+        let _ = std::fs::remove_dir_all(&directory);
+
+        let mut history = History::new();
+        history.record("type", "fix");
+        history.record("scope", "ui");
+        let mut storage = FileStorage::new(&directory);
+        storage.save("history", &history.serialize()).unwrap();
+
+        let build_form = || {
+            let mut form = Form::new();
+            let mut step = CompoundStep::new();
+            let mut select = SelectInput::new("Type:", vec![("feat", ""), ("fix", "")]);
+            select.set_name("type");
+            select.add_to(&mut step);
+            let mut select = SelectInput::new("Scope:", vec![("api", ""), ("ui", "")]);
+            select.set_name("scope");
+            select.set_sticky(false);
+            select.add_to(&mut step);
+            step.add_to(&mut form);
+            form.set_history(FileStorage::new(&directory), "history")
+                .unwrap();
+            form
+        };
+
+        // The last choice is preselected, other than for the opted-out control
+        let form = build_form();
+        let mut terminal = VirtualTerminal::new();
+        let mut interface = Interface::new_relative(&mut terminal).unwrap();
+        let result = form.execute(&mut interface, &mut VirtualInputDevice);
+        assert_eq!("fixapi", result.unwrap());
+
+        // Submitted choices are recorded
+        let saved = storage.load("history").unwrap().unwrap();
+        let history = History::deserialize(&saved).unwrap();
+        assert_eq!(2, history.count("type", "fix"));
+        assert_eq!(1, history.count("scope", "ui"));
+
+        let _ = std::fs::remove_dir_all(&directory);
+    }
+
+    #[test]
+    fn test_frequent_select_ordering() {
+        let mut history = History::new();
+        for value in ["fix", "docs", "fix"] {
+            history.record("type", value);
+        }
+        let mut storage = MemoryStorage::new();
+        storage.save("history", &history.serialize()).unwrap();
+
+        let mut form = Form::new();
+        let mut step = CompoundStep::new();
+        let options = vec![("feat", "A feature."), ("fix", "A fix."), ("docs", "Docs.")];
+        let mut select = SelectInput::new("Type:", options);
+        select.set_name("type");
+        select.set_ordering(OptionOrdering::MostFrequent(1));
+        select.add_to(&mut step);
+        step.add_to(&mut form);
+        form.set_history(storage, "history").unwrap();
+
+        press(&mut form, KeyCode::Down);
+
+        let terminal = render(&mut form);
+
+        // The most frequent choice floats above the rest, which keep their declared order
+        let screen = terminal.screen_contents();
+        let fix = screen.find("fix  - A fix.").unwrap();
+        let divider = screen.find("───").unwrap();
+        let feat = screen.find("feat - A feature.").unwrap();
+        let docs = screen.find("docs - Docs.").unwrap();
+        assert!(fix < divider && divider < feat && feat < docs, "{}", screen);
+
+        // Having preselected the last choice, the input moves down into the rest
+        assert_eq!(Some("feat"), form.values().get_text("type"));
+    }
+
+    #[test]
+    fn test_select_drawer_alignment() {
+        let mut form = Form::new();
+        let mut step = CompoundStep::new();
+        let options = vec![
+            ("feat", "A feature."),
+            ("ci", "A CI change."),
+            ("日本", "Wide."),
+        ];
+        let mut select = SelectInput::new("Type:", options);
+        select.set_separator(" │ ");
+        select.add_to(&mut step);
+        step.add_to(&mut form);
+
+        let terminal = render(&mut form);
+
+        // Descriptions align in a column, whatever their values' display widths
+        let screen = terminal.screen_contents();
+        let separator_columns: Vec<_> = screen
+            .lines()
+            .filter(|line| line.contains('│'))
+            .map(|line| line.split('│').next().unwrap().width())
+            .collect();
+        assert_eq!(vec![8, 8, 8], separator_columns, "{}", screen);
+    }
+
+    #[test]
+    fn test_select_item_renderer() {
+        let mut form = Form::new();
+        let mut step = CompoundStep::new();
+        let mut select = SelectInput::new("Branch:", vec![("main", ""), ("dev", "")]);
+        select.set_item_renderer(|option, is_selected, index| {
+            let marker = if is_selected { "●" } else { "○" };
+            let row = format!(" {} {}. {}", marker, index + 1, option.value());
+            Text::new(row).as_segment()
+        });
+        select.add_to(&mut step);
+        step.add_to(&mut form);
+
+        press(&mut form, KeyCode::Down);
+
+        let terminal = render(&mut form);
+
+        terminal.expect_screen_contains(" ○ 1. main\n ● 2. dev");
+    }
+
+    #[test]
+    fn test_esc_closes_drawer_before_retreating() {
+        let mut form = Form::new();
+        let mut step = CompoundStep::new();
+        TextInput::new("Scope:", false).add_to(&mut step);
+        step.add_to(&mut form);
+        let mut step = CompoundStep::new();
+        let options = vec![("feat", "A feature."), ("fix", "A fix.")];
+        SelectInput::new("Type:", options).add_to(&mut step);
+        step.add_to(&mut form);
+
+        type_text(&mut form, "ui");
+        press(&mut form, KeyCode::Enter);
+        render(&mut form).expect_screen_contains("fix  - A fix.");
+
+        // The first Esc only closes the drawer
+        press(&mut form, KeyCode::Esc);
+        assert_eq!(1, form.state().active_step());
+        let screen = render(&mut form).screen_contents();
+        assert!(!screen.contains("A fix."), "{}", screen);
+
+        // Navigating the options re-opens it
+        press(&mut form, KeyCode::Down);
+        render(&mut form).expect_screen_contains("fix  - A fix.");
+
+        press(&mut form, KeyCode::Esc);
+        press(&mut form, KeyCode::Esc);
+        assert_eq!(0, form.state().active_step());
+    }
+
+    #[test]
+    fn test_held_navigation_accelerates() {
+        let values: Vec<String> = (0..30).map(|index| index.to_string()).collect();
+        let options = values.iter().map(|value| (value.as_str(), "")).collect();
+        let mut select = SelectInput::new("Option:", options);
+
+        let navigate = |select: &mut SelectInput, code| {
+            select.update(KeyEvent::new(code, KeyModifiers::NONE));
+            select.selected_option
+        };
+
+        // A single press wraps around the options
+        assert_eq!(29, navigate(&mut select, KeyCode::Up));
+        assert_eq!(0, navigate(&mut select, KeyCode::Down));
+
+        // Rapid repeats of the same key move further
+        select.last_navigation = None;
+        let positions: Vec<_> = (0..7)
+            .map(|_| navigate(&mut select, KeyCode::Down))
+            .collect();
+        assert_eq!(vec![1, 2, 3, 4, 5, 7, 9], positions);
+
+        // Pausing resets the acceleration
+        let paused = Instant::now() - Duration::from_secs(1);
+        select.last_navigation = Some((KeyCode::Down, paused));
+        assert_eq!(10, navigate(&mut select, KeyCode::Down));
+
+        // Accelerated navigation stops at the ends rather than wrapping
+        select.selected_option = 28;
+        select.repeat_count = 10;
+        select.last_navigation = Some((KeyCode::Down, Instant::now()));
+        assert_eq!(29, navigate(&mut select, KeyCode::Down));
+    }
+
+    #[test]
+    fn test_background_options() {
+        let (sender, receiver) = channel::<()>();
+
+        let mut form = Form::new();
+        let mut step = CompoundStep::new();
+        let mut framework = SelectInput::new("Select a framework.", Vec::new());
+        framework.set_name("framework");
+        framework.load_options(move || {
+            receiver.recv().unwrap();
+            vec![
+                SelectInputOption::new("axum", ""),
+                SelectInputOption::new("actix", ""),
+            ]
+        });
+        assert!(framework.set_value("actix"));
+        framework.add_to(&mut step);
+        step.add_to(&mut form);
+
+        // While loading, the drawer says so and the input may not be advanced past
+        render(&mut form).expect_screen_contains("loading…");
+        assert_eq!(FormStatus::Active, press(&mut form, KeyCode::Enter));
+
+        // Once loaded, the options are swapped in as the form ticks, selecting the pending value
+        sender.send(()).unwrap();
+        let started_at = Instant::now();
+        while form.values().get_text("framework") != Some("actix") {
+            assert!(
+                started_at.elapsed() < Duration::from_secs(5),
+                "options never loaded"
+            );
+            thread::sleep(Duration::from_millis(1));
+            form.update(InputEvent::Tick);
+        }
+
+        render(&mut form).expect_screen_contains("axum");
+        assert_eq!(FormStatus::Submitted, press(&mut form, KeyCode::Enter));
+    }
+
+    #[test]
+    fn test_failed_background_options() {
+        let mut input = SelectInput::new("Select a framework.", vec![("axum", "")]);
+        input.load_options(|| panic!("lookup failed"));
+
+        // A provider which panics leaves the input without options rather than loading forever
+        let started_at = Instant::now();
+        while input.is_loading() {
+            assert!(
+                started_at.elapsed() < Duration::from_secs(5),
+                "options never loaded"
+            );
+            thread::sleep(Duration::from_millis(1));
+            input.tick();
+        }
+
+        assert_eq!(None, input.validation_error());
+        assert_eq!(None, input.refresh_interval());
+    }
+}
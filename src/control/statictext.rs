@@ -1,34 +1,37 @@
-use crossterm::event::KeyEvent;
+use std::sync::{Arc, Mutex};
+
 use tty_interface::Style;
 
 use crate::{
     dependency::{Action, DependencyId, Evaluation},
+    input::KeyEvent,
     step::CompoundStep,
     text::{DrawerContents, Segment, Text},
 };
 
-use super::Control;
+use super::{Control, ControlKind, ControlMeta};
 
-/// Static, unfocusable, formatable display text. May be dependent on other form elements.
+/// Static, unfocusable, formatable display text. May be dependent on other form elements. Text
+/// with embedded newlines is rendered across multiple lines of its step.
 ///
 /// # Examples
 /// ```
-/// use tty_interface::Style;
-///
 /// use tty_form::{
 ///     step::CompoundStep,
 ///     control::{Control, StaticText},
+///     style::style,
 /// };
 ///
 /// let mut text = StaticText::new("Hello, world!");
-/// text.set_style(Style::new().set_bold(true));
+/// text.set_style(style().bold());
 ///
 /// let mut step = CompoundStep::new();
 /// text.add_to(&mut step);
 /// ```
 pub struct StaticText {
-    text: String,
-    style: Option<Style>,
+    name: Option<String>,
+    content: Arc<Mutex<StaticTextContent>>,
+    has_handle: bool,
     dependency: Option<(DependencyId, Action)>,
 }
 
@@ -36,20 +39,60 @@ impl StaticText {
     /// Create a new static text control with the specified content.
     pub fn new(text: &str) -> Self {
         Self {
-            text: text.to_string(),
-            style: None,
+            name: None,
+            content: Arc::new(Mutex::new(StaticTextContent {
+                text: text.to_string(),
+                style: None,
+            })),
+            has_handle: false,
             dependency: None,
         }
     }
 
+    /// Set this control's name, by which it may be retrieved from its step or form.
+    pub fn set_name(&mut self, name: &str) {
+        self.name = Some(name.to_string());
+    }
+
     /// Set the text for this control.
     pub fn set_text(&mut self, text: &str) {
-        self.text = text.to_string();
+        if let Ok(mut content) = self.content.lock() {
+            content.text = text.to_string();
+        }
     }
 
     /// Set the optional style for this control.
-    pub fn set_style(&mut self, style: Style) {
-        self.style = Some(style);
+    pub fn set_style(&mut self, style: impl Into<Style>) {
+        if let Ok(mut content) = self.content.lock() {
+            content.style = Some(style.into());
+        }
+    }
+
+    /// A handle by which the application, another thread, or other controls' callbacks may change
+    /// this control's text and style while its form executes. Its form re-renders to reflect them.
+    ///
+    /// # Examples
+    /// ```
+    /// use tty_form::{
+    ///     step::CompoundStep,
+    ///     control::{Control, StaticText},
+    /// };
+    ///
+    /// let mut status = StaticText::new("No trailers added");
+    /// let handle = status.handle();
+    ///
+    /// let mut step = CompoundStep::new();
+    /// status.add_to(&mut step);
+    ///
+    /// handle.set_text("3 trailers added");
+    /// assert_eq!("3 trailers added", handle.text());
+    /// ```
+    pub fn handle(&mut self) -> StaticTextHandle {
+        self.has_handle = true;
+
+        StaticTextHandle {
+            content: self.content.clone(),
+        }
     }
 
     /// Sets a dependency on the specified ID, performing some action if it evaluates true.
@@ -59,10 +102,22 @@ impl StaticText {
 }
 
 impl Control for StaticText {
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    fn meta(&self) -> ControlMeta {
+        ControlMeta::new(ControlKind::Static).with_name(self.name())
+    }
+
     fn focusable(&self) -> bool {
         false
     }
 
+    fn has_handle(&self) -> bool {
+        self.has_handle
+    }
+
     fn update(&mut self, _input: KeyEvent) {}
 
     fn help(&self) -> Option<Segment> {
@@ -70,7 +125,15 @@ impl Control for StaticText {
     }
 
     fn text(&self) -> (Segment, Option<u16>) {
-        (Text::new(self.text.to_string()).as_segment(), None)
+        let text = match self.content.lock() {
+            Ok(content) => match content.style {
+                Some(style) => Text::new_styled(content.text.clone(), style),
+                None => Text::new(content.text.clone()),
+            },
+            Err(_) => Text::new(String::new()),
+        };
+
+        (text.as_segment(), None)
     }
 
     fn drawer(&self) -> Option<DrawerContents> {
@@ -93,3 +156,73 @@ impl Control for StaticText {
         step.add_control(Box::new(self));
     }
 }
+
+/// The text and style of a static text control, shared with its handles.
+#[derive(Debug)]
+struct StaticTextContent {
+    text: String,
+    style: Option<Style>,
+}
+
+/// A handle by which a static text control's text and style may be changed while its form
+/// executes, such as to show status driven by other steps. Obtained from [StaticText::handle].
+#[derive(Debug, Clone)]
+pub struct StaticTextHandle {
+    content: Arc<Mutex<StaticTextContent>>,
+}
+
+impl StaticTextHandle {
+    /// The control's current text.
+    pub fn text(&self) -> String {
+        self.content
+            .lock()
+            .map(|content| content.text.clone())
+            .unwrap_or_default()
+    }
+
+    /// Replace the control's text.
+    pub fn set_text(&self, text: &str) {
+        if let Ok(mut content) = self.content.lock() {
+            content.text = text.to_string();
+        }
+    }
+
+    /// Replace the control's style.
+    pub fn set_style(&self, style: impl Into<Style>) {
+        if let Ok(mut content) = self.content.lock() {
+            content.style = Some(style.into());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use crate::{
+        control::{Control, StaticText, TextInput},
+        step::{CompoundStep, Step},
+        test::{render, type_text},
+        Form,
+    };
+
+    #[test]
+    fn test_static_text_handle() {
+        let mut form = Form::new();
+        let mut step = CompoundStep::new();
+        TextInput::new("Enter a trailer.", false).add_to(&mut step);
+        let mut status = StaticText::new(" (no trailers added)");
+        let handle = status.handle();
+        status.add_to(&mut step);
+        step.add_to(&mut form);
+
+        type_text(&mut form, "Refs: #1");
+        render(&mut form).expect_screen_contains("Refs: #1 (no trailers added)");
+
+        // Changes made through the handle, such as from another thread, show on the next render
+        thread::spawn(move || handle.set_text(" (3 trailers added)"))
+            .join()
+            .unwrap();
+        render(&mut form).expect_screen_contains("Refs: #1 (3 trailers added)");
+    }
+}
@@ -1,14 +1,25 @@
-use crossterm::event::{KeyCode, KeyEvent};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
 use tty_text::Key;
 
 use crate::{
-    dependency::{Action, DependencyId, Evaluation},
+    dependency::{evaluate_value, Action, DependencyId, Evaluation},
+    input::{KeyCode, KeyEvent},
     step::CompoundStep,
-    style::help_style,
+    style::{error_style, help_style, muted_style},
     text::{DrawerContents, Segment, Text},
+    utility::{delete_paired, insert_paired, text_with_value},
+    validation::{AsyncValidation, CancellationToken, ValidationState},
+    value::ControlValue,
 };
 
-use super::Control;
+use super::{CharFilter, Control, ControlKind, ControlMeta, Transform};
+
+/// The character rendered in place of each of a sensitive input's characters.
+const MASK_CHARACTER: char = '*';
 
 /// A single-line text field input. May be used as an evaluation for dependent form elements.
 ///
@@ -23,20 +34,115 @@ use super::Control;
 /// TextInput::new("Enter your name:", false).add_to(&mut step);
 /// ```
 pub struct TextInput {
+    name: Option<String>,
     prompt: String,
+    long_help: Option<DrawerContents>,
+    examples: Vec<String>,
+    example_interval: Duration,
+    examples_shown_at: Instant,
     text: tty_text::Text,
-    force_lowercase: bool,
+    transforms: Vec<Transform>,
+    preview_transforms: bool,
+    allowed_chars: CharFilter,
+    rejected: bool,
     evaluation: Option<(DependencyId, Evaluation)>,
+    validation: Option<AsyncValidation>,
+    required: bool,
+    sensitive: bool,
+    auto_pair: bool,
+    locked: bool,
 }
 
 impl TextInput {
     /// Create a new text input control with the specified prompt and casing-rules.
     pub fn new(prompt: &str, force_lowercase: bool) -> Self {
         Self {
+            name: None,
             prompt: prompt.to_string(),
+            long_help: None,
+            examples: Vec::new(),
+            example_interval: Duration::ZERO,
+            examples_shown_at: Instant::now(),
             text: tty_text::Text::new(false),
-            force_lowercase,
+            transforms: if force_lowercase {
+                vec![Transform::Lowercase]
+            } else {
+                Vec::new()
+            },
+            preview_transforms: false,
+            allowed_chars: CharFilter::Any,
+            rejected: false,
             evaluation: None,
+            validation: None,
+            required: false,
+            sensitive: false,
+            auto_pair: false,
+            locked: false,
+        }
+    }
+
+    /// Set this input's name, by which it may be retrieved from its step or form.
+    pub fn set_name(&mut self, name: &str) {
+        self.name = Some(name.to_string());
+    }
+
+    /// Set this input's extended documentation, such as examples, shown in a drawer when the
+    /// form's help key is pressed.
+    pub fn set_long_help(&mut self, long_help: Vec<Segment>) {
+        self.long_help = Some(long_help);
+    }
+
+    /// Set example values to show in this input's help while it's empty, rotating to the next
+    /// example each interval.
+    pub fn set_examples(&mut self, examples: Vec<&str>, interval: Duration) {
+        self.examples = examples.into_iter().map(str::to_string).collect();
+        self.example_interval = interval;
+        self.examples_shown_at = Instant::now();
+    }
+
+    /// The example currently shown in this input's help, if any.
+    fn current_example(&self) -> Option<&str> {
+        if self.examples.is_empty() || !self.text.value().is_empty() {
+            return None;
+        }
+
+        let elapsed = self.examples_shown_at.elapsed().as_millis();
+        let interval = self.example_interval.as_millis().max(1);
+        let index = (elapsed / interval) as usize % self.examples.len();
+
+        Some(&self.examples[index])
+    }
+
+    /// Validate this input's value on a background thread once it's been unchanged for the debounce
+    /// interval, such as to check that a ticket exists remotely. The validator returns an error
+    /// message for invalid values. The input may not be advanced past until its value is valid.
+    /// Pressing Esc while the validator runs cancels its token, and the form retreats once it
+    /// returns.
+    pub fn set_async_validator(
+        &mut self,
+        debounce: Duration,
+        validator: impl Fn(&str, &CancellationToken) -> Result<(), String> + Send + Sync + 'static,
+    ) {
+        let validation = AsyncValidation::new(Arc::new(validator), debounce, &self.value());
+        self.validation = Some(validation);
+    }
+
+    /// The state of this input's background validation, if it has a validator.
+    pub fn validation_state(&self) -> Option<&ValidationState> {
+        self.validation.as_ref().map(AsyncValidation::state)
+    }
+
+    /// This input's current value.
+    pub fn value(&self) -> String {
+        self.text.value()
+    }
+
+    /// Replace this input's value, placing the cursor at its end.
+    pub fn set_value(&mut self, value: &str) {
+        self.text = text_with_value(value, false);
+
+        if let Some(validation) = &mut self.validation {
+            validation.changed(value);
         }
     }
 
@@ -47,7 +153,55 @@ impl TextInput {
 
     /// Specify whether this input should force its value to be lowercase.
     pub fn set_force_lowercase(&mut self, force: bool) {
-        self.force_lowercase = force;
+        self.transforms
+            .retain(|transform| !matches!(transform, Transform::Lowercase));
+
+        if force {
+            self.transforms.push(Transform::Lowercase);
+        }
+    }
+
+    /// Add a transform normalizing this input's value as it loses focus. Transforms are applied in
+    /// the order they're added.
+    pub fn add_transform(&mut self, transform: Transform) {
+        self.transforms.push(transform);
+    }
+
+    /// Specify whether this input's help previews its transformed value while it differs from the
+    /// value as typed.
+    pub fn set_preview_transforms(&mut self, preview: bool) {
+        self.preview_transforms = preview;
+    }
+
+    /// This input's value with its transforms applied.
+    fn transformed_value(&self) -> String {
+        self.transforms
+            .iter()
+            .fold(self.value(), |value, transform| transform.apply(&value))
+    }
+
+    /// Restrict the characters which may be typed into this input. Characters are filtered after
+    /// any case transforms are applied.
+    pub fn set_allowed_chars(&mut self, filter: CharFilter) {
+        self.allowed_chars = filter;
+    }
+
+    /// Specify whether typing an opening bracket or quote also inserts its closing character, which
+    /// is typed over rather than duplicated.
+    pub fn set_auto_pair(&mut self, auto_pair: bool) {
+        self.auto_pair = auto_pair;
+    }
+
+    /// Specify whether this input requires a value before it may be advanced past.
+    pub fn set_required(&mut self, required: bool) {
+        self.required = required;
+    }
+
+    /// Specify whether this input's value is secret, such as a password or token. A sensitive value
+    /// is masked as it's rendered and redacted from logs and autosaved drafts, but is included in
+    /// the form's result.
+    pub fn set_sensitive(&mut self, sensitive: bool) {
+        self.sensitive = sensitive;
     }
 
     /// Sets the dependency evaluation which other form elements can react to.
@@ -59,37 +213,178 @@ impl TextInput {
 }
 
 impl Control for TextInput {
-    fn focusable(&self) -> bool {
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    fn meta(&self) -> ControlMeta {
+        ControlMeta::new(ControlKind::Text)
+            .with_name(self.name())
+            .with_required(self.required)
+            .with_sensitive(self.sensitive)
+    }
+
+    fn form_value(&self) -> Option<ControlValue> {
+        Some(ControlValue::Text(self.value()))
+    }
+
+    fn set_form_value(&mut self, value: &ControlValue) -> bool {
+        match value.as_text() {
+            Some(text) => {
+                self.set_value(text);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn lock(&mut self) -> bool {
+        self.locked = true;
         true
     }
 
+    fn focusable(&self) -> bool {
+        !self.locked
+    }
+
+    fn accepts_text(&self) -> bool {
+        !self.locked
+    }
+
     fn update(&mut self, input: KeyEvent) {
         match input.code {
             KeyCode::Char(mut ch) => {
-                if self.force_lowercase {
-                    ch = ch.to_lowercase().next().unwrap();
+                for transform in &self.transforms {
+                    ch = transform.apply_char(ch);
                 }
 
-                self.text.handle_input(Key::Char(ch));
+                if !self.allowed_chars.allows(ch) {
+                    self.rejected = true;
+                    return;
+                }
+
+                if self.auto_pair {
+                    insert_paired(&mut self.text, ch);
+                } else {
+                    self.text.handle_input(Key::Char(ch));
+                }
             }
+            KeyCode::Backspace if self.auto_pair => delete_paired(&mut self.text),
             KeyCode::Backspace => self.text.handle_input(Key::Backspace),
             KeyCode::Left => self.text.handle_input(Key::Left),
             KeyCode::Right => self.text.handle_input(Key::Right),
             _ => {}
         };
+
+        if let Some(validation) = &mut self.validation {
+            validation.changed(&self.text.value());
+        }
+    }
+
+    fn blur(&mut self) {
+        let transformed = self.transformed_value();
+        if transformed != self.value() {
+            self.set_value(&transformed);
+        }
+    }
+
+    fn take_rejection(&mut self) -> bool {
+        std::mem::take(&mut self.rejected)
+    }
+
+    fn tick(&mut self) {
+        if let Some(validation) = &mut self.validation {
+            validation.tick();
+        }
+    }
+
+    fn cancel(&mut self) -> bool {
+        match &mut self.validation {
+            Some(validation) => validation.cancel(),
+            None => false,
+        }
+    }
+
+    fn is_cancelling(&self) -> bool {
+        self.validation
+            .as_ref()
+            .is_some_and(AsyncValidation::is_cancelling)
     }
 
     fn help(&self) -> Option<Segment> {
-        Some(Text::new_styled(self.prompt.clone(), help_style()).as_segment())
+        if let Some(ValidationState::Invalid(message)) = self.validation_state() {
+            return Some(Text::new_styled(message.clone(), error_style()).as_segment());
+        }
+
+        let mut help = Text::new_styled(self.prompt.clone(), help_style()).as_segment();
+
+        if let Some(example) = self.current_example() {
+            let example = format!(" e.g. {}", example);
+            help.push(Text::new_styled(example, muted_style()));
+        }
+
+        if self.preview_transforms && !self.sensitive {
+            let transformed = self.transformed_value();
+            if transformed != self.value() {
+                let preview = format!(" → {}", transformed);
+                help.push(Text::new_styled(preview, muted_style()));
+            }
+        }
+
+        Some(help)
+    }
+
+    fn long_help(&self) -> Option<DrawerContents> {
+        self.long_help.clone()
+    }
+
+    fn refresh_interval(&self) -> Option<Duration> {
+        let rotating = self.examples.len() > 1 && self.text.value().is_empty();
+        let examples = rotating.then_some(self.example_interval);
+        let validation = self
+            .validation
+            .as_ref()
+            .and_then(AsyncValidation::refresh_interval);
+
+        match (examples, validation) {
+            (Some(examples), Some(validation)) => Some(examples.min(validation)),
+            (examples, validation) => examples.or(validation),
+        }
     }
 
     fn text(&self) -> (Segment, Option<u16>) {
-        let segment = Text::new(self.text.value()).as_segment();
+        let mut value = self.text.value();
+        if self.sensitive {
+            value = MASK_CHARACTER.to_string().repeat(value.chars().count());
+        }
+
+        let segment = Text::new(value).as_segment();
         let cursor_column = self.text.cursor().0 as u16;
 
         (segment, Some(cursor_column))
     }
 
+    fn indicator(&self) -> Option<Segment> {
+        let validation = self.validation.as_ref()?;
+        Some(validation.state().indicator(validation.is_cancelling()))
+    }
+
+    fn validation_error(&self) -> Option<String> {
+        if self.required && self.text.value().is_empty() {
+            return Some("A value is required.".to_string());
+        }
+
+        match self.validation_state()? {
+            ValidationState::Pending => Some("Waiting for validation…".to_string()),
+            ValidationState::Valid => None,
+            ValidationState::Invalid(message) => Some(message.clone()),
+        }
+    }
+
+    fn result_text(&self) -> String {
+        self.text.value()
+    }
+
     fn drawer(&self) -> Option<DrawerContents> {
         None
     }
@@ -103,14 +398,54 @@ impl Control for TextInput {
     }
 
     fn evaluate(&self, evaluation: &Evaluation) -> bool {
-        match evaluation {
-            Evaluation::Equal(value) => &self.text.value() == value,
-            Evaluation::NotEqual(value) => &self.text.value() != value,
-            Evaluation::IsEmpty => self.text.value().is_empty(),
-        }
+        evaluate_value(&self.text.value(), evaluation)
     }
 
     fn add_to(self, step: &mut CompoundStep) {
         step.add_control(Box::new(self))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use crate::{
+        control::Control,
+        input::{KeyCode, KeyEvent, KeyModifiers},
+    };
+
+    use super::TextInput;
+
+    fn help_text(input: &TextInput) -> String {
+        let help = input.help().unwrap_or_default();
+        help.iter().map(|text| text.content()).collect()
+    }
+
+    #[test]
+    fn test_rotating_examples() {
+        let interval = Duration::from_secs(3);
+
+        let mut input = TextInput::new("Enter a scope.", false);
+        input.set_examples(vec!["ui", "parser"], interval);
+        assert_eq!("Enter a scope. e.g. ui", help_text(&input));
+        assert_eq!(Some(interval), input.refresh_interval());
+
+        // Each interval rotates to the next example, wrapping around
+        input.examples_shown_at = Instant::now() - interval;
+        assert_eq!("Enter a scope. e.g. parser", help_text(&input));
+        input.examples_shown_at = Instant::now() - interval * 2;
+        assert_eq!("Enter a scope. e.g. ui", help_text(&input));
+
+        // Examples are hidden, and no longer refreshed, once the input has a value
+        input.update(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE));
+        assert_eq!("Enter a scope.", help_text(&input));
+        assert_eq!(None, input.refresh_interval());
+
+        // A single example is shown without refreshing
+        let mut input = TextInput::new("Enter a scope.", false);
+        input.set_examples(vec!["ui"], interval);
+        assert_eq!("Enter a scope. e.g. ui", help_text(&input));
+        assert_eq!(None, input.refresh_interval());
+    }
+}
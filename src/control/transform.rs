@@ -0,0 +1,111 @@
+use std::{fmt, sync::Arc};
+
+/// A function transforming a value.
+type TransformFn = Arc<dyn Fn(&str) -> String + Send + Sync>;
+
+/// A normalization applied to a [super::TextInput]'s value as it loses focus. Case transforms are
+/// also applied to each character as it's typed.
+///
+/// # Examples
+/// ```
+/// use tty_form::control::Transform;
+///
+/// assert_eq!("add-user-login", Transform::Slugify.apply(" Add user  login! "));
+/// assert_eq!("a b", Transform::CollapseWhitespace.apply(" a \t b "));
+/// ```
+#[derive(Clone)]
+pub enum Transform {
+    /// Lowercase the value.
+    Lowercase,
+    /// Uppercase the value.
+    Uppercase,
+    /// Remove leading and trailing whitespace.
+    Trim,
+    /// Trim the value and replace each run of whitespace with a single space.
+    CollapseWhitespace,
+    /// Lowercase the value's letters and digits, joining each run of them with a hyphen.
+    Slugify,
+    /// An application-defined transform.
+    Custom(TransformFn),
+}
+
+impl Transform {
+    /// A transform applying the specified function.
+    pub fn custom(transform: impl Fn(&str) -> String + Send + Sync + 'static) -> Self {
+        Self::Custom(Arc::new(transform))
+    }
+
+    /// Apply this transform to the specified value.
+    pub fn apply(&self, value: &str) -> String {
+        match self {
+            Self::Lowercase => value.to_lowercase(),
+            Self::Uppercase => value.to_uppercase(),
+            Self::Trim => value.trim().to_string(),
+            Self::CollapseWhitespace => value.split_whitespace().collect::<Vec<_>>().join(" "),
+            Self::Slugify => value
+                .split(|ch: char| !ch.is_alphanumeric())
+                .filter(|word| !word.is_empty())
+                .map(str::to_lowercase)
+                .collect::<Vec<_>>()
+                .join("-"),
+            Self::Custom(transform) => transform(value),
+        }
+    }
+
+    /// Apply this transform to a character as it's typed, if it applies per keystroke.
+    pub(crate) fn apply_char(&self, ch: char) -> char {
+        match self {
+            Self::Lowercase => ch.to_lowercase().next().unwrap_or(ch),
+            Self::Uppercase => ch.to_uppercase().next().unwrap_or(ch),
+            _ => ch,
+        }
+    }
+}
+
+impl fmt::Debug for Transform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Lowercase => write!(f, "Lowercase"),
+            Self::Uppercase => write!(f, "Uppercase"),
+            Self::Trim => write!(f, "Trim"),
+            Self::CollapseWhitespace => write!(f, "CollapseWhitespace"),
+            Self::Slugify => write!(f, "Slugify"),
+            Self::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        control::{Control, TextInput},
+        input::KeyCode,
+        step::{CompoundStep, Step},
+        test::press,
+        Form, FormStatus,
+    };
+
+    use super::Transform;
+
+    #[test]
+    fn test_transforms() {
+        let mut form = Form::new();
+        let mut step = CompoundStep::new();
+        let mut input = TextInput::new("Enter a branch.", false);
+        input.set_name("branch");
+        input.add_transform(Transform::Slugify);
+        input.set_preview_transforms(true);
+        input.add_to(&mut step);
+        step.add_to(&mut form);
+
+        for ch in "Add User!".chars() {
+            press(&mut form, KeyCode::Char(ch));
+        }
+
+        // The typed value is kept until the input loses focus
+        assert_eq!(Some("Add User!"), form.values().get_text("branch"));
+
+        assert_eq!(FormStatus::Submitted, press(&mut form, KeyCode::Enter));
+        assert_eq!("add-user", form.result());
+    }
+}
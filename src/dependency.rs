@@ -1,8 +1,11 @@
 use std::{
     collections::HashMap,
+    fmt,
     sync::atomic::{AtomicUsize, Ordering},
 };
 
+use crate::pattern::Pattern;
+
 /// A unique identifier.
 #[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
 pub struct DependencyId(usize);
@@ -17,6 +20,12 @@ impl DependencyId {
     }
 }
 
+impl fmt::Display for DependencyId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
 /// An evaluation to apply to the source of a dependency.
 #[derive(Clone)]
 pub enum Evaluation {
@@ -26,6 +35,40 @@ pub enum Evaluation {
     Equal(String),
     /// Evaluates true if the source's value is different from the evaluation parameter.
     NotEqual(String),
+    /// Evaluates true if the source's value contains the evaluation parameter.
+    Contains(String),
+    /// Evaluates true if the source's value starts with the evaluation parameter.
+    StartsWith(String),
+    /// Evaluates true if the source's value matches the evaluation parameter, a pattern supporting
+    /// a subset of regular expression syntax.
+    MatchesPattern(Pattern),
+}
+
+/// Evaluate the specified source value against an evaluation. Shared by each evaluation source such
+/// that every variant behaves consistently across controls and steps.
+///
+/// # Examples
+/// ```
+/// use tty_form::{
+///     dependency::{evaluate_value, Evaluation},
+///     pattern::Pattern,
+/// };
+///
+/// assert!(evaluate_value("feat", &Evaluation::StartsWith("fe".to_string())));
+/// let ticket = Pattern::parse(r"^[A-Z]+-\d+$")?;
+/// assert!(evaluate_value("PROJ-12", &Evaluation::MatchesPattern(ticket)));
+/// assert!(!evaluate_value("", &Evaluation::Contains("x".to_string())));
+/// # Ok::<(), tty_form::Error>(())
+/// ```
+pub fn evaluate_value(value: &str, evaluation: &Evaluation) -> bool {
+    match evaluation {
+        Evaluation::IsEmpty => value.is_empty(),
+        Evaluation::Equal(expected) => value == expected,
+        Evaluation::NotEqual(expected) => value != expected,
+        Evaluation::Contains(expected) => value.contains(expected.as_str()),
+        Evaluation::StartsWith(expected) => value.starts_with(expected.as_str()),
+        Evaluation::MatchesPattern(pattern) => pattern.is_match(value),
+    }
 }
 
 /// An action to apply to the target if the source evaluates true.
@@ -35,36 +78,347 @@ pub enum Action {
     Hide,
     /// If the evaluation is false for the source, the target is shown, otherwise it is hidden.
     Show,
+    /// If the evaluation is true for the source, the target requires a value before it may be
+    /// advanced past.
+    Require,
 }
 
+/// A callback notified of each change to a dependency's evaluation.
+type Observer = Box<dyn FnMut(DependencyId, bool)>;
+
+/// The latest evaluation of each of a form's dependencies, which its steps and controls react to.
+///
+/// # Examples
+/// ```
+/// use tty_form::{
+///     Form,
+///     step::{Step, CompoundStep},
+///     control::{Control, TextInput},
+///     dependency::Evaluation,
+/// };
+///
+/// let mut form = Form::new();
+/// let mut step = CompoundStep::new();
+/// let mut input = TextInput::new("Enter a scope:", false);
+/// let empty_scope = input.set_evaluation(Evaluation::IsEmpty);
+/// input.add_to(&mut step);
+/// step.add_to(&mut form);
+///
+/// form.observe_dependencies(|id, value| println!("{} is now {}", id, value));
+/// assert_eq!(None, form.dependency_state().get(&empty_scope));
+/// ```
+#[derive(Default)]
 pub struct DependencyState {
     /// The latest evaluation value for each dependency.
     evaluation_states: HashMap<DependencyId, bool>,
     /// Maps a dependency to its source (step, control) indices.
     evaluation_sources: HashMap<DependencyId, (usize, usize)>,
+    /// Callbacks notified of each change to an evaluation.
+    observers: Vec<Observer>,
 }
 
 impl DependencyState {
-    pub(crate) fn new() -> Self {
-        Self {
-            evaluation_states: HashMap::new(),
-            evaluation_sources: HashMap::new(),
-        }
+    /// Create a new state with no evaluations, such as to exercise a custom step in isolation.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The latest evaluation of the specified dependency, if it's been evaluated.
+    pub fn get(&self, id: &DependencyId) -> Option<bool> {
+        self.evaluation_states.get(id).copied()
+    }
+
+    /// The (step, control) indices of the specified dependency's source, if it's registered.
+    pub fn source(&self, id: &DependencyId) -> Option<(usize, usize)> {
+        self.evaluation_sources.get(id).copied()
+    }
+
+    /// Each evaluated dependency and its latest evaluation, ordered by identifier.
+    pub fn iter(&self) -> impl Iterator<Item = (DependencyId, bool)> {
+        let mut evaluations: Vec<_> = self
+            .evaluation_states
+            .iter()
+            .map(|(id, value)| (*id, *value))
+            .collect();
+        evaluations.sort_by_key(|(id, _)| id.0);
+
+        evaluations.into_iter()
+    }
+
+    /// A copy of each dependency's latest evaluation, such as to compare against later.
+    pub fn snapshot(&self) -> HashMap<DependencyId, bool> {
+        self.evaluation_states.clone()
+    }
+
+    /// Register a callback notified with a dependency and its new evaluation each time one changes.
+    pub fn add_observer(&mut self, observer: impl FnMut(DependencyId, bool) + 'static) {
+        self.observers.push(Box::new(observer));
     }
 
     pub(crate) fn register_evaluation(&mut self, id: &DependencyId, step: usize, control: usize) {
         self.evaluation_sources.insert(*id, (step, control));
     }
 
+    /// Forget the evaluations sourced from the specified step, such as once it's removed.
+    pub(crate) fn remove_step_evaluations(&mut self, step: usize) {
+        let ids: Vec<DependencyId> = self
+            .evaluation_sources
+            .iter()
+            .filter(|(_, (source_step, _))| *source_step == step)
+            .map(|(id, _)| *id)
+            .collect();
+
+        for id in ids {
+            self.evaluation_sources.remove(&id);
+            self.evaluation_states.remove(&id);
+        }
+    }
+
     pub(crate) fn get_source(&self, id: &DependencyId) -> (usize, usize) {
         *self.evaluation_sources.get(id).unwrap()
     }
 
     pub(crate) fn update_evaluation(&mut self, id: &DependencyId, value: bool) {
-        self.evaluation_states.insert(*id, value);
+        trace_event!(
+            trace,
+            dependency = id.0,
+            value,
+            "updated dependency evaluation"
+        );
+        let previous = self.evaluation_states.insert(*id, value);
+        if previous != Some(value) {
+            for observer in &mut self.observers {
+                observer(*id, value);
+            }
+        }
     }
 
     pub(crate) fn get_evaluation(&self, id: &DependencyId) -> bool {
         *self.evaluation_states.get(id).unwrap_or(&false)
     }
+
+    /// Describe each registered evaluation's source and latest value, ordered by identifier.
+    pub(crate) fn describe_evaluations(&self) -> Vec<String> {
+        let mut sources: Vec<_> = self.evaluation_sources.iter().collect();
+        sources.sort_by_key(|(id, _)| id.0);
+
+        sources
+            .into_iter()
+            .map(|(id, (step, control))| {
+                format!(
+                    "dep {} @ {}:{} = {}",
+                    id.0,
+                    step,
+                    control,
+                    self.get_evaluation(id)
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        context::Context,
+        control::{Control, FunctionalControl, SelectInput, StaticText, TextInput},
+        input::{KeyCode, KeyEvent, KeyModifiers},
+        pattern::Pattern,
+        step::{CompoundStep, KeyValueStep, Step, YesNoStep},
+        test::{press, render, type_text},
+        text::Segment,
+        value::{ControlValue, ValueStore},
+        Form, FormStatus,
+    };
+
+    use super::{Action, DependencyId, DependencyState, Evaluation};
+
+    #[test]
+    fn test_evaluations() {
+        let pattern = || Evaluation::MatchesPattern(Pattern::parse(r"^f\w+$").unwrap());
+        let cases = |value: &dyn Fn(&Evaluation) -> bool| {
+            [
+                value(&Evaluation::Equal("feat".to_string())),
+                value(&Evaluation::NotEqual("fix".to_string())),
+                value(&Evaluation::Contains("ea".to_string())),
+                value(&Evaluation::StartsWith("fe".to_string())),
+                value(&pattern()),
+                value(&Evaluation::IsEmpty),
+            ]
+        };
+        let expected = [true, true, true, true, true, false];
+
+        let mut text = TextInput::new("Enter a type.", false);
+        text.set_value("feat");
+        assert_eq!(expected, cases(&|evaluation| text.evaluate(evaluation)));
+
+        let mut select = SelectInput::new("Select a type.", vec![("fix", ""), ("feat", "")]);
+        select.set_value("feat");
+        assert_eq!(expected, cases(&|evaluation| select.evaluate(evaluation)));
+
+        let mut functional = FunctionalControl::new("feat", |_| (Segment::new(), None));
+        functional.set_value_fn(|state| Some(ControlValue::Text(state.to_string())));
+        assert_eq!(
+            expected,
+            cases(&|evaluation| functional.evaluate(evaluation))
+        );
+
+        let fixed = StaticText::new("feat");
+        assert_eq!([false; 6], cases(&|evaluation| fixed.evaluate(evaluation)));
+
+        // Steps update their evaluations as their values change
+        let values = ValueStore::new();
+        let step_evaluation = |step: &mut dyn Step, keys: &str, evaluation: DependencyId| {
+            let mut dependency_state = DependencyState::new();
+            step.initialize(&mut dependency_state, &Context::new(), 0);
+            for ch in keys.chars() {
+                let code = if ch == '\t' {
+                    KeyCode::Tab
+                } else {
+                    KeyCode::Char(ch)
+                };
+                step.update(
+                    &mut dependency_state,
+                    &values,
+                    KeyEvent::new(code, KeyModifiers::NONE),
+                );
+            }
+            dependency_state.get_evaluation(&evaluation)
+        };
+
+        let mut yes_no = YesNoStep::new("Breaking?", "Describe the change:", "BREAKING:");
+        let id = yes_no.set_evaluation(Evaluation::StartsWith("N".to_string()));
+        assert!(step_evaluation(&mut yes_no, "", id));
+
+        let mut pairs = KeyValueStep::new("Enter trailers:");
+        let id = pairs.set_evaluation(Evaluation::Contains("Fixes: #".to_string()));
+        assert!(step_evaluation(&mut pairs, "Fixes\t#12", id));
+        let mut pairs = KeyValueStep::new("Enter trailers:");
+        let fixes = Pattern::parse(r"^Fixes: #\d+$").unwrap();
+        let id = pairs.set_evaluation(Evaluation::MatchesPattern(fixes));
+        assert!(!step_evaluation(&mut pairs, "Fixes\t12", id));
+    }
+
+    #[test]
+    fn test_dependency_observers() {
+        let mut form = Form::new();
+        let mut step = CompoundStep::new();
+        let mut input = TextInput::new("Enter a scope.", false);
+        let empty_scope = input.set_evaluation(Evaluation::IsEmpty);
+        input.add_to(&mut step);
+        step.add_to(&mut form);
+
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        let observed_changes = changes.clone();
+        form.observe_dependencies(move |id, value| observed_changes.borrow_mut().push((id, value)));
+
+        assert_eq!(None, form.dependency_state().get(&empty_scope));
+        let before = form.dependency_state().snapshot();
+
+        type_text(&mut form, "ab");
+        assert_eq!(Some(false), form.dependency_state().get(&empty_scope));
+        assert_ne!(before, form.dependency_state().snapshot());
+
+        // Observers are notified of the initial evaluation and each change, but not repeats
+        assert_eq!(
+            vec![(empty_scope, true), (empty_scope, false)],
+            *changes.borrow()
+        );
+        assert_eq!(
+            vec![(empty_scope, false)],
+            form.dependency_state().iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_require_dependency() {
+        let build_form = || {
+            let mut form = Form::new();
+
+            let mut step = CompoundStep::new();
+            let breaking = step.add(TextInput::new("Is this a breaking change?", false));
+            step.add_to(&mut form);
+
+            let mut step = CompoundStep::new();
+            let description = step.add(TextInput::new("Describe the breaking change.", false));
+            step.add_to(&mut form);
+
+            let evaluation = Evaluation::Equal("yes".to_string());
+            form.depend(&description, &breaking, evaluation, Action::Require)
+                .unwrap();
+            form
+        };
+
+        // The description is optional unless the change is breaking
+        let mut form = build_form();
+        type_text(&mut form, "no");
+        press(&mut form, KeyCode::Enter);
+        assert_eq!(FormStatus::Submitted, press(&mut form, KeyCode::Enter));
+
+        let mut form = build_form();
+        type_text(&mut form, "yes");
+        press(&mut form, KeyCode::Enter);
+        assert_eq!(FormStatus::Active, press(&mut form, KeyCode::Enter));
+        assert_eq!(1, form.state().active_step());
+
+        type_text(&mut form, "Removes the v1 API.");
+        assert_eq!(FormStatus::Submitted, press(&mut form, KeyCode::Enter));
+    }
+
+    #[test]
+    fn test_invalid_dependency() {
+        let mut form = Form::new();
+
+        let mut step = CompoundStep::new();
+        let scope = step.add(TextInput::new("Enter a scope.", false));
+        let paren = step.add(StaticText::new("("));
+        step.add_to(&mut form);
+
+        // Controls must belong to one of the form's steps
+        let mut detached = CompoundStep::new();
+        let orphan = detached.add(TextInput::new("Enter anything.", false));
+        assert!(form
+            .depend(&orphan, &scope, Evaluation::IsEmpty, Action::Hide)
+            .is_err());
+        assert!(form
+            .depend(&paren, &orphan, Evaluation::IsEmpty, Action::Hide)
+            .is_err());
+
+        // Dependencies must be declared before the form is initialized
+        press(&mut form, KeyCode::Char('x'));
+        assert!(form
+            .depend(&paren, &scope, Evaluation::IsEmpty, Action::Hide)
+            .is_err());
+    }
+
+    #[test]
+    fn test_cross_step_dependency_display() {
+        let mut form = Form::new();
+
+        let mut summary = CompoundStep::new();
+        StaticText::new("feat").add_to(&mut summary);
+        let bang = summary.add(StaticText::new("!"));
+        StaticText::new(": ").add_to(&mut summary);
+        TextInput::new("Enter a description.", false).add_to(&mut summary);
+        summary.add_to(&mut form);
+
+        let mut breaking = YesNoStep::new("Breaking?", "Describe the change.", "BREAKING CHANGE");
+        let is_breaking = breaking.set_evaluation(Evaluation::NotEqual("No".to_string()));
+        breaking.add_to(&mut form);
+        form.get_mut(&bang)
+            .unwrap()
+            .set_dependency(is_breaking, Action::Show);
+
+        type_text(&mut form, "add");
+        press(&mut form, KeyCode::Enter);
+        assert!(render(&mut form).screen_contents().contains("feat: add"));
+
+        // Toggling the later step updates the earlier step's display immediately
+        press(&mut form, KeyCode::Up);
+        assert!(render(&mut form).screen_contents().contains("feat!: add"));
+        press(&mut form, KeyCode::Down);
+        assert!(render(&mut form).screen_contents().contains("feat: add"));
+    }
 }
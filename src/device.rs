@@ -1,14 +1,251 @@
+use std::{
+    io,
+    sync::mpsc::{self, Receiver, RecvTimeoutError, Sender, TryRecvError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    input::{self, InputEvent},
+    Result,
+};
+
 /// An input device to use for controlling a form.
 pub trait InputDevice {
     /// Blocks until an input event is received.
-    fn read(&mut self) -> crossterm::Result<crossterm::event::Event>;
+    fn read(&mut self) -> Result<InputEvent>;
+
+    /// Waits up to the specified duration for an input event, returning whether one is available
+    /// to read. Devices which can't wait report that an event is always available.
+    fn poll(&mut self, _timeout: Duration) -> Result<bool> {
+        Ok(true)
+    }
 }
 
 /// The standard input device.
 pub struct StdinDevice;
 
 impl InputDevice for StdinDevice {
-    fn read(&mut self) -> crossterm::Result<crossterm::event::Event> {
-        crossterm::event::read()
+    fn read(&mut self) -> Result<InputEvent> {
+        // Events without a form equivalent, such as mouse events, are skipped
+        loop {
+            if let Some(event) = input::from_crossterm(crossterm::event::read()?) {
+                return Ok(event);
+            }
+        }
+    }
+
+    fn poll(&mut self, timeout: Duration) -> Result<bool> {
+        Ok(crossterm::event::poll(timeout)?)
+    }
+}
+
+/// An input device receiving events sent over a channel, such as by automation or a remote-control
+/// feature running on another thread.
+pub struct ChannelInputDevice {
+    receiver: Receiver<InputEvent>,
+    pending: Option<InputEvent>,
+}
+
+impl ChannelInputDevice {
+    /// Create a new device receiving events from the specified channel.
+    pub fn new(receiver: Receiver<InputEvent>) -> Self {
+        Self {
+            receiver,
+            pending: None,
+        }
+    }
+
+    /// Create a new device along with the sender by which to send it events.
+    pub fn channel() -> (Sender<InputEvent>, Self) {
+        let (sender, receiver) = mpsc::channel();
+        (sender, Self::new(receiver))
+    }
+}
+
+impl InputDevice for ChannelInputDevice {
+    fn read(&mut self) -> Result<InputEvent> {
+        if let Some(event) = self.pending.take() {
+            return Ok(event);
+        }
+
+        self.receiver.recv().map_err(|_| disconnected_error())
+    }
+
+    fn poll(&mut self, timeout: Duration) -> Result<bool> {
+        if self.pending.is_some() {
+            return Ok(true);
+        }
+
+        // A disconnected channel will never have an event available, so it is never ready
+        let received = if timeout.is_zero() {
+            self.receiver
+                .try_recv()
+                .map_err(|err| err == TryRecvError::Empty)
+        } else {
+            self.receiver
+                .recv_timeout(timeout)
+                .map_err(|err| err == RecvTimeoutError::Timeout)
+        };
+
+        match received {
+            Ok(event) => {
+                self.pending = Some(event);
+                Ok(true)
+            }
+            Err(_) => Ok(false),
+        }
+    }
+}
+
+/// The error reported when reading from a channel whose senders have all been dropped, or from a
+/// composite device without any devices.
+fn disconnected_error() -> crate::Error {
+    io::Error::new(io::ErrorKind::UnexpectedEof, "input disconnected").into()
+}
+
+/// How long a composite device waits between polling its devices for events.
+const COMPOSITE_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// An input device multiplexing several others, such as the keyboard alongside a
+/// [ChannelInputDevice] for automation. Devices are polled in turn, starting after the device which
+/// last produced an event, so that a busy device can't starve the others. Reading without any
+/// devices fails rather than waiting forever.
+///
+/// # Examples
+/// ```
+/// use tty_form::{
+///     device::{ChannelInputDevice, CompositeInputDevice, InputDevice},
+///     input::{InputEvent, KeyCode, KeyEvent, KeyModifiers},
+/// };
+///
+/// let (sender, channel) = ChannelInputDevice::channel();
+///
+/// let mut device = CompositeInputDevice::new();
+/// device.add_device(channel);
+///
+/// let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+/// sender.send(InputEvent::Key(enter)).unwrap();
+///
+/// assert!(matches!(device.read(), Ok(InputEvent::Key(event)) if event == enter));
+/// ```
+#[derive(Default)]
+pub struct CompositeInputDevice {
+    devices: Vec<Box<dyn InputDevice>>,
+    next_device: usize,
+    ready_device: Option<usize>,
+}
+
+impl CompositeInputDevice {
+    /// Create a new composite device with no devices.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the specified device to be polled for events.
+    pub fn add_device(&mut self, device: impl InputDevice + 'static) {
+        self.devices.push(Box::new(device));
+    }
+
+    /// Poll each device once without waiting, starting with the next device in turn, returning the
+    /// index of the first with an event available.
+    fn poll_devices(&mut self) -> Result<Option<usize>> {
+        let count = self.devices.len();
+        for offset in 0..count {
+            let index = (self.next_device + offset) % count;
+            if self.devices[index].poll(Duration::ZERO)? {
+                return Ok(Some(index));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+impl InputDevice for CompositeInputDevice {
+    fn read(&mut self) -> Result<InputEvent> {
+        if self.devices.is_empty() {
+            return Err(disconnected_error());
+        }
+
+        while self.ready_device.is_none() {
+            self.poll(COMPOSITE_POLL_INTERVAL)?;
+        }
+
+        let index = self.ready_device.take().unwrap_or_default();
+        self.next_device = (index + 1) % self.devices.len();
+        self.devices[index].read()
+    }
+
+    fn poll(&mut self, timeout: Duration) -> Result<bool> {
+        if self.ready_device.is_some() {
+            return Ok(true);
+        }
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            self.ready_device = self.poll_devices()?;
+            if self.ready_device.is_some() {
+                return Ok(true);
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Ok(false);
+            }
+
+            thread::sleep(remaining.min(COMPOSITE_POLL_INTERVAL));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        input::{InputEvent, KeyCode, KeyEvent, KeyModifiers},
+        Error,
+    };
+
+    use super::{ChannelInputDevice, CompositeInputDevice, InputDevice};
+
+    #[test]
+    fn test_composite_input_device() {
+        let key = |ch| InputEvent::Key(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+
+        let (first_sender, first_channel) = ChannelInputDevice::channel();
+        let (second_sender, second_channel) = ChannelInputDevice::channel();
+
+        let mut device = CompositeInputDevice::new();
+        device.add_device(first_channel);
+        device.add_device(second_channel);
+        assert!(!device.poll(Duration::ZERO).unwrap());
+
+        for ch in ['a', 'b', 'c'] {
+            first_sender.send(key(ch)).unwrap();
+        }
+        second_sender.send(key('x')).unwrap();
+
+        // Devices take turns, so the busy first device doesn't starve the second
+        let mut received = String::new();
+        for _ in 0..4 {
+            if let InputEvent::Key(event) = device.read().unwrap() {
+                if let KeyCode::Char(ch) = event.code {
+                    received.push(ch);
+                }
+            }
+        }
+        assert_eq!("axbc", received);
+
+        drop(first_sender);
+        assert!(!device.poll(Duration::ZERO).unwrap());
+    }
+
+    #[test]
+    fn test_empty_composite_input_device() {
+        let mut device = CompositeInputDevice::new();
+        assert!(!device.poll(Duration::ZERO).unwrap());
+        assert!(matches!(device.read(), Err(Error::Terminal(_))));
     }
 }
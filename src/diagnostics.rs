@@ -0,0 +1,124 @@
+//! Structured diagnostics of form execution, emitted through `tracing` if the `tracing` feature is
+//! enabled and otherwise compiled away.
+
+/// Emit a `tracing` event at the specified level, e.g. `trace_event!(debug, step = 1, "message")`.
+macro_rules! trace_event {
+    ($level:ident, $($arg:tt)+) => {
+        #[cfg(feature = "tracing")]
+        tracing::$level!($($arg)+);
+    };
+}
+
+/// Enter a `tracing` span at the specified level until the end of the enclosing block, e.g.
+/// `trace_span!(DEBUG, "render", step = 1)`.
+macro_rules! trace_span {
+    ($level:ident, $($arg:tt)+) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::span!(tracing::Level::$level, $($arg)+).entered();
+    };
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use std::{
+        fmt,
+        sync::{Arc, Mutex},
+    };
+
+    use tracing::{
+        field::{Field, Visit},
+        span, Event, Metadata, Subscriber,
+    };
+
+    use crate::{
+        control::{Control, TextInput},
+        input::KeyCode,
+        step::{CompoundStep, Step},
+        test::{press, type_text},
+        Form, FormStatus,
+    };
+
+    /// A subscriber recording the messages of the events it receives.
+    struct MessageRecorder(Arc<Mutex<Vec<String>>>);
+
+    impl Subscriber for MessageRecorder {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _span: &span::Attributes<'_>) -> span::Id {
+            span::Id::from_u64(1)
+        }
+
+        fn record(&self, _span: &span::Id, _values: &span::Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut visitor = MessageVisitor(None);
+            event.record(&mut visitor);
+
+            if let Some(message) = visitor.0 {
+                self.0.lock().unwrap().push(message);
+            }
+        }
+
+        fn enter(&self, _span: &span::Id) {}
+
+        fn exit(&self, _span: &span::Id) {}
+    }
+
+    /// Extracts an event's message field.
+    struct MessageVisitor(Option<String>);
+
+    impl Visit for MessageVisitor {
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            if field.name() == "message" {
+                self.0 = Some(format!("{:?}", value));
+            }
+        }
+    }
+
+    #[test]
+    fn test_traced_navigation() {
+        let messages = Arc::new(Mutex::new(Vec::new()));
+        let recorder = MessageRecorder(messages.clone());
+
+        tracing::subscriber::with_default(recorder, || {
+            let mut form = Form::new();
+            for prompt in ["Enter a scope.", "Enter a summary."] {
+                let mut step = CompoundStep::new();
+                TextInput::new(prompt, false).add_to(&mut step);
+                step.add_to(&mut form);
+            }
+
+            type_text(&mut form, "a");
+            press(&mut form, KeyCode::Enter);
+            press(&mut form, KeyCode::Esc);
+            press(&mut form, KeyCode::Enter);
+            assert_eq!(FormStatus::Submitted, press(&mut form, KeyCode::Enter));
+        });
+
+        let messages = messages.lock().unwrap();
+        let received = messages
+            .iter()
+            .filter(|message| *message == "received input")
+            .count();
+        assert_eq!(5, received);
+
+        let navigation: Vec<_> = messages
+            .iter()
+            .filter(|message| message.ends_with("step") || message.ends_with("form"))
+            .filter(|message| *message != "focused step")
+            .collect();
+        assert_eq!(
+            vec![
+                "advanced to step",
+                "retreated to step",
+                "advanced to step",
+                "submitted form"
+            ],
+            navigation
+        );
+    }
+}
@@ -1,14 +1,53 @@
-use crossterm::event::{Event, KeyCode, KeyModifiers};
-use tty_interface::{pos, Interface, Position};
+use std::{
+    any::Any,
+    fmt,
+    io::Write,
+    ops::Range,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
+
+use tty_interface::{pos, Position};
 
+#[cfg(feature = "signals")]
+use crate::signal::{SignalGuard, POLL_INTERVAL};
 use crate::{
-    dependency::DependencyState,
+    backend::{CursorStyle, RenderBackend},
+    context::Context,
+    control::{Control, ControlHandle, ControlId, ControlMeta, REDACTION},
+    dependency::{Action, DependencyId, DependencyState, Evaluation},
     device::InputDevice,
-    step::{InputResult, Step},
+    history::History,
+    input::{EventInjector, InputEvent, Intercept, KeyCode, KeyEvent},
+    keybinding::{CancelPress, CancelTracker, KeyBinding, KeyBindings},
+    navigation::{BackAction, NavigationMode, NavigationPolicy},
+    overrides::Overrides,
+    preset::Presets,
+    prompts::confirm_transient,
+    state::FormState,
+    step::{CompoundStep, InputResult, Step},
+    storage::{FileStorage, StorageBackend},
+    style::{error_style, flash_style, help_style, muted_style, valid_style},
+    terminal,
+    text::{overlay_segment_style, truncate_segment, util::get_segment_width, Segment, Text},
     utility::render_segment,
-    Error, Result,
+    value::{Answers, ControlValue, FormValuesHandle, ValueStore},
+    ConfigIssue, Error, Result,
 };
 
+/// How often a form with a values handle or event injector checks it for changes made by other
+/// threads.
+pub(crate) const WATCH_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How long the help line is flashed to signal rejected input.
+const FLASH_DURATION: Duration = Duration::from_millis(150);
+
+/// The columns occupied by the status gutter, including its padding from the steps.
+const GUTTER_WIDTH: u16 = 2;
+
+/// The environment variable which, if set, enables the debug overlay of all forms.
+const DEBUG_VARIABLE: &str = "TTY_FORM_DEBUG";
+
 /// A TTY-based form with multiple steps and inputs.
 ///
 /// # Examples
@@ -46,6 +85,204 @@ pub struct Form {
 
     /// The last render's height.
     last_height: u16,
+
+    /// Whether to render in compatibility mode for terminals without relative positioning.
+    compat_mode: bool,
+
+    /// The tallest render's height, to which compatibility mode pads shorter renders.
+    tallest_height: u16,
+
+    /// Whether a gutter of each step's status is rendered left of the steps while the form executes.
+    status_gutter: bool,
+
+    /// Whether completed steps are rendered as single-line summaries while the form executes.
+    collapse_completed: bool,
+
+    /// The rows within which focused renders display steps, if limited.
+    viewport_height: Option<u16>,
+
+    /// Each step's height when it was last rendered, by which the viewport's steps are chosen.
+    step_heights: Vec<u16>,
+
+    /// The evaluation state of dependencies between the form's controls.
+    dependency_state: DependencyState,
+
+    /// The current values of the form's named controls.
+    values: ValueStore,
+
+    /// Whether the form's steps have been initialized.
+    initialized: bool,
+
+    /// Dependencies between controls declared at the form level, wired upon initialization.
+    control_dependencies: Vec<ControlDependency>,
+
+    /// An optional handler receiving the form's in-progress result as each step is completed.
+    draft_handler: Option<DraftHandler>,
+
+    /// The form's key bindings, such as those which cancel it.
+    key_bindings: KeyBindings,
+
+    /// Tracks key presses to detect cancellation, such as by a double-press.
+    cancel_tracker: CancelTracker,
+
+    /// Whether the form was most recently canceled by retreating from its first step.
+    retreated: bool,
+
+    /// Whether to record when each step is started and completed.
+    record_timing: bool,
+
+    /// The recorded timing of each step, if enabled.
+    step_timings: Vec<StepTiming>,
+
+    /// The indices of optional steps skipped with the skip keys, leaving them empty.
+    skipped_steps: Vec<usize>,
+
+    /// When the active step was last focused, if timing is recorded.
+    step_focused_at: Option<Instant>,
+
+    /// Where and how often to save the form's in-progress state, if enabled.
+    autosave: Option<Autosave>,
+
+    /// The values chosen in the form's previous executions, if history is enabled.
+    history: Option<FormHistory>,
+
+    /// The minimum terminal columns and rows required to render the form, if any.
+    min_size: Option<(u16, u16)>,
+
+    /// The position at which the form is rendered while it executes.
+    origin: Position,
+
+    /// Application data shared with the form's steps and controls.
+    context: Context,
+
+    /// Whether to render an overlay describing the form's layout and focus state.
+    debug: bool,
+
+    /// The interval at which the focused step is ticked between input events, if enabled.
+    tick: Option<Duration>,
+
+    /// When the focused step was last ticked.
+    last_tick: Instant,
+
+    /// How rejected input is signaled to the user.
+    reject_feedback: RejectFeedback,
+
+    /// When the help line's flash signaling rejected input ends, if it's flashing.
+    flash_until: Option<Instant>,
+    bell_pending: bool,
+
+    /// The style of the terminal's cursor while the form executes, if overridden.
+    cursor_style: Option<CursorStyle>,
+
+    /// The minimum interval between rendered frames, if capped.
+    frame_interval: Option<Duration>,
+
+    /// When the form was last rendered while executing.
+    last_frame: Instant,
+
+    /// Whether a render was skipped to honor the frame interval, and is yet to be rendered.
+    frame_pending: bool,
+
+    /// A handle by which other threads update this form's values, if one was requested.
+    values_handle: Option<FormValuesHandle>,
+
+    /// How the form's steps are revealed and moved between.
+    navigation_mode: NavigationMode,
+
+    /// How backward navigation keys behave, unless overridden by the active step.
+    navigation_policy: NavigationPolicy,
+
+    /// Transformations applied in order to the form's final result.
+    post_processors: Vec<PostProcessor>,
+
+    /// Called each time a step is completed, such as to insert follow-up steps.
+    router: Option<Router>,
+
+    /// Called with each application-defined event the form receives.
+    external_handler: Option<ExternalHandler>,
+
+    /// Decides how each key event is handled before the form's bindings and steps receive it.
+    event_interceptor: Option<EventInterceptor>,
+
+    /// A handle by which other threads inject events into this form, if one was requested.
+    event_injector: Option<EventInjector>,
+
+    /// Whether the focused control's extended documentation is shown in place of its drawer.
+    long_help_open: bool,
+
+    /// The validation error shown in place of the focused step's help, such as after a blocked
+    /// submission, until the next input.
+    validation_message: Option<String>,
+
+    /// Named sets of values which may be applied to the form's controls.
+    presets: Presets,
+
+    /// The index of the preset applied by the next press of the apply-preset keys.
+    next_preset: usize,
+
+    /// A notice shown in place of the focused step's help, such as after applying a preset, until
+    /// the next input.
+    notice: Option<String>,
+
+    /// Values for named controls provided by the embedding command line, applied as it begins.
+    overrides: Overrides,
+
+    /// The names of the controls and steps pre-filled by overrides.
+    prefilled: Vec<String>,
+
+    /// The names and values of overrides rejected by their controls, which fail the form's
+    /// execution.
+    rejected_overrides: Vec<(String, String)>,
+}
+
+/// A location to which a form's in-progress state is periodically saved.
+struct Autosave {
+    storage: Box<dyn StorageBackend>,
+    key: String,
+    interval: Duration,
+    last_saved: Option<Instant>,
+}
+
+/// The values chosen in a form's previous executions and where they're stored.
+struct FormHistory {
+    storage: Box<dyn StorageBackend>,
+    key: String,
+    history: History,
+}
+
+/// A handler receiving a form's in-progress result.
+type DraftHandler = Box<dyn FnMut(&str)>;
+
+/// A transformation of a form's final result.
+type PostProcessor = Box<dyn Fn(String) -> String>;
+
+/// A handler called with a form each time one of its steps is completed.
+type Router = Box<dyn FnMut(&mut Form, usize)>;
+
+/// A handler called with a form and each application-defined event it receives.
+type ExternalHandler = Box<dyn FnMut(&mut Form, &(dyn Any + Send))>;
+
+/// A function deciding how a form handles each key event it receives.
+type EventInterceptor = Box<dyn FnMut(KeyEvent) -> Intercept>;
+
+/// A dependency between two controls declared at the form level.
+struct ControlDependency {
+    id: DependencyId,
+    target: ControlId,
+    source: ControlId,
+    evaluation: Evaluation,
+    action: Action,
+}
+
+/// The status of a form after processing an input event.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum FormStatus {
+    /// The form is still accepting input.
+    Active,
+    /// The form's final step was completed and its result is ready.
+    Submitted,
+    /// The form was canceled before completion.
+    Canceled,
 }
 
 impl Default for Form {
@@ -56,6 +293,55 @@ impl Default for Form {
             active_step: 0,
             max_step: 0,
             last_height: 0,
+            compat_mode: false,
+            tallest_height: 0,
+            status_gutter: false,
+            collapse_completed: false,
+            viewport_height: None,
+            step_heights: Vec::new(),
+            dependency_state: DependencyState::new(),
+            values: ValueStore::new(),
+            initialized: false,
+            control_dependencies: Vec::new(),
+            draft_handler: None,
+            key_bindings: KeyBindings::default(),
+            cancel_tracker: CancelTracker::default(),
+            retreated: false,
+            record_timing: false,
+            step_timings: Vec::new(),
+            skipped_steps: Vec::new(),
+            step_focused_at: None,
+            autosave: None,
+            history: None,
+            min_size: None,
+            origin: pos!(0, 0),
+            debug: std::env::var_os(DEBUG_VARIABLE).is_some_and(|value| !value.is_empty()),
+            context: Context::new(),
+            tick: None,
+            last_tick: Instant::now(),
+            reject_feedback: RejectFeedback::default(),
+            flash_until: None,
+            bell_pending: false,
+            cursor_style: None,
+            frame_interval: None,
+            last_frame: Instant::now(),
+            frame_pending: false,
+            values_handle: None,
+            navigation_mode: NavigationMode::default(),
+            navigation_policy: NavigationPolicy::default(),
+            post_processors: Vec::new(),
+            router: None,
+            external_handler: None,
+            event_interceptor: None,
+            event_injector: None,
+            long_help_open: false,
+            validation_message: None,
+            presets: Presets::new(),
+            next_preset: 0,
+            notice: None,
+            overrides: Overrides::new(),
+            prefilled: Vec::new(),
+            rejected_overrides: Vec::new(),
         }
     }
 }
@@ -68,142 +354,3625 @@ impl Form {
 
     /// Append and return a compound step with multiple component controls.
     pub fn add_step(&mut self, step: Box<dyn Step>) {
-        self.steps.push(step);
+        self.insert_step(self.steps.len(), step);
     }
 
-    /// Execute the provided form and return its WYSIWYG result.
-    pub fn execute<D: InputDevice>(
-        mut self,
-        interface: &mut Interface,
-        input_device: &mut D,
-    ) -> Result<String> {
-        let mut dependency_state = DependencyState::new();
+    /// Insert a step at the specified index, returning whether it was inserted. Once the form has
+    /// begun, such as from its router, steps may only be inserted after the active step.
+    pub fn insert_step(&mut self, index: usize, mut step: Box<dyn Step>) -> bool {
+        if index > self.steps.len() || (self.initialized && index <= self.active_step) {
+            return false;
+        }
 
-        for (step_index, step) in self.steps.iter_mut().enumerate() {
-            step.initialize(&mut dependency_state, step_index);
+        if self.initialized {
+            step.initialize(&mut self.dependency_state, &self.context, index);
+
+            // In batch mode, every step remains shown, including those appended
+            if index <= self.max_step || self.navigation_mode == NavigationMode::Batch {
+                self.max_step += 1;
+            }
+
+            if self.record_timing {
+                self.step_timings.insert(index, StepTiming::default());
+            }
         }
 
-        self.render_form(interface, &dependency_state);
-        interface.apply()?;
+        for skipped_step in self.skipped_steps.iter_mut() {
+            if *skipped_step >= index {
+                *skipped_step += 1;
+            }
+        }
 
-        loop {
-            interface.set_cursor(None);
+        self.steps.insert(index, step);
+        self.reindex_steps(index + 1);
+        self.refresh_values();
 
-            if let Event::Key(key_event) = input_device.read()? {
-                if (KeyModifiers::CONTROL, KeyCode::Char('c'))
-                    == (key_event.modifiers, key_event.code)
-                {
-                    return self.cancel_form(interface, &dependency_state);
-                }
-
-                if let Some(action) =
-                    self.steps[self.active_step].update(&mut dependency_state, key_event)
-                {
-                    match action {
-                        InputResult::AdvanceForm => {
-                            if self.advance() {
-                                break;
-                            }
-                        }
-                        InputResult::RetreatForm => {
-                            if self.retreat() {
-                                return self.cancel_form(interface, &dependency_state);
-                            }
-                        }
-                    }
-                }
+        true
+    }
+
+    /// Remove and return the step at the specified index, if one exists. Once the form has begun,
+    /// such as from its router, only steps after the active step may be removed.
+    pub fn remove_step(&mut self, index: usize) -> Option<Box<dyn Step>> {
+        if index >= self.steps.len() || (self.initialized && index <= self.active_step) {
+            return None;
+        }
+
+        let step = self.steps.remove(index);
+
+        self.skipped_steps
+            .retain(|skipped_step| *skipped_step != index);
+        for skipped_step in self.skipped_steps.iter_mut() {
+            if *skipped_step > index {
+                *skipped_step -= 1;
+            }
+        }
+
+        if self.initialized {
+            self.dependency_state.remove_step_evaluations(index);
+
+            if index <= self.max_step {
+                self.max_step -= 1;
             }
 
-            self.render_form(interface, &dependency_state);
-            interface.apply()?;
+            if self.record_timing && index < self.step_timings.len() {
+                self.step_timings.remove(index);
+            }
         }
 
-        self.render_form(interface, &dependency_state);
-        interface.apply()?;
+        self.reindex_steps(index);
+        self.refresh_values();
 
-        let mut result = String::new();
+        Some(step)
+    }
+
+    /// Specify a router called each time a step is completed, before the form advances, with the
+    /// form and the completed step's index. The router may insert or remove subsequent steps, such
+    /// as to add follow-up steps based on earlier answers. A step completed again after retreating
+    /// to it calls the router again.
+    ///
+    /// # Examples
+    /// ```
+    /// use tty_form::{
+    ///     Form,
+    ///     step::{Step, CompoundStep, TextBlockStep},
+    ///     control::{Control, TextInput},
+    /// };
+    ///
+    /// let mut form = Form::new();
+    ///
+    /// let mut step = CompoundStep::new();
+    /// let mut components = TextInput::new("Enter the affected components:", false);
+    /// components.set_name("components");
+    /// components.add_to(&mut step);
+    /// step.add_to(&mut form);
+    ///
+    /// // Ask for details about each component once they've been entered
+    /// form.set_router(|form, completed_step| {
+    ///     if completed_step != 0 || form.step(1).is_some() {
+    ///         return;
+    ///     }
+    ///
+    ///     let components = form.values().get_text("components").unwrap_or_default().to_string();
+    ///     for (index, component) in components.split_whitespace().enumerate() {
+    ///         let prompt = format!("Describe the changes to {}:", component);
+    ///         form.insert_step(index + 1, Box::new(TextBlockStep::new(&prompt)));
+    ///     }
+    /// });
+    /// ```
+    pub fn set_router(&mut self, router: impl FnMut(&mut Form, usize) + 'static) {
+        self.router = Some(Box::new(router));
+    }
+
+    /// The step at the specified index, if one exists.
+    pub fn step(&self, index: usize) -> Option<&dyn Step> {
+        self.steps.get(index).map(|step| step.as_ref())
+    }
+
+    /// The step at the specified index mutably, if one exists.
+    pub fn step_mut(&mut self, index: usize) -> Option<&mut dyn Step> {
+        self.steps.get_mut(index).map(|step| step.as_mut())
+    }
+
+    /// The control with the specified name from any of this form's compound steps, if one exists
+    /// and is of the specified type.
+    pub fn get_control<T: Control>(&self, name: &str) -> Option<&T> {
+        self.steps
+            .iter()
+            .filter_map(|step| step.downcast_ref::<CompoundStep>())
+            .find_map(|step| step.get_control(name))
+    }
+
+    /// The control with the specified name mutably from any of this form's compound steps, if one
+    /// exists and is of the specified type.
+    pub fn get_control_mut<T: Control>(&mut self, name: &str) -> Option<&mut T> {
+        self.steps
+            .iter_mut()
+            .filter_map(|step| step.downcast_mut::<CompoundStep>())
+            .find_map(|step| step.get_control_mut(name))
+    }
+
+    /// The control referenced by the specified handle from any of this form's compound steps.
+    pub fn get<T: Control>(&self, handle: &ControlHandle<T>) -> Option<&T> {
+        self.steps
+            .iter()
+            .filter_map(|step| step.downcast_ref::<CompoundStep>())
+            .find_map(|step| step.get(handle))
+    }
+
+    /// The control referenced by the specified handle mutably from any of this form's compound
+    /// steps.
+    pub fn get_mut<T: Control>(&mut self, handle: &ControlHandle<T>) -> Option<&mut T> {
+        self.steps
+            .iter_mut()
+            .filter_map(|step| step.downcast_mut::<CompoundStep>())
+            .find_map(|step| step.get_mut(handle))
+    }
+
+    /// Declare a dependency of the target control on the source control, which may belong to
+    /// different steps. If the evaluation is true for the source, the action is applied to the
+    /// target. Both controls' steps must be added first, and dependencies must be declared before
+    /// the form is initialized by executing, updating, or previewing it.
+    ///
+    /// # Errors
+    /// If the form is already initialized or either control doesn't belong to any of its steps.
+    pub fn depend<T: Control, S: Control>(
+        &mut self,
+        target: &ControlHandle<T>,
+        source: &ControlHandle<S>,
+        evaluation: Evaluation,
+        action: Action,
+    ) -> Result<DependencyId> {
+        if self.initialized {
+            return Err(Error::Dependency(
+                "dependencies must be declared before the form is initialized".to_string(),
+            ));
+        }
 
-        for step in self.steps {
-            result.push_str(&step.result(&dependency_state));
+        for (role, id) in [("target", target.id()), ("source", source.id())] {
+            let is_known = self
+                .steps
+                .iter()
+                .filter_map(|step| step.downcast_ref::<CompoundStep>())
+                .any(|step| step.control_index(id).is_some());
+
+            if !is_known {
+                return Err(Error::Dependency(format!(
+                    "the dependency's {} control doesn't belong to any of the form's steps",
+                    role
+                )));
+            }
         }
 
-        result = result.trim().to_string();
+        let id = DependencyId::new();
 
-        Ok(result)
+        self.control_dependencies.push(ControlDependency {
+            id,
+            target: target.id(),
+            source: source.id(),
+            evaluation,
+            action,
+        });
+
+        Ok(id)
     }
 
-    /// Exits the form early by performing a final, unfocused render and returning a cancelation code.
-    fn cancel_form(
+    /// Specify whether to render in a compatibility mode for terminals without relative-positioning
+    /// support. The form's region never shrinks while executing, instead being padded with blank
+    /// lines, and is explicitly reset before the final render to avoid corrupting scrollback.
+    pub fn set_compat_mode(&mut self, compat_mode: bool) {
+        self.compat_mode = compat_mode;
+    }
+
+    /// Specify whether a gutter left of the steps indicates each step's status while the form
+    /// executes: `✓` complete, `●` active, or `!` needing attention before submission.
+    pub fn set_status_gutter(&mut self, status_gutter: bool) {
+        self.status_gutter = status_gutter;
+    }
+
+    /// Specify whether completed steps are rendered as a checkmark and a single-line summary of
+    /// their result while the form executes, expanding again when refocused.
+    pub fn set_collapse_completed(&mut self, collapse_completed: bool) {
+        self.collapse_completed = collapse_completed;
+    }
+
+    /// Limit focused renders to the steps fitting within the specified number of rows around the
+    /// active step, summarizing those before and after as one-line placeholders. Keeps the cost of
+    /// each render bounded for forms with many steps. The drawer is rendered beyond these rows.
+    pub fn set_viewport_height(&mut self, rows: u16) {
+        self.viewport_height = Some(rows);
+    }
+
+    /// Specify a handler to receive the form's in-progress result each time a step is completed,
+    /// such as to persist drafts of long entries. Sensitive values are masked in drafts.
+    pub fn set_draft_handler(&mut self, handler: impl FnMut(&str) + 'static) {
+        self.draft_handler = Some(Box::new(handler));
+    }
+
+    /// Set the minimum terminal size required to execute the form. While the terminal is smaller,
+    /// a message asking the user to enlarge it is shown in place of the form and input is paused.
+    pub fn set_min_size(&mut self, columns: u16, rows: u16) {
+        self.min_size = Some((columns, rows));
+    }
+
+    /// Check that the standard input and output are a supported terminal at least this form's
+    /// minimum size, if any, before executing it there. Fails with a dedicated error, such as
+    /// [Error::NoTerminal], whose [Error::hint] suggests a remedy, such that the caller may fall
+    /// back to a non-interactive mode. See [crate::terminal::check].
+    pub fn check_terminal(&self) -> Result<()> {
+        terminal::check(self.min_size)
+    }
+
+    /// Specify whether to render an overlay in the top-right corner describing each step's
+    /// boundaries, the active and furthest steps and controls, and the dependency evaluations.
+    /// Enabled by default if the `TTY_FORM_DEBUG` environment variable is set.
+    pub fn set_debug(&mut self, debug: bool) {
+        self.debug = debug;
+    }
+
+    /// Set application data, such as configuration, caches, or API clients, to share with this
+    /// form's steps and controls as they're initialized.
+    pub fn set_context<T: Any>(&mut self, context: T) {
+        self.context = Context::with(context);
+    }
+
+    /// This form's application data shared with its steps and controls.
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+
+    /// Set the interval at which the focused step is ticked between input events, such as to animate
+    /// spinners or debounce validation. Disabled by default.
+    pub fn set_tick(&mut self, interval: Duration) {
+        self.tick = Some(interval);
+    }
+
+    /// Specify how input rejected by the form's steps, such as a disallowed character or an attempt
+    /// to advance past an invalid value, is signaled to the user. Silent by default.
+    pub fn set_reject_feedback(&mut self, feedback: RejectFeedback) {
+        self.reject_feedback = feedback;
+    }
+
+    /// Set the terminal cursor's shape and blinking while the form executes, restoring the
+    /// terminal's default once it exits. Applied by rendering backends supporting cursor styles,
+    /// such as those writing [CursorStyle::escape_sequence] to their device. Unchanged by default.
+    pub fn set_cursor_style(&mut self, style: CursorStyle) {
+        self.cursor_style = Some(style);
+    }
+
+    /// Cap the rate at which the executing form is rendered, coalescing the renders of rapid input
+    /// such as key repeat into fewer frames at the cost of a few milliseconds' latency. Uncapped by
+    /// default.
+    pub fn set_max_fps(&mut self, frames_per_second: u8) {
+        self.frame_interval = Some(Duration::from_secs(1) / u32::from(frames_per_second.max(1)));
+    }
+
+    /// Specify a handler to receive each application-defined [InputEvent::External] event, whether
+    /// read from the input device or injected, along with the form to update in response.
+    pub fn set_external_handler(
         &mut self,
-        interface: &mut Interface,
-        dependency_state: &DependencyState,
-    ) -> Result<String> {
-        self.active_step = usize::MAX;
-        self.render_form(interface, &dependency_state);
-        interface.apply()?;
+        handler: impl FnMut(&mut Form, &(dyn Any + Send)) + 'static,
+    ) {
+        self.external_handler = Some(Box::new(handler));
+    }
 
-        return Err(Error::Canceled);
+    /// Set a function deciding how each key event is handled before the form's bindings and steps
+    /// receive it, such as to swallow keys or remap them to others. See [Intercept].
+    pub fn set_event_interceptor(
+        &mut self,
+        interceptor: impl FnMut(KeyEvent) -> Intercept + 'static,
+    ) {
+        self.event_interceptor = Some(Box::new(interceptor));
     }
 
-    /// Advance the form to its next step. Returns whether we've finished the form.
-    fn advance(&mut self) -> bool {
-        let is_last_step = self.active_step + 1 == self.steps.len();
-        if !is_last_step {
-            self.active_step += 1;
+    /// A handle by which other threads may inject events into this form while it executes, such as
+    /// to wake it once background work completes. Injected events are handled as the form ticks.
+    pub fn event_injector(&mut self) -> EventInjector {
+        self.event_injector
+            .get_or_insert_with(EventInjector::new)
+            .clone()
+    }
 
-            if self.active_step > self.max_step {
-                self.max_step = self.active_step;
-            }
+    /// Queue the specified event to be handled as the form next ticks, as if by its injector.
+    pub fn inject_event(&mut self, event: InputEvent) {
+        self.event_injector().inject(event);
+    }
+
+    /// A handle by which other threads may update this form's named values while it executes. The
+    /// changes are applied and rendered as the form ticks.
+    pub fn values_handle(&mut self) -> FormValuesHandle {
+        self.values_handle
+            .get_or_insert_with(FormValuesHandle::new)
+            .clone()
+    }
+
+    /// Set how this form's steps are revealed and moved between, one at a time by default.
+    pub fn set_navigation_mode(&mut self, mode: NavigationMode) {
+        self.navigation_mode = mode;
+    }
+
+    /// Set how backward navigation keys behave in this form's steps, unless overridden by a step.
+    pub fn set_navigation_policy(&mut self, policy: NavigationPolicy) {
+        self.navigation_policy = policy;
+    }
+
+    /// Add a transformation of the form's final result, applied after any previously-added
+    /// transformations. See [crate::postprocess] for common transformations.
+    pub fn add_post_processor(&mut self, post_processor: impl Fn(String) -> String + 'static) {
+        self.post_processors.push(Box::new(post_processor));
+    }
+
+    /// Set this form's key bindings, such as those which cancel it.
+    pub fn set_key_bindings(&mut self, key_bindings: KeyBindings) {
+        self.key_bindings = key_bindings;
+    }
+
+    /// This form's key bindings.
+    pub fn key_bindings(&self) -> &KeyBindings {
+        &self.key_bindings
+    }
+
+    /// Set whether to record when each step is started and completed, exposed through
+    /// [Form::form_result].
+    pub fn set_record_timing(&mut self, record_timing: bool) {
+        self.record_timing = record_timing;
+    }
+
+    /// Set the named sets of values which may be applied to this form's controls, either with
+    /// [Form::apply_preset] or by cycling through them with the apply-preset keys (Ctrl+P by
+    /// default). See [Presets::load_user] to load a user's presets from their configuration.
+    ///
+    /// # Examples
+    /// ```
+    /// use tty_form::{prefab, preset::Presets};
+    ///
+    /// let presets = Presets::parse(r#"
+    ///     [deps]
+    ///     commit_type = "chore"
+    ///     commit_scope = "deps"
+    /// "#)?;
+    ///
+    /// let mut form = prefab::conventional_commit();
+    /// form.set_presets(presets);
+    ///
+    /// assert!(form.apply_preset("deps"));
+    /// assert_eq!(Some("deps"), form.values().get_text("commit_scope"));
+    /// # Ok::<(), tty_form::Error>(())
+    /// ```
+    pub fn set_presets(&mut self, presets: Presets) {
+        self.presets = presets;
+        self.next_preset = 0;
+    }
+
+    /// This form's presets.
+    pub fn presets(&self) -> &Presets {
+        &self.presets
+    }
+
+    /// Fill this form's named controls with the values of the preset with the specified name,
+    /// returning whether it exists.
+    pub fn apply_preset(&mut self, name: &str) -> bool {
+        let values = match self.presets.get(name) {
+            Some(preset) => preset.values().clone(),
+            None => return false,
+        };
+
+        self.initialize();
+        self.apply_values(&values);
+        trace_event!(debug, preset = name, "applied preset");
+
+        true
+    }
+
+    /// Apply the next of this form's presets, cycling back to the first after the last, and note
+    /// which was applied.
+    fn apply_next_preset(&mut self) {
+        let index = self.next_preset % self.presets.len();
+        self.next_preset = index + 1;
+
+        let name = match self.presets.iter().nth(index) {
+            Some(preset) => preset.name().to_string(),
+            None => return,
+        };
+
+        self.apply_preset(&name);
+        self.notice = Some(format!(
+            "Applied preset \"{}\" ({}/{})",
+            name,
+            index + 1,
+            self.presets.len()
+        ));
+    }
+
+    /// Pre-fill this form's named controls with values provided by its embedding command line,
+    /// such as `--set commit_scope=ui`, as it begins. Overrides take precedence over recalled
+    /// history and, if read-only, lock their controls so that steps left without editable input
+    /// are skipped. Fails if any override names no control or step of this form, while executing
+    /// the form fails if a control rejects its override's value, such as an option it doesn't
+    /// offer.
+    ///
+    /// # Examples
+    /// ```
+    /// use tty_form::{overrides::Overrides, prefab};
+    ///
+    /// let mut overrides = Overrides::from_pairs(["commit_type=fix"])?;
+    /// overrides.set_read_only(true);
+    ///
+    /// let mut form = prefab::conventional_commit();
+    /// form.set_overrides(overrides)?;
+    ///
+    /// assert!(form.set_overrides(Overrides::from_pairs(["colour=blue"])?).is_err());
+    /// # Ok::<(), tty_form::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    /// If any override names no control or step of this form.
+    pub fn set_overrides(&mut self, overrides: Overrides) -> Result<()> {
+        let names: Vec<String> = self
+            .steps
+            .iter()
+            .flat_map(|step| step.form_values())
+            .map(|(name, _)| name)
+            .collect();
+
+        if let Some((unknown, _)) = overrides
+            .iter()
+            .find(|(name, _)| !names.contains(&name.to_string()))
+        {
+            return Err(Error::Override(format!(
+                "no control is named \"{}\"; expected one of: {}",
+                unknown,
+                names.join(", ")
+            )));
         }
 
-        is_last_step
+        self.overrides = overrides;
+        Ok(())
     }
 
-    /// Retreat the form to its previous step. Returns whether we're at the first step.
-    fn retreat(&mut self) -> bool {
-        let is_first_step = self.active_step == 0;
-        if !is_first_step {
-            self.active_step -= 1;
+    /// Load the values chosen in this form's previous executions from the specified key of a
+    /// storage backend, such that its controls may recall them as it begins, for instance to
+    /// preselect an option's last choice. Once the form is submitted, its controls' values are
+    /// recorded and the history saved back to the key.
+    pub fn set_history(&mut self, storage: impl StorageBackend + 'static, key: &str) -> Result<()> {
+        let history = storage
+            .load(key)?
+            .as_deref()
+            .and_then(History::deserialize)
+            .unwrap_or_default();
+
+        self.history = Some(FormHistory {
+            storage: Box::new(storage),
+            key: key.to_string(),
+            history,
+        });
+
+        Ok(())
+    }
+
+    /// Periodically save the form's in-progress state to the specified file while it executes. If
+    /// the file exists when the form is next executed, the user is offered to restore it. The file
+    /// is removed once the form is submitted.
+    ///
+    /// Only the active step and the values of named controls and steps are saved, so the contents
+    /// of unnamed ones, such as an unnamed [crate::step::TextBlockStep]'s text, are lost.
+    /// Sensitive values are never saved. The state is saved after handling input once the
+    /// interval has elapsed, not while the form sits idle.
+    pub fn set_autosave(&mut self, path: PathBuf, interval: Duration) {
+        let directory = path.parent().unwrap_or(Path::new("")).to_path_buf();
+        let key = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        self.set_autosave_storage(FileStorage::new(directory), &key, interval);
+    }
+
+    /// Periodically save the form's in-progress state under the specified key of a storage
+    /// backend while it executes. If the key has stored contents when the form is next executed,
+    /// the user is offered to restore it. The contents are removed once the form is submitted.
+    /// What's saved, and when, is as described for [Form::set_autosave].
+    pub fn set_autosave_storage(
+        &mut self,
+        storage: impl StorageBackend + 'static,
+        key: &str,
+        interval: Duration,
+    ) {
+        self.autosave = Some(Autosave {
+            storage: Box::new(storage),
+            key: key.to_string(),
+            interval,
+            last_saved: None,
+        });
+    }
+
+    /// A snapshot of this form's progress, which may later be restored.
+    pub fn state(&self) -> FormState {
+        FormState::new(self.active_step, self.values.clone())
+    }
+
+    /// The latest evaluation of each of this form's dependencies, such as to assert on them
+    /// mid-form.
+    pub fn dependency_state(&self) -> &DependencyState {
+        &self.dependency_state
+    }
+
+    /// Register a callback notified with a dependency and its new evaluation each time one of this
+    /// form's dependencies changes.
+    pub fn observe_dependencies(&mut self, observer: impl FnMut(DependencyId, bool) + 'static) {
+        self.dependency_state.add_observer(observer);
+    }
+
+    /// A snapshot of this form's progress without its sensitive values, safe to persist.
+    pub(crate) fn redacted_state(&self) -> FormState {
+        let mut values = self.values.clone();
+        for name in self.sensitive_names() {
+            values.remove(&name);
         }
 
-        is_first_step
+        FormState::new(self.active_step, values)
     }
 
-    /// Re-render the form's updated state.
-    fn render_form(&mut self, interface: &mut Interface, dependency_state: &DependencyState) {
-        for line in 0..self.last_height {
-            interface.clear_line(line);
+    /// This form's current result with its sensitive values masked, safe to share as a draft.
+    pub(crate) fn redacted_result(&self) -> String {
+        let mut values = self.values.clone();
+        for name in self.sensitive_names() {
+            values.set(&name, ControlValue::Text(REDACTION.to_string()));
         }
 
-        let mut drawer = None;
-        let mut line = 1;
+        self.result_of(|step| step.redacted_result(&self.dependency_state, &values))
+    }
+
+    /// The names of this form's controls whose values are sensitive.
+    fn sensitive_names(&self) -> Vec<String> {
+        self.controls_meta()
+            .into_iter()
+            .filter(|meta| meta.sensitive())
+            .filter_map(|meta| meta.name().map(str::to_string))
+            .collect()
+    }
+
+    /// Describe the specified key event for diagnostics, redacting it if the focused control is
+    /// sensitive.
+    #[cfg(feature = "tracing")]
+    fn traced_key(&self, key_event: &KeyEvent) -> String {
+        let is_sensitive = self.steps[self.active_step]
+            .focused_control_meta()
+            .is_some_and(|meta| meta.sensitive());
+
+        if is_sensitive {
+            return REDACTION.to_string();
+        }
+
+        format!("{:?}", key_event.code)
+    }
+
+    /// Restore a snapshot of this form's progress, updating its named controls and steps and
+    /// returning to the snapshot's active step.
+    pub fn restore(&mut self, state: &FormState) {
+        self.apply_values(state.values());
+
+        if !self.steps.is_empty() {
+            self.active_step = state.active_step().min(self.steps.len() - 1);
+            self.max_step = self.max_step.max(self.active_step);
+        }
+    }
+
+    /// Detect problems with this form's configuration before it's executed, such as steps without
+    /// focusable controls, dependencies on evaluations nothing provides, duplicate names, and
+    /// static text exceeding its step's maximum line length.
+    ///
+    /// # Examples
+    /// ```
+    /// use tty_form::{
+    ///     ConfigIssue, Form,
+    ///     step::{Step, CompoundStep},
+    ///     control::{Control, StaticText},
+    /// };
+    ///
+    /// let mut form = Form::new();
+    /// let mut step = CompoundStep::new();
+    /// StaticText::new("Nothing to enter here.").add_to(&mut step);
+    /// step.add_to(&mut form);
+    ///
+    /// let issues = form.validate_configuration();
+    /// assert_eq!(vec![ConfigIssue::NoFocusableControls { step: 0 }], issues);
+    /// ```
+    pub fn validate_configuration(&self) -> Vec<ConfigIssue> {
+        let mut issues = Vec::new();
+
         for (step_index, step) in self.steps.iter().enumerate() {
-            if step_index > self.max_step {
-                break;
+            issues.extend(step.configuration_issues(step_index));
+        }
+
+        // Dependencies must be sourced from an evaluation provided by some step or control
+        let evaluation_ids: Vec<DependencyId> = self
+            .steps
+            .iter()
+            .flat_map(|step| step.evaluation_ids())
+            .chain(
+                self.control_dependencies
+                    .iter()
+                    .map(|dependency| dependency.id),
+            )
+            .collect();
+
+        for (step_index, step) in self.steps.iter().enumerate() {
+            let unregistered = step
+                .dependency_ids()
+                .iter()
+                .any(|id| !evaluation_ids.contains(id));
+
+            if unregistered {
+                issues.push(ConfigIssue::UnregisteredDependency { step: step_index });
             }
+        }
 
-            let step_height = step.render(
-                interface,
-                dependency_state,
-                pos!(0, line),
-                step_index == self.active_step,
-            );
+        // Form-level dependencies must reference controls in the form's compound steps
+        for dependency in &self.control_dependencies {
+            let is_known = |control| {
+                self.steps
+                    .iter()
+                    .filter_map(|step| step.downcast_ref::<CompoundStep>())
+                    .any(|step| step.control_index(control).is_some())
+            };
 
-            line += step_height;
+            if !is_known(dependency.source) || !is_known(dependency.target) {
+                issues.push(ConfigIssue::UnknownControl);
+            }
+        }
 
-            if step_index == self.active_step {
-                render_segment(interface, pos!(0, 0), step.help());
-                drawer = step.drawer();
+        let mut names: Vec<String> = Vec::new();
+        for (name, _) in self.steps.iter().flat_map(|step| step.form_values()) {
+            let is_duplicate = names.contains(&name);
+            let is_reported = issues.contains(&ConfigIssue::DuplicateName { name: name.clone() });
+
+            if is_duplicate && !is_reported {
+                issues.push(ConfigIssue::DuplicateName { name: name.clone() });
             }
+
+            names.push(name);
         }
 
-        if let Some(drawer) = drawer {
-            for item in drawer {
-                render_segment(interface, pos!(0, line), item);
-                line += 1;
+        issues
+    }
+
+    /// A tree describing this form's structure: its steps and their controls with their names,
+    /// kinds, prompts, and the dependency edges between them, such as for logs and error reports.
+    /// Form-level dependencies are included once the form is initialized.
+    ///
+    /// # Examples
+    /// ```
+    /// use tty_form::{
+    ///     Form,
+    ///     step::{Step, CompoundStep, TextBlockStep},
+    ///     control::{Control, StaticText, TextInput},
+    /// };
+    ///
+    /// let mut form = Form::new();
+    ///
+    /// let mut step = CompoundStep::new();
+    /// StaticText::new("Scope: ").add_to(&mut step);
+    /// let mut scope = TextInput::new("Enter a scope.", false);
+    /// scope.set_name("scope");
+    /// scope.add_to(&mut step);
+    /// step.add_to(&mut form);
+    ///
+    /// TextBlockStep::new("Enter a body.").add_to(&mut form);
+    ///
+    /// assert_eq!(
+    ///     "Form\n\
+    ///      ├── CompoundStep\n\
+    ///      │   ├── Static \"Scope: \"\n\
+    ///      │   └── Text scope \"Enter a scope.\"\n\
+    ///      └── TextBlockStep \"Enter a body.\"",
+    ///     form.describe(),
+    /// );
+    /// ```
+    pub fn describe(&self) -> String {
+        let mut lines = vec!["Form".to_string()];
+
+        for (step_index, step) in self.steps.iter().enumerate() {
+            let is_last_step = step_index + 1 == self.steps.len();
+            let (branch, indent) = if is_last_step {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            };
+
+            let mut step_lines = step.describe().into_iter();
+            if let Some(header) = step_lines.next() {
+                lines.push(format!("{}{}", branch, header));
+            }
+
+            let control_lines: Vec<String> = step_lines.collect();
+            for (control_index, control_line) in control_lines.iter().enumerate() {
+                let control_branch = if control_index + 1 == control_lines.len() {
+                    "└── "
+                } else {
+                    "├── "
+                };
+                lines.push(format!("{}{}{}", indent, control_branch, control_line));
             }
         }
 
-        self.last_height = line;
+        lines.join("\n")
+    }
+
+    /// Compute the result this form would produce from the specified answers, including the
+    /// effects of dependencies and post-processors, without rendering anything. The form's own
+    /// values are restored afterward. Controls and steps whose values the answers change are
+    /// restored as by [Form::restore], resetting their cursor, focus, and validation, while the
+    /// rest are undisturbed.
+    ///
+    /// # Examples
+    /// ```
+    /// use tty_form::{
+    ///     Form,
+    ///     step::{Step, CompoundStep},
+    ///     control::{Control, StaticText, TextInput},
+    ///     value::{Answers, ControlValue},
+    /// };
+    ///
+    /// let mut form = Form::new();
+    /// let mut step = CompoundStep::new();
+    /// StaticText::new("Hello, ").add_to(&mut step);
+    /// let mut name = TextInput::new("Enter your name:", false);
+    /// name.set_name("name");
+    /// name.add_to(&mut step);
+    /// step.add_to(&mut form);
+    ///
+    /// let mut answers = Answers::new();
+    /// answers.set("name", ControlValue::Text("Daniel".to_string()));
+    ///
+    /// assert_eq!("Hello, Daniel", form.preview_result(&answers));
+    /// assert_eq!(Some(""), form.values().get_text("name"));
+    /// ```
+    pub fn preview_result(&mut self, answers: &Answers) -> String {
+        self.initialize();
+
+        let mut changes = ValueStore::new();
+        let mut previous_values = ValueStore::new();
+        for (name, value) in answers.iter() {
+            let previous_value = self.values.get(name);
+            if previous_value != Some(value) {
+                changes.set(name, value.clone());
+
+                if let Some(previous_value) = previous_value {
+                    previous_values.set(name, previous_value.clone());
+                }
+            }
+        }
+
+        self.apply_values(&changes);
+
+        let result = self.result();
+        self.apply_values(&previous_values);
+
+        result
+    }
+
+    /// Update this form's named controls and steps with the specified values.
+    fn apply_values(&mut self, values: &ValueStore) {
+        for (name, value) in values.iter() {
+            for step in self.steps.iter_mut() {
+                step.set_form_value(&mut self.dependency_state, name, value);
+            }
+        }
+
+        self.refresh_values();
+    }
+
+    /// Parse a result previously produced by this form back into its state, such that it may be
+    /// restored to edit the result in place. Returns nothing if the text couldn't be parsed by the
+    /// form's steps in order.
+    ///
+    /// # Examples
+    /// ```
+    /// use tty_form::prefab;
+    ///
+    /// let form = prefab::conventional_commit();
+    /// let message = "feat(ui): add a button\n\nIt's blue.\n\nRefs: #1\nBREAKING CHANGE: Gone.";
+    /// let state = form.parse(message).unwrap();
+    ///
+    /// assert_eq!(Some("ui"), state.values().get_text("commit_scope"));
+    /// assert_eq!(Some("It's blue."), state.values().get_text("commit_body"));
+    /// assert_eq!(Some("Gone."), state.values().get_text("commit_breaking_change"));
+    ///
+    /// let mut form = prefab::conventional_commit();
+    /// form.restore(&state);
+    /// ```
+    pub fn parse(&self, text: &str) -> Option<FormState> {
+        let parsed_values = parse_steps(&self.steps, text.trim())?;
+
+        let mut values = ValueStore::new();
+        for (name, value) in parsed_values {
+            values.set(&name, value);
+        }
+
+        Some(FormState::new(0, values))
+    }
+
+    /// Execute the provided form and return its structured result.
+    pub fn execute_for_result<B: RenderBackend, D: InputDevice>(
+        mut self,
+        interface: &mut B,
+        input_device: &mut D,
+    ) -> Result<FormResult> {
+        self.run(interface, input_device)?;
+        Ok(self.form_result())
+    }
+
+    /// Execute the provided form and stream its WYSIWYG result into the specified writer.
+    pub fn execute_to<B: RenderBackend, D: InputDevice, W: Write>(
+        mut self,
+        interface: &mut B,
+        input_device: &mut D,
+        writer: &mut W,
+    ) -> Result<()> {
+        self.run(interface, input_device)?;
+        self.write_result(writer)
+    }
+
+    /// Execute the provided form and return its WYSIWYG result.
+    pub fn execute<B: RenderBackend, D: InputDevice>(
+        mut self,
+        interface: &mut B,
+        input_device: &mut D,
+    ) -> Result<String> {
+        self.run(interface, input_device)?;
+        Ok(self.result())
+    }
+
+    /// Run the form's input loop until it is submitted or canceled. With the `signals` feature,
+    /// receiving SIGINT or SIGTERM cancels the form so the caller may restore the terminal.
+    pub(crate) fn run<B: RenderBackend, D: InputDevice>(
+        &mut self,
+        interface: &mut B,
+        input_device: &mut D,
+    ) -> Result<()> {
+        trace_span!(DEBUG, "form", steps = self.steps.len());
+
+        self.check_overrides()?;
+        self.offer_autosave_restore(interface, input_device)?;
+
+        if self.drive(interface, input_device)? == FormStatus::Canceled {
+            return self.cancel_form(interface);
+        }
+
+        self.finish(interface)
+    }
+
+    /// Drive the form's input loop at its origin until it is submitted or canceled, returning
+    /// which. This loop is shared by every way of executing a form, such as within a chain.
+    pub(crate) fn drive<B: RenderBackend, D: InputDevice>(
+        &mut self,
+        interface: &mut B,
+        input_device: &mut D,
+    ) -> Result<FormStatus> {
+        self.check_overrides()?;
+
+        // The cursor's style is restored however the loop exits
+        if let Some(cursor_style) = self.cursor_style {
+            interface.set_cursor_style(Some(cursor_style))?;
+        }
+
+        let result = self.drive_loop(interface, input_device);
+
+        if self.cursor_style.is_some() {
+            interface.set_cursor_style(None)?;
+        }
+
+        result
+    }
+
+    /// Run the form's input loop until it is submitted or canceled.
+    fn drive_loop<B: RenderBackend, D: InputDevice>(
+        &mut self,
+        interface: &mut B,
+        input_device: &mut D,
+    ) -> Result<FormStatus> {
+        #[cfg(feature = "signals")]
+        let signals = SignalGuard::register()?;
+
+        self.render_running(interface);
+        interface.apply()?;
+        self.last_tick = Instant::now();
+        self.last_frame = Instant::now();
+
+        loop {
+            interface.set_cursor(None);
+
+            #[cfg(feature = "signals")]
+            if signals.received() {
+                return Ok(FormStatus::Canceled);
+            }
+
+            // Between input events, tick and re-render any step whose rendering changes over time,
+            // and render any frame the form or backend deferred
+            let timeout = [
+                self.poll_timeout(),
+                self.frame_delay(),
+                self.until_flash_ends(),
+                interface.frame_delay(),
+            ]
+            .into_iter()
+            .flatten()
+            .min();
+            if let Some(timeout) = timeout {
+                if !input_device.poll(timeout)? {
+                    match self.tick_if_due() {
+                        FormStatus::Active => {}
+                        status => return Ok(status),
+                    }
+
+                    self.render_frame(interface)?;
+                    continue;
+                }
+            }
+
+            let event = input_device.read()?;
+            if let InputEvent::Resize(columns, rows) = event {
+                interface.resize(columns, rows);
+            }
+
+            // Input is paused while the terminal is too small, other than to cancel the form
+            if !self.meets_min_size(interface) {
+                if let InputEvent::Key(key_event) = event {
+                    let press = self
+                        .cancel_tracker
+                        .press(&self.key_bindings, &key_event, true);
+                    if press == CancelPress::Cancel {
+                        return Ok(FormStatus::Canceled);
+                    }
+                }
+
+                self.render_frame(interface)?;
+                continue;
+            }
+
+            let status = match self.update(event) {
+                FormStatus::Active => self.tick_if_due(),
+                status => status,
+            };
+
+            if status != FormStatus::Active {
+                return Ok(status);
+            }
+
+            self.autosave_if_due()?;
+
+            self.render_frame(interface)?;
+        }
+    }
+
+    /// Render the submitted form's final, focused state and complete it.
+    pub(crate) fn finish(&mut self, interface: &mut dyn RenderBackend) -> Result<()> {
+        self.reset_region(interface, self.origin);
+        self.render_at(interface, self.origin, true);
+        interface.apply()?;
+
+        self.complete()
+    }
+
+    /// Remove the submitted form's autosaved state and record its history, if enabled.
+    pub(crate) fn complete(&mut self) -> Result<()> {
+        self.remove_autosave()?;
+        self.save_history()
+    }
+
+    /// Set the position at which the form is rendered while it executes.
+    pub(crate) fn set_origin(&mut self, origin: Position) {
+        self.origin = origin;
+    }
+
+    /// Render the executing form and apply it, unless it was last rendered too recently for its
+    /// frame rate cap, in which case the render is deferred until the cap permits it.
+    fn render_frame(&mut self, interface: &mut dyn RenderBackend) -> Result<()> {
+        if self.bell_pending {
+            interface.bell()?;
+            self.bell_pending = false;
+        }
+
+        if self
+            .until_next_frame()
+            .is_some_and(|delay| !delay.is_zero())
+        {
+            self.frame_pending = true;
+            return Ok(());
+        }
+
+        self.render_running(interface);
+        interface.apply()?;
+        self.last_frame = Instant::now();
+        self.frame_pending = false;
+
+        Ok(())
+    }
+
+    /// How long until a deferred render is permitted by the frame rate cap, if one is pending.
+    fn frame_delay(&self) -> Option<Duration> {
+        if !self.frame_pending {
+            return None;
+        }
+
+        self.until_next_frame()
+    }
+
+    /// How long until the frame rate cap permits the next render, if it's capped.
+    fn until_next_frame(&self) -> Option<Duration> {
+        self.frame_interval
+            .map(|interval| interval.saturating_sub(self.last_frame.elapsed()))
+    }
+
+    /// Signal rejected input to the user as configured. The bell is rung with the next frame.
+    fn signal_rejection(&mut self) {
+        match self.reject_feedback {
+            RejectFeedback::Silent => {}
+            RejectFeedback::Bell => self.bell_pending = true,
+            RejectFeedback::Flash => self.flash_until = Some(Instant::now() + FLASH_DURATION),
+        }
+    }
+
+    /// How long until the help line's flash ends, if it's flashing.
+    fn until_flash_ends(&self) -> Option<Duration> {
+        let now = Instant::now();
+        self.flash_until
+            .filter(|until| *until > now)
+            .map(|until| until - now)
+    }
+
+    /// How long to wait for an input event before re-rendering, if the form should be re-rendered
+    /// without input, such as to tick steps, rotate example hints, or check for signals.
+    fn poll_timeout(&self) -> Option<Duration> {
+        let until_tick = self
+            .tick_interval()
+            .map(|interval| interval.saturating_sub(self.last_tick.elapsed()));
+
+        #[cfg(feature = "signals")]
+        let until_tick = Some(until_tick.map_or(POLL_INTERVAL, |until| until.min(POLL_INTERVAL)));
+
+        until_tick
+    }
+
+    /// The interval at which the focused step is ticked: the form's tick interval, the step's
+    /// refresh interval, or the watch interval of the form's handles and any step's, whichever is
+    /// shortest, if any.
+    fn tick_interval(&self) -> Option<Duration> {
+        let refresh = self
+            .steps
+            .get(self.active_step)
+            .and_then(|step| step.refresh_interval());
+        let watch = (self.values_handle.is_some()
+            || self.event_injector.is_some()
+            || self.steps.iter().any(|step| step.has_handle()))
+        .then_some(WATCH_INTERVAL);
+
+        [self.tick, refresh, watch].into_iter().flatten().min()
+    }
+
+    /// Handle any events injected by other threads, then tick the focused step if its tick
+    /// interval has elapsed since it was last ticked, applying any action it returns.
+    pub(crate) fn tick_if_due(&mut self) -> FormStatus {
+        if let Some(injector) = &self.event_injector {
+            for event in injector.take() {
+                let status = self.update(event);
+                if status != FormStatus::Active {
+                    return status;
+                }
+            }
+        }
+
+        if self
+            .tick_interval()
+            .is_none_or(|interval| self.last_tick.elapsed() < interval)
+        {
+            return FormStatus::Active;
+        }
+
+        self.tick()
+    }
+
+    /// Tick the focused step, applying any action it returns.
+    fn tick(&mut self) -> FormStatus {
+        self.last_tick = Instant::now();
+
+        // Apply any values changed by other threads before the step observes them
+        if let Some(handle) = &self.values_handle {
+            let values = handle.take();
+            if values.iter().next().is_some() {
+                self.apply_values(&values);
+            }
+        }
+
+        let action = match self.steps.get_mut(self.active_step) {
+            Some(step) => step.tick(),
+            None => None,
+        };
+
+        // Ticks may change controls' values, such as selecting an option once options load
+        self.refresh_values();
+
+        match action {
+            Some(action) => self.apply_action(action),
+            None => FormStatus::Active,
+        }
+    }
+
+    /// Render the executing form, or a message to enlarge the terminal if it's too small.
+    fn render_running(&mut self, interface: &mut dyn RenderBackend) {
+        if self.meets_min_size(interface) {
+            self.render_at(interface, self.origin, true);
+            return;
+        }
+
+        let (columns, rows) = self.min_size.unwrap_or_default();
+        let message = format!("Please enlarge your terminal (need {}x{})", columns, rows);
+
+        // Center the message horizontally within the terminal's current width
+        let message_width = get_segment_width(&Text::new(message.clone()).as_segment()) as u16;
+        let width = interface.width().unwrap_or_default();
+        let x = width.saturating_sub(message_width) / 2;
+
+        self.clear_region(interface, self.origin);
+        render_segment(
+            interface,
+            pos!(x, self.origin.y()),
+            Text::new_styled(message, error_style()).as_segment(),
+        );
+        interface.set_cursor(None);
+
+        self.last_height = 1;
+    }
+
+    /// Whether the interface meets the form's minimum size, if it has one and the size is known.
+    fn meets_min_size(&self, interface: &dyn RenderBackend) -> bool {
+        let (columns, rows) = match self.min_size {
+            Some(min_size) => min_size,
+            None => return true,
+        };
+
+        interface.width().is_none_or(|width| width >= columns)
+            && interface.height().is_none_or(|height| height >= rows)
+    }
+
+    /// If an autosaved state exists, offer to restore it before the form begins.
+    pub(crate) fn offer_autosave_restore<B: RenderBackend, D: InputDevice>(
+        &mut self,
+        interface: &mut B,
+        input_device: &mut D,
+    ) -> Result<()> {
+        let serialized = match &self.autosave {
+            Some(autosave) => autosave.storage.load(&autosave.key)?,
+            None => return Ok(()),
+        };
+
+        if let Some(state) = serialized.as_deref().and_then(FormState::deserialize) {
+            if confirm_transient("Restore your unsaved draft?", interface, input_device)? {
+                self.restore(&state);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Save the form's state if autosave is enabled and its interval has elapsed.
+    fn autosave_if_due(&mut self) -> Result<()> {
+        let state = self.redacted_state();
+
+        let autosave = match &mut self.autosave {
+            Some(autosave) => autosave,
+            None => return Ok(()),
+        };
+
+        if let Some(last_saved) = autosave.last_saved {
+            if last_saved.elapsed() < autosave.interval {
+                return Ok(());
+            }
+        }
+
+        autosave.storage.save(&autosave.key, &state.serialize())?;
+        autosave.last_saved = Some(Instant::now());
+
+        Ok(())
+    }
+
+    /// Remove the form's autosaved state, if any, once it's no longer needed.
+    fn remove_autosave(&mut self) -> Result<()> {
+        if let Some(autosave) = &mut self.autosave {
+            autosave.storage.remove(&autosave.key)?;
+        }
+
+        Ok(())
+    }
+
+    /// Record the submitted form's values in its history and save it, if history is enabled.
+    fn save_history(&mut self) -> Result<()> {
+        if let Some(history) = &mut self.history {
+            for step in &self.steps {
+                step.remember(&mut history.history);
+            }
+
+            history
+                .storage
+                .save(&history.key, &history.history.serialize())?;
+        }
+
+        Ok(())
+    }
+
+    /// The current values of the form's named controls.
+    pub fn values(&self) -> &ValueStore {
+        &self.values
+    }
+
+    /// Render this form into a host interface with its top-left corner at the specified origin,
+    /// returning the rendered height. Only the region right of the origin's column is drawn over.
+    /// If focused, the active step is highlighted and positions the interface's cursor; otherwise
+    /// the cursor is left for the host to manage.
+    pub fn render_at(
+        &mut self,
+        interface: &mut dyn RenderBackend,
+        origin: Position,
+        is_focused: bool,
+    ) -> u16 {
+        self.initialize();
+        self.refresh_values();
+
+        let focused_step = if is_focused {
+            Some(self.active_step)
+        } else {
+            None
+        };
+
+        self.render_form(interface, origin, focused_step)
+    }
+
+    /// Forward an input event to the form's active step, returning the form's resulting status.
+    /// Used by applications which host the form within their own event loop.
+    pub fn update(&mut self, event: InputEvent) -> FormStatus {
+        self.initialize();
+
+        let key_event = match event {
+            InputEvent::Key(key_event) => key_event,
+            InputEvent::Tick => return self.tick(),
+            InputEvent::External(payload) => {
+                self.handle_external(payload.as_ref());
+                return FormStatus::Active;
+            }
+            InputEvent::Resize(..) | InputEvent::Refresh => return FormStatus::Active,
+        };
+
+        self.retreated = false;
+
+        // The application may swallow or remap keys before anything else handles them
+        let intercept = match &mut self.event_interceptor {
+            Some(interceptor) => interceptor(key_event),
+            None => Intercept::Pass,
+        };
+
+        let key_event = match intercept {
+            Intercept::Pass => key_event,
+            Intercept::Swallow => return FormStatus::Active,
+            Intercept::Remap(remapped) => remapped,
+            Intercept::Submit => return self.submit(),
+        };
+
+        trace_event!(trace, step = self.active_step, key = %self.traced_key(&key_event), "received input");
+
+        let step_is_read_only = self.steps[self.active_step].read_only();
+        match self
+            .cancel_tracker
+            .press(&self.key_bindings, &key_event, step_is_read_only)
+        {
+            CancelPress::Pass => {}
+            CancelPress::Armed => {
+                // The first of a double-press only arms cancellation, rather than reaching the step
+                let binding = KeyBinding::new(key_event.code, key_event.modifiers);
+                self.notice = Some(format!("Press {} again to cancel", binding));
+                return FormStatus::Active;
+            }
+            CancelPress::Cancel => {
+                trace_event!(debug, step = self.active_step, "canceled form");
+                return FormStatus::Canceled;
+            }
+        }
+
+        self.validation_message = None;
+        self.notice = None;
+
+        if self.key_bindings.is_toggle_help(&key_event) {
+            self.long_help_open = !self.long_help_open;
+            return FormStatus::Active;
+        }
+
+        if self.key_bindings.is_next_error(&key_event) {
+            self.focus_next_error();
+            return FormStatus::Active;
+        }
+
+        if self.key_bindings.is_submit(&key_event) {
+            return self.submit();
+        }
+
+        if self.key_bindings.is_skip(&key_event) && self.steps[self.active_step].optional() {
+            return self.skip_step();
+        }
+
+        if self.key_bindings.is_apply_preset(&key_event) && !self.presets.is_empty() {
+            self.apply_next_preset();
+            return FormStatus::Active;
+        }
+
+        // In batch mode, arrows the focused step doesn't use move focus between steps
+        let is_arrow = key_event.code == KeyCode::Up || key_event.code == KeyCode::Down;
+        if self.navigation_mode == NavigationMode::Batch
+            && is_arrow
+            && !self.steps[self.active_step].captures_arrow(key_event.code)
+        {
+            self.focus_adjacent_step(key_event.code == KeyCode::Down);
+            return FormStatus::Active;
+        }
+
+        let step = &mut self.steps[self.active_step];
+        let policy = step.navigation_policy().unwrap_or(self.navigation_policy);
+
+        let action = match policy.action_for(key_event.code) {
+            Some(BackAction::Step) => Some(InputResult::RetreatForm),
+            Some(BackAction::Ignore) => None,
+            Some(BackAction::Element) | None => {
+                // Input to a skipped step, including accepting it, restores it
+                self.skipped_steps
+                    .retain(|skipped_step| *skipped_step != self.active_step);
+
+                let action = step.update(&mut self.dependency_state, &self.values, key_event);
+                if step.take_rejection() {
+                    self.signal_rejection();
+                }
+
+                self.refresh_values();
+                action
+            }
+        };
+
+        match action {
+            Some(action) => self.apply_action(action),
+            None => FormStatus::Active,
+        }
+    }
+
+    /// Advance or retreat the form as instructed by the active step.
+    fn apply_action(&mut self, action: InputResult) -> FormStatus {
+        match action {
+            InputResult::AdvanceForm => {
+                self.route(self.active_step);
+
+                if self.next_step().is_none() && self.block_submission() {
+                    return FormStatus::Active;
+                }
+
+                if self.advance() {
+                    return FormStatus::Submitted;
+                }
+
+                if self.draft_handler.is_some() {
+                    let draft = self.redacted_result();
+                    if let Some(handler) = &mut self.draft_handler {
+                        handler(&draft);
+                    }
+                }
+            }
+            InputResult::RetreatForm => {
+                if self.retreat() {
+                    self.retreated = true;
+                    return FormStatus::Canceled;
+                }
+            }
+        }
+
+        FormStatus::Active
+    }
+
+    /// Submit the form from the active step, accepting the remaining steps' current values, unless
+    /// any step has errors.
+    fn submit(&mut self) -> FormStatus {
+        if self.block_submission() {
+            return FormStatus::Active;
+        }
+
+        self.end_step_timing(true);
+        self.long_help_open = false;
+
+        // Every step is shown in the final render, including those submitted without visiting
+        self.max_step = self.steps.len().saturating_sub(1);
+        trace_event!(debug, step = self.active_step, "submitted form");
+
+        FormStatus::Submitted
+    }
+
+    /// Skip the active optional step, leaving it empty, and advance past it.
+    fn skip_step(&mut self) -> FormStatus {
+        if !self.skipped_steps.contains(&self.active_step) {
+            self.skipped_steps.push(self.active_step);
+        }
+
+        trace_event!(debug, step = self.active_step, "skipped step");
+        self.refresh_values();
+
+        self.apply_action(InputResult::AdvanceForm)
+    }
+
+    /// Block submission if any step has errors, focusing the first of them, returning whether it
+    /// was blocked.
+    fn block_submission(&mut self) -> bool {
+        let errors = self.validation_errors();
+        let (step_index, control_index, message) = match errors.first() {
+            Some(error) => error,
+            None => return false,
+        };
+
+        self.validation_message = Some(format!(
+            "Resolve {} error(s) before submitting: {}",
+            errors.len(),
+            message
+        ));
+        self.focus_error(*step_index, *control_index);
+        self.signal_rejection();
+
+        true
+    }
+
+    /// This form's current WYSIWYG result, transformed by any post-processors.
+    pub fn result(&self) -> String {
+        self.result_of(|step| step.result(&self.dependency_state, &self.values))
+    }
+
+    /// This form's result from each unskipped step's specified text, transformed by any
+    /// post-processors.
+    fn result_of(&self, step_result: impl Fn(&dyn Step) -> String) -> String {
+        let mut result = String::new();
+
+        for (step_index, step) in self.steps.iter().enumerate() {
+            if !self.skipped_steps.contains(&step_index) {
+                result.push_str(&step_result(step.as_ref()));
+            }
+        }
+
+        self.post_processors
+            .iter()
+            .fold(result.trim().to_string(), |result, post_processor| {
+                post_processor(result)
+            })
+    }
+
+    /// This form's current structured result, including its named values and any recorded timing.
+    pub fn form_result(&self) -> FormResult {
+        FormResult {
+            text: self.result(),
+            values: self.values.clone(),
+            controls: self.controls_meta(),
+            timings: self.step_timings.clone(),
+            skipped_steps: self.skipped_steps.clone(),
+            prefilled: self.prefilled.clone(),
+        }
+    }
+
+    /// Metadata identifying each of this form's controls, in order.
+    pub fn controls_meta(&self) -> Vec<ControlMeta> {
+        self.steps
+            .iter()
+            .flat_map(|step| step.controls_meta())
+            .collect()
+    }
+
+    /// Write this form's current WYSIWYG result into the specified writer, one step at a time. If
+    /// the form has post-processors, the result is instead written once it's fully transformed.
+    pub fn write_result<W: Write>(&self, writer: &mut W) -> Result<()> {
+        if !self.post_processors.is_empty() {
+            writer
+                .write_all(self.result().as_bytes())
+                .map_err(Error::Output)?;
+
+            return writer.flush().map_err(Error::Output);
+        }
+
+        // Whitespace trailing the written content, withheld until more content follows it
+        let mut pending_whitespace = String::new();
+        let mut started = false;
+
+        for (step_index, step) in self.steps.iter().enumerate() {
+            if self.skipped_steps.contains(&step_index) {
+                continue;
+            }
+
+            let step_result = step.result(&self.dependency_state, &self.values);
+
+            let mut content = step_result.as_str();
+            if !started {
+                content = content.trim_start();
+            }
+
+            let trimmed = content.trim_end();
+            if trimmed.is_empty() {
+                if started {
+                    pending_whitespace.push_str(content);
+                }
+
+                continue;
+            }
+
+            writer
+                .write_all(pending_whitespace.as_bytes())
+                .map_err(Error::Output)?;
+            writer
+                .write_all(trimmed.as_bytes())
+                .map_err(Error::Output)?;
+
+            pending_whitespace = content[trimmed.len()..].to_string();
+            started = true;
+        }
+
+        writer.flush().map_err(Error::Output)
+    }
+
+    /// Whether the form's most recent cancellation was by retreating from its first step, rather
+    /// than by a cancel key.
+    pub(crate) fn retreated(&self) -> bool {
+        self.retreated
+    }
+
+    /// Initialize the form's steps and dependencies, if they haven't been already.
+    fn initialize(&mut self) {
+        if self.initialized {
+            return;
+        }
+
+        // Wire form-level dependencies into the steps containing their controls
+        for dependency in self.control_dependencies.drain(..) {
+            for step in self.steps.iter_mut() {
+                if let Some(step) = step.downcast_mut::<CompoundStep>() {
+                    if step.control_index(dependency.source).is_some() {
+                        step.add_evaluation(
+                            dependency.source,
+                            dependency.id,
+                            dependency.evaluation.clone(),
+                        );
+                    }
+
+                    if step.control_index(dependency.target).is_some() {
+                        step.add_dependency(dependency.target, dependency.id, dependency.action);
+                    }
+                }
+            }
+        }
+
+        // Controls recall their previous choices before their evaluations are registered
+        if let Some(history) = &self.history {
+            for step in self.steps.iter_mut() {
+                step.recall(&history.history);
+            }
+
+            self.refresh_values();
+        }
+
+        for (step_index, step) in self.steps.iter_mut().enumerate() {
+            step.initialize(&mut self.dependency_state, &self.context, step_index);
+        }
+
+        if !self.overrides.is_empty() {
+            self.apply_overrides();
+        }
+
+        self.initialized = true;
+
+        // In batch mode, every step is shown from the start
+        if self.navigation_mode == NavigationMode::Batch {
+            self.max_step = self.steps.len().saturating_sub(1);
+        }
+
+        // Skip leading steps which accept no input, leaving the last step to be submitted
+        while self.active_step + 1 < self.steps.len() && self.steps[self.active_step].skippable() {
+            self.active_step += 1;
+            self.max_step = self.max_step.max(self.active_step);
+        }
+
+        if self.record_timing {
+            self.step_timings = vec![StepTiming::default(); self.steps.len()];
+            self.begin_step_timing();
+        }
+    }
+
+    /// Pre-fill this form's named controls with its overrides, locking them if read-only. Values
+    /// rejected by their controls are recorded to fail the form's execution.
+    fn apply_overrides(&mut self) {
+        for (name, value) in self.overrides.iter() {
+            let mut is_accepted = false;
+
+            for step in self.steps.iter_mut() {
+                // Read-only controls are locked even if they reject their value, so it can't be
+                // edited in place of the override
+                if self.overrides.read_only() {
+                    step.lock_form_value(name);
+                }
+
+                if !step.set_form_value(&mut self.dependency_state, name, &value) {
+                    continue;
+                }
+
+                is_accepted = true;
+                if !self.prefilled.iter().any(|prefilled| prefilled == name) {
+                    self.prefilled.push(name.to_string());
+                }
+            }
+
+            if !is_accepted {
+                let value = value.as_text().unwrap_or_default().to_string();
+                self.rejected_overrides.push((name.to_string(), value));
+            }
+        }
+
+        self.refresh_values();
+    }
+
+    /// Initialize this form, failing if any of its overrides were rejected by their controls.
+    fn check_overrides(&mut self) -> Result<()> {
+        self.initialize();
+
+        match self.rejected_overrides.first() {
+            Some((name, value)) => Err(Error::Override(format!(
+                "\"{}\" isn't a valid value for \"{}\"",
+                value, name
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Update the indices of the initialized steps starting from the specified index, such as after
+    /// a step is inserted or removed.
+    fn reindex_steps(&mut self, from: usize) {
+        if !self.initialized {
+            return;
+        }
+
+        for step_index in from..self.steps.len() {
+            self.steps[step_index].initialize(
+                &mut self.dependency_state,
+                &self.context,
+                step_index,
+            );
+        }
+    }
+
+    /// Call the form's external event handler, if it has one, with the specified event.
+    fn handle_external(&mut self, payload: &(dyn Any + Send)) {
+        if let Some(mut handler) = self.external_handler.take() {
+            handler(self, payload);
+
+            // The handler may have replaced itself
+            if self.external_handler.is_none() {
+                self.external_handler = Some(handler);
+            }
+        }
+    }
+
+    /// Call the form's router, if it has one, for the specified completed step.
+    fn route(&mut self, completed_step: usize) {
+        if let Some(mut router) = self.router.take() {
+            router(self, completed_step);
+
+            // The router may have replaced itself
+            if self.router.is_none() {
+                self.router = Some(router);
+            }
+        }
+    }
+
+    /// Record that the active step has been focused, if timing is recorded.
+    fn begin_step_timing(&mut self) {
+        if let Some(timing) = self.step_timings.get_mut(self.active_step) {
+            timing.started_at.get_or_insert_with(SystemTime::now);
+            self.step_focused_at = Some(Instant::now());
+        }
+    }
+
+    /// Record that the active step has lost focus, optionally by being completed.
+    fn end_step_timing(&mut self, completed: bool) {
+        if let Some(timing) = self.step_timings.get_mut(self.active_step) {
+            if let Some(focused_at) = self.step_focused_at.take() {
+                timing.duration += focused_at.elapsed();
+            }
+
+            if completed {
+                timing.completed_at = Some(SystemTime::now());
+            }
+        }
+    }
+
+    /// Rebuild the value store from the current values of the form's named controls.
+    fn refresh_values(&mut self) {
+        let mut values = ValueStore::new();
+        for (step_index, step) in self.steps.iter().enumerate() {
+            // Skipped steps are left empty
+            if self.skipped_steps.contains(&step_index) {
+                continue;
+            }
+
+            for (name, value) in step.form_values() {
+                values.set(&name, value);
+            }
+        }
+
+        self.values = values;
+    }
+
+    /// Exits the form early by performing a final, unfocused render and returning a cancelation code.
+    pub(crate) fn cancel_form(&mut self, interface: &mut dyn RenderBackend) -> Result<()> {
+        self.reset_region(interface, self.origin);
+        self.render_at(interface, self.origin, false);
+        interface.apply()?;
+
+        Err(Error::Canceled)
+    }
+
+    /// Advance the form to its next step. Returns whether we've finished the form.
+    fn advance(&mut self) -> bool {
+        self.end_step_timing(true);
+        self.long_help_open = false;
+
+        let next_step = self.next_step();
+
+        let is_last_step = next_step.is_none();
+        if let Some(next_step) = next_step {
+            self.active_step = next_step;
+            trace_event!(debug, step = self.active_step, "advanced to step");
+
+            if self.active_step > self.max_step {
+                self.max_step = self.active_step;
+            }
+
+            self.begin_step_timing();
+        } else {
+            trace_event!(debug, step = self.active_step, "submitted form");
+        }
+
+        is_last_step
+    }
+
+    /// The step following the active step which accepts input, if any. Steps accepting no input
+    /// are skipped, so the form is submitted if only those remain.
+    fn next_step(&self) -> Option<usize> {
+        (self.active_step + 1..self.steps.len())
+            .find(|&step_index| !self.steps[step_index].skippable())
+    }
+
+    /// The validation errors of the form's steps in order, each with its step and control index.
+    fn validation_errors(&self) -> Vec<(usize, usize, String)> {
+        self.steps
+            .iter()
+            .enumerate()
+            .filter(|(step_index, _)| !self.skipped_steps.contains(step_index))
+            .flat_map(|(step_index, step)| {
+                step.validation_errors(&self.dependency_state, &self.values)
+                    .into_iter()
+                    .map(move |(control_index, message)| (step_index, control_index, message))
+            })
+            .collect()
+    }
+
+    /// Focus the error following the focused control, wrapping around to the form's first error.
+    fn focus_next_error(&mut self) {
+        let focused = (
+            self.active_step,
+            self.steps
+                .get(self.active_step)
+                .and_then(|step| step.focused_control_index())
+                .unwrap_or_default(),
+        );
+
+        let errors = self.validation_errors();
+        let next_error = errors
+            .iter()
+            .find(|(step_index, control_index, _)| (*step_index, *control_index) > focused)
+            .or(errors.first());
+
+        if let Some((step_index, control_index, message)) = next_error {
+            self.validation_message = Some(message.clone());
+            self.focus_error(*step_index, *control_index);
+        }
+    }
+
+    /// Focus the specified step and control to correct its error.
+    fn focus_error(&mut self, step_index: usize, control_index: usize) {
+        self.focus_step(step_index);
+        self.steps[step_index].focus_control(control_index);
+    }
+
+    /// Focus the nearest step accepting input after or before the active step, if there is one.
+    fn focus_adjacent_step(&mut self, forward: bool) {
+        let adjacent_step = if forward {
+            self.next_step()
+        } else {
+            (0..self.active_step)
+                .rev()
+                .find(|&step_index| !self.steps[step_index].skippable())
+        };
+
+        if let Some(step_index) = adjacent_step {
+            self.focus_step(step_index);
+        }
+    }
+
+    /// Focus the specified step directly, regardless of the steps between it and the active step.
+    fn focus_step(&mut self, step_index: usize) {
+        if step_index == self.active_step {
+            return;
+        }
+
+        self.end_step_timing(false);
+        self.long_help_open = false;
+
+        self.active_step = step_index;
+        self.max_step = self.max_step.max(step_index);
+        trace_event!(debug, step = self.active_step, "focused step");
+        self.begin_step_timing();
+    }
+
+    /// Retreat the form to its previous step. Returns whether we're at the first step.
+    fn retreat(&mut self) -> bool {
+        self.end_step_timing(false);
+        self.long_help_open = false;
+
+        // Steps accepting no input are skipped, so the form is exited if only those precede it
+        let previous_step = (0..self.active_step)
+            .rev()
+            .find(|&step_index| !self.steps[step_index].skippable());
+
+        let is_first_step = previous_step.is_none();
+        if let Some(previous_step) = previous_step {
+            self.active_step = previous_step;
+            trace_event!(debug, step = self.active_step, "retreated to step");
+            self.begin_step_timing();
+        }
+
+        is_first_step
+    }
+
+    /// Re-render the form's updated state at the specified origin, optionally with a focused step.
+    fn render_form(
+        &mut self,
+        interface: &mut dyn RenderBackend,
+        origin: Position,
+        focused_step: Option<usize>,
+    ) -> u16 {
+        trace_span!(TRACE, "render", focused_step = ?focused_step);
+
+        for line in 0..self.last_height {
+            interface.clear_rest_of_line(pos!(origin.x(), origin.y() + line));
+        }
+
+        let rendered_steps = (self.max_step + 1).min(self.steps.len());
+        let visible_steps = match (focused_step, self.viewport_height) {
+            (Some(focused_step), Some(rows)) => self.viewport_steps(focused_step, rows),
+            _ => 0..rendered_steps,
+        };
+
+        // While executing, steps may be offset to make room for a gutter of their statuses
+        let show_gutter = self.status_gutter && focused_step.is_some();
+        let step_x = if show_gutter {
+            origin.x() + GUTTER_WIDTH
+        } else {
+            origin.x()
+        };
+
+        let mut drawer = None;
+        let mut line = 1;
+        let mut step_bounds = Vec::new();
+
+        if visible_steps.start > 0 {
+            let summary = format!("  ⋯ {} earlier steps", visible_steps.start);
+            render_segment(
+                interface,
+                pos!(origin.x(), origin.y() + line),
+                Text::new_styled(summary, muted_style()).as_segment(),
+            );
+            line += 1;
+        }
+
+        for step_index in visible_steps.clone() {
+            let step = &self.steps[step_index];
+            let is_focused = Some(step_index) == focused_step;
+
+            // Completed steps are those before the furthest reached, which may not be completed yet
+            let is_collapsed = self.collapse_completed
+                && focused_step.is_some()
+                && !is_focused
+                && step_index < self.max_step;
+
+            let step_height = if self.skipped_steps.contains(&step_index) && !is_focused {
+                let segment = Text::new_styled("(skipped)".to_string(), muted_style()).as_segment();
+                render_segment(interface, pos!(step_x, origin.y() + line), segment);
+
+                1
+            } else if is_collapsed {
+                let summary = step.summary(&self.dependency_state, &self.values);
+
+                // The gutter already marks the step as complete
+                let mut segment = Segment::new();
+                if !show_gutter {
+                    segment.push(Text::new_styled("✓ ".to_string(), valid_style()));
+                }
+                segment.push(Text::new_styled(summary, muted_style()));
+                render_segment(interface, pos!(step_x, origin.y() + line), segment);
+
+                1
+            } else {
+                step.render(
+                    interface,
+                    &self.dependency_state,
+                    &self.values,
+                    pos!(step_x, origin.y() + line),
+                    is_focused,
+                )
+            };
+
+            if show_gutter {
+                let status = if is_focused {
+                    Some(Text::new_styled("●".to_string(), help_style()))
+                } else if step.warning(&self.dependency_state, &self.values).is_some() {
+                    Some(Text::new_styled("!".to_string(), error_style()))
+                } else if step_index < self.max_step {
+                    Some(Text::new_styled("✓".to_string(), valid_style()))
+                } else {
+                    None
+                };
+
+                if let Some(status) = status {
+                    let position = pos!(origin.x(), origin.y() + line);
+                    render_segment(interface, position, status.as_segment());
+                }
+            }
+
+            if self.step_heights.len() < self.steps.len() {
+                self.step_heights.resize(self.steps.len(), 1);
+            }
+            self.step_heights[step_index] = step_height;
+
+            step_bounds.push((step_index, line, step_height));
+            line += step_height;
+
+            if is_focused {
+                let mut help = match (&self.validation_message, &self.notice) {
+                    (Some(message), _) => {
+                        Text::new_styled(message.clone(), error_style()).as_segment()
+                    }
+                    (None, Some(notice)) => {
+                        Text::new_styled(notice.clone(), muted_style()).as_segment()
+                    }
+                    (None, None) => step.help(),
+                };
+
+                // Optional steps hint at the key which skips them
+                if let Some(binding) = self.key_bindings.skip_keys().first() {
+                    if step.optional() && self.validation_message.is_none() && self.notice.is_none()
+                    {
+                        let hint = format!(" ({} to skip)", binding);
+                        help.push(Text::new_styled(hint, muted_style()));
+                    }
+                }
+
+                // Rejected input briefly flashes the help line
+                if self.until_flash_ends().is_some() {
+                    overlay_segment_style(&mut help, flash_style());
+                }
+
+                render_segment(interface, origin, help);
+
+                drawer = step.drawer();
+                if self.long_help_open {
+                    drawer = step.long_help().or(drawer);
+                }
+            }
+        }
+
+        if visible_steps.end < rendered_steps {
+            let summary = format!("  ⋯ {} later steps", rendered_steps - visible_steps.end);
+            render_segment(
+                interface,
+                pos!(origin.x(), origin.y() + line),
+                Text::new_styled(summary, muted_style()).as_segment(),
+            );
+            line += 1;
+        }
+
+        // Drawer rows are truncated rather than wrapped, preserving their alignment
+        let drawer_width = interface
+            .width()
+            .map(|width| width.saturating_sub(origin.x()).into());
+
+        if let Some(drawer) = drawer {
+            for mut item in drawer {
+                if let Some(width) = drawer_width {
+                    item = truncate_segment(item, width);
+                }
+
+                render_segment(interface, pos!(origin.x(), origin.y() + line), item);
+                line += 1;
+            }
+        }
+
+        if self.debug {
+            let overlay_height = self.render_debug_overlay(interface, origin, &step_bounds);
+            line = line.max(overlay_height);
+        }
+
+        // Pad shorter renders to the tallest render's height so the region never shrinks
+        if self.compat_mode {
+            self.tallest_height = self.tallest_height.max(line);
+            for pad_line in line..self.tallest_height {
+                interface.set(pos!(origin.x(), origin.y() + pad_line), " ");
+            }
+
+            line = self.tallest_height;
+        }
+
+        self.last_height = line;
+        trace_event!(trace, height = line, "rendered form");
+
+        line
+    }
+
+    /// The range of steps around the focused step whose last-rendered heights fit within the
+    /// specified rows, less those reserved for summarizing the steps outside of it.
+    fn viewport_steps(&self, focused_step: usize, rows: u16) -> Range<usize> {
+        let rendered_steps = (self.max_step + 1).min(self.steps.len());
+        let height = |step_index: usize| self.step_heights.get(step_index).copied().unwrap_or(1);
+        let budget = rows.saturating_sub(2);
+
+        let (mut start, mut end) = (focused_step, focused_step + 1);
+        let mut used = height(focused_step);
+        loop {
+            let mut expanded = false;
+
+            if start > 0 && used + height(start - 1) <= budget {
+                start -= 1;
+                used += height(start);
+                expanded = true;
+            }
+
+            if end < rendered_steps && used + height(end) <= budget {
+                used += height(end);
+                end += 1;
+                expanded = true;
+            }
+
+            if !expanded {
+                break;
+            }
+        }
+
+        start..end
+    }
+
+    /// Render the debug overlay in the top-right corner of the interface, or right of the
+    /// origin if its width is unknown, returning the overlay's height.
+    fn render_debug_overlay(
+        &self,
+        interface: &mut dyn RenderBackend,
+        origin: Position,
+        step_bounds: &[(usize, u16, u16)],
+    ) -> u16 {
+        let mut lines = vec![format!(
+            "step {}/{} max {}",
+            self.active_step,
+            self.steps.len(),
+            self.max_step
+        )];
+
+        for &(step_index, start, height) in step_bounds {
+            let marker = if step_index == self.active_step {
+                '>'
+            } else {
+                ' '
+            };
+
+            let mut line = format!("{} #{} rows {}+{}", marker, step_index, start, height);
+            if let Some(info) = self.steps[step_index].debug_info() {
+                line.push_str(&format!(" {}", info));
+            }
+
+            lines.push(line);
+        }
+
+        lines.extend(self.dependency_state.describe_evaluations());
+
+        let overlay_width = lines
+            .iter()
+            .map(|line| get_segment_width(&Text::new(line.clone()).as_segment()) as u16)
+            .max()
+            .unwrap_or_default();
+
+        let x = match interface.width() {
+            Some(width) => width.saturating_sub(overlay_width).max(origin.x()),
+            None => origin.x() + 60,
+        };
+
+        for (line_index, line) in lines.iter().enumerate() {
+            let position = pos!(x, origin.y() + line_index as u16);
+            render_segment(
+                interface,
+                position,
+                Text::new_styled(line.clone(), muted_style()).as_segment(),
+            );
+        }
+
+        lines.len() as u16
+    }
+
+    /// Clear the form's entire rendered region.
+    pub(crate) fn clear_region(&mut self, interface: &mut dyn RenderBackend, origin: Position) {
+        for line in 0..self.last_height {
+            interface.clear_rest_of_line(pos!(origin.x(), origin.y() + line));
+        }
+
+        self.last_height = 0;
+    }
+
+    /// In compatibility mode, clear the form's entire region in preparation for a final render.
+    fn reset_region(&mut self, interface: &mut dyn RenderBackend, origin: Position) {
+        if !self.compat_mode {
+            return;
+        }
+
+        for line in 0..self.tallest_height {
+            interface.clear_rest_of_line(pos!(origin.x(), origin.y() + line));
+        }
+
+        self.tallest_height = 0;
+        self.last_height = 0;
+    }
+}
+
+impl fmt::Debug for Form {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.describe())
+    }
+}
+
+/// Parse the specified text by the specified steps in order, consuming it entirely, returning the
+/// values parsed by each step.
+fn parse_steps(steps: &[Box<dyn Step>], text: &str) -> Option<Vec<(String, ControlValue)>> {
+    let (step, remaining_steps) = match steps.split_first() {
+        Some(split) => split,
+        None => return text.trim().is_empty().then(Vec::new),
+    };
+
+    for (mut values, consumed) in step.parse_result(text) {
+        if let Some(remaining_values) = parse_steps(remaining_steps, &text[consumed..]) {
+            values.extend(remaining_values);
+            return Some(values);
+        }
+    }
+
+    None
+}
+
+/// A form's structured result: its WYSIWYG text, named values, control metadata, and any recorded
+/// step timing.
+#[derive(Debug, Clone)]
+pub struct FormResult {
+    text: String,
+    values: ValueStore,
+    controls: Vec<ControlMeta>,
+    timings: Vec<StepTiming>,
+    skipped_steps: Vec<usize>,
+    prefilled: Vec<String>,
+}
+
+impl FormResult {
+    /// The form's WYSIWYG result.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The form's named values.
+    pub fn values(&self) -> &ValueStore {
+        &self.values
+    }
+
+    /// Metadata identifying each of the form's controls, in order, such as to find which named
+    /// values are sensitive.
+    pub fn controls(&self) -> &[ControlMeta] {
+        &self.controls
+    }
+
+    /// Each step's recorded timing, or empty if timing wasn't recorded.
+    pub fn timings(&self) -> &[StepTiming] {
+        &self.timings
+    }
+
+    /// Whether the step at the specified index is optional and was skipped, leaving it empty.
+    pub fn is_skipped(&self, step_index: usize) -> bool {
+        self.skipped_steps.contains(&step_index)
+    }
+
+    /// Whether the named control or step was pre-filled by a command-line override. See
+    /// [Form::set_overrides].
+    pub fn is_prefilled(&self, name: &str) -> bool {
+        self.prefilled.iter().any(|prefilled| prefilled == name)
+    }
+
+    /// The total time spent focused on the form's steps.
+    pub fn total_duration(&self) -> Duration {
+        self.timings.iter().map(StepTiming::duration).sum()
+    }
+
+    /// A trailer line describing the total time spent on the form, such as `Time-Spent: 2m 5s`, if
+    /// timing was recorded.
+    pub fn timing_trailer(&self) -> Option<String> {
+        if self.timings.is_empty() {
+            return None;
+        }
+
+        let seconds = self.total_duration().as_secs();
+        let duration = match (seconds / 3600, seconds / 60 % 60, seconds % 60) {
+            (0, 0, seconds) => format!("{}s", seconds),
+            (0, minutes, seconds) => format!("{}m {}s", minutes, seconds),
+            (hours, minutes, seconds) => format!("{}h {}m {}s", hours, minutes, seconds),
+        };
+
+        Some(format!("Time-Spent: {}", duration))
+    }
+
+    /// The changes to named values from the specified earlier result to this one, ordered by name,
+    /// such as to show which fields were amended when a form is re-run to edit a submission.
+    ///
+    /// # Examples
+    /// ```
+    /// use tty_form::{
+    ///     FieldChange, Form,
+    ///     step::{Step, CompoundStep},
+    ///     control::TextInput,
+    ///     value::ControlValue,
+    /// };
+    ///
+    /// let form_with_scope = |scope: &str| {
+    ///     let mut form = Form::new();
+    ///     let mut step = CompoundStep::new();
+    ///     let mut input = TextInput::new("Enter a scope:", false);
+    ///     input.set_name("scope");
+    ///     input.set_value(scope);
+    ///     step.add(input);
+    ///     step.add_to(&mut form);
+    ///     form
+    /// };
+    ///
+    /// let original = form_with_scope("api").form_result();
+    /// let edited = form_with_scope("ui").form_result();
+    ///
+    /// assert_eq!(
+    ///     vec![FieldChange::Changed {
+    ///         name: "scope".to_string(),
+    ///         before: ControlValue::Text("api".to_string()),
+    ///         after: ControlValue::Text("ui".to_string()),
+    ///     }],
+    ///     edited.diff(&original),
+    /// );
+    /// ```
+    pub fn diff(&self, earlier: &FormResult) -> Vec<FieldChange> {
+        let mut names: Vec<&str> = earlier
+            .values
+            .iter()
+            .chain(self.values.iter())
+            .map(|(name, _)| name)
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+
+        names
+            .into_iter()
+            .filter_map(|name| {
+                let name_string = name.to_string();
+                match (earlier.values.get(name), self.values.get(name)) {
+                    (None, Some(value)) => Some(FieldChange::Added {
+                        name: name_string,
+                        value: value.clone(),
+                    }),
+                    (Some(value), None) => Some(FieldChange::Removed {
+                        name: name_string,
+                        value: value.clone(),
+                    }),
+                    (Some(before), Some(after)) if before != after => Some(FieldChange::Changed {
+                        name: name_string,
+                        before: before.clone(),
+                        after: after.clone(),
+                    }),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+}
+
+/// A change to a named value between two of a form's results.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum FieldChange {
+    /// The value is present only in the later result.
+    Added {
+        /// The value's name.
+        name: String,
+        /// The later result's value.
+        value: ControlValue,
+    },
+    /// The value is present only in the earlier result.
+    Removed {
+        /// The value's name.
+        name: String,
+        /// The earlier result's value.
+        value: ControlValue,
+    },
+    /// The value differs between the results.
+    Changed {
+        /// The value's name.
+        name: String,
+        /// The earlier result's value.
+        before: ControlValue,
+        /// The later result's value.
+        after: ControlValue,
+    },
+}
+
+impl FieldChange {
+    /// The changed value's name.
+    pub fn name(&self) -> &str {
+        match self {
+            FieldChange::Added { name, .. }
+            | FieldChange::Removed { name, .. }
+            | FieldChange::Changed { name, .. } => name,
+        }
+    }
+}
+
+/// How a form signals input rejected by its steps.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum RejectFeedback {
+    /// Rejected input is silently ignored.
+    #[default]
+    Silent,
+    /// The terminal bell is rung, by rendering backends supporting it.
+    Bell,
+    /// The help line is briefly flashed.
+    Flash,
+}
+
+/// When a step was started and completed, and how long it was focused.
+#[derive(Debug, Clone, Default)]
+pub struct StepTiming {
+    started_at: Option<SystemTime>,
+    completed_at: Option<SystemTime>,
+    duration: Duration,
+}
+
+impl StepTiming {
+    /// When the step was first focused, if it has been.
+    pub fn started_at(&self) -> Option<SystemTime> {
+        self.started_at
+    }
+
+    /// When the step was last completed, if it has been.
+    pub fn completed_at(&self) -> Option<SystemTime> {
+        self.completed_at
+    }
+
+    /// The total time the step has been focused.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        thread,
+        time::{Duration, SystemTime},
+    };
+
+    use tty_interface::{pos, Interface, Position};
+
+    use crate::{
+        backend::{CursorShape, CursorStyle, RenderBackend, TerminalSizeBackend},
+        control::{
+            CharFilter, Control, ControlKind, ControlMeta, SelectInput, StaticText, TextInput,
+        },
+        dependency::{Action, Evaluation},
+        device::ChannelInputDevice,
+        input::{InputEvent, Intercept, KeyCode, KeyEvent, KeyModifiers},
+        keybinding::{KeyBinding, KeyBindings},
+        navigation::NavigationMode,
+        postprocess, prefab,
+        recording::FrameRecorder,
+        step::{CompoundStep, KeyValueStep, Step, TextBlockStep, YesNoStep},
+        test::{cursor, press, press_ctrl, render, type_text, SizedBackend, VirtualTerminal},
+        text::Text,
+        value::{Answers, ControlValue},
+        ConfigIssue, Error,
+    };
+
+    use super::{FieldChange, Form, FormStatus, RejectFeedback, FLASH_DURATION, WATCH_INTERVAL};
+
+    const SECRET: &str = "hunter2";
+
+    fn sensitive_form() -> Form {
+        let mut form = Form::new();
+
+        let mut step = CompoundStep::new();
+        let mut user = TextInput::new("Enter your username.", false);
+        user.set_name("user");
+        user.add_to(&mut step);
+        let mut password = TextInput::new("Enter your password.", false);
+        password.set_name("password");
+        password.set_sensitive(true);
+        password.add_to(&mut step);
+        step.add_to(&mut form);
+
+        TextBlockStep::new("Enter a note.").add_to(&mut form);
+
+        form
+    }
+
+    #[test]
+    fn test_sensitive_values_redacted() {
+        let mut form = sensitive_form();
+        type_text(&mut form, "daniel");
+        press(&mut form, KeyCode::Tab);
+        type_text(&mut form, SECRET);
+
+        // Rendered output, and so recordings and screenshots, are masked
+        let mut recorder = FrameRecorder::new(VirtualTerminal::new());
+        let mut interface = Interface::new_relative(&mut recorder).unwrap();
+        form.render_at(&mut interface, pos!(0, 0), true);
+        interface.apply().unwrap();
+        drop(interface);
+
+        let screen = recorder.device().screen_contents();
+        assert!(screen.contains("daniel*******"));
+        assert!(!screen.contains(SECRET));
+
+        let mut cast = Vec::new();
+        recorder.write_cast(&mut cast).unwrap();
+        assert!(!String::from_utf8_lossy(&cast).contains(SECRET));
+
+        // Drafts and autosaved state exclude the secret
+        assert_eq!("daniel***", form.redacted_result());
+        assert_eq!(None, form.redacted_state().values().get("password"));
+        assert!(!form.redacted_state().serialize().contains(SECRET));
+
+        // The form's own result retains the secret
+        assert_eq!(Some(SECRET), form.values().get_text("password"));
+        assert_eq!(format!("daniel{}", SECRET), form.result());
+    }
+
+    #[test]
+    fn test_redaction_per_control() {
+        let mut form = Form::new();
+        let mut step = CompoundStep::new();
+        StaticText::new("user: ").add_to(&mut step);
+        let mut user = TextInput::new("Enter your username.", false);
+        user.set_name("user");
+        user.add_to(&mut step);
+        StaticText::new(" pin: ").add_to(&mut step);
+        let mut pin = TextInput::new("Enter your PIN.", false);
+        pin.set_name("pin");
+        pin.set_sensitive(true);
+        pin.add_to(&mut step);
+        StaticText::new(" token: ").add_to(&mut step);
+        let mut token = TextInput::new("Enter your token.", false);
+        token.set_sensitive(true);
+        token.add_to(&mut step);
+        step.add_to(&mut form);
+
+        type_text(&mut form, "essie");
+        press(&mut form, KeyCode::Tab);
+        type_text(&mut form, "e");
+        press(&mut form, KeyCode::Tab);
+        type_text(&mut form, "s3cr3t");
+
+        // Short values occurring elsewhere in the result, and unnamed controls, are masked in place
+        assert_eq!("user: essie pin: e token: s3cr3t", form.result());
+        assert_eq!("user: essie pin: *** token: ***", form.redacted_result());
+
+        let state = form.redacted_state();
+        assert_eq!(Some("essie"), state.values().get_text("user"));
+        assert_eq!(None, state.values().get("pin"));
+        assert!(!state.serialize().contains("s3cr3t"));
+    }
+
+    #[test]
+    fn test_preview_preserves_focus() {
+        let mut form = Form::new();
+        let mut step = KeyValueStep::new("Enter trailers.");
+        step.set_name("trailers");
+        step.add_to(&mut form);
+
+        let mut step = CompoundStep::new();
+        let mut scope = TextInput::new("Enter a scope.", false);
+        scope.set_name("scope");
+        scope.add_to(&mut step);
+        step.add_to(&mut form);
+
+        type_text(&mut form, "a");
+        press(&mut form, KeyCode::Tab);
+        type_text(&mut form, "12");
+        press(&mut form, KeyCode::Left);
+        let cursor_before = cursor(&mut form);
+
+        let mut answers = Answers::new();
+        answers.set("scope", ControlValue::Text("ui".to_string()));
+        answers.set(
+            "trailers",
+            ControlValue::Pairs(vec![("a".to_string(), "12".to_string())]),
+        );
+        assert_eq!("a: 12\nui", form.preview_result(&answers));
+        assert_eq!(Some(""), form.values().get_text("scope"));
+
+        // The focused pair's value, and its cursor, are undisturbed by the preview
+        assert_eq!(cursor_before, cursor(&mut form));
+        type_text(&mut form, "x");
+        assert_eq!(
+            Some(&[("a".to_string(), "1x2".to_string())][..]),
+            form.values().get_pairs("trailers")
+        );
+    }
+
+    #[test]
+    fn test_validate_configuration() {
+        let mut form = Form::new();
+
+        let mut step = CompoundStep::new();
+        let mut scope = TextInput::new("Enter a scope.", false);
+        scope.set_name("scope");
+        let provided_id = scope.set_evaluation(Evaluation::IsEmpty);
+        scope.add_to(&mut step);
+        let mut hint = StaticText::new("(optional)");
+        hint.set_dependency(provided_id, Action::Hide);
+        hint.add_to(&mut step);
+        step.add_to(&mut form);
+        assert!(form.validate_configuration().is_empty());
+
+        // A dependency on an evaluation from a control outside the form can never be satisfied
+        let mut step = CompoundStep::new();
+        let mut scope = TextInput::new("Enter another scope.", false);
+        scope.set_name("scope");
+        scope.add_to(&mut step);
+        let mut orphan = TextInput::new("Unused.", false);
+        let orphan_id = orphan.set_evaluation(Evaluation::IsEmpty);
+        let mut hint = StaticText::new("(required)");
+        hint.set_dependency(orphan_id, Action::Show);
+        hint.add_to(&mut step);
+        step.add_to(&mut form);
+
+        let mut step = CompoundStep::new();
+        let mut scope = TextInput::new("Enter a third scope.", false);
+        scope.set_name("scope");
+        scope.add_to(&mut step);
+        step.add_to(&mut form);
+
+        // Each duplicated name is reported once
+        assert_eq!(
+            vec![
+                ConfigIssue::UnregisteredDependency { step: 1 },
+                ConfigIssue::DuplicateName {
+                    name: "scope".to_string()
+                },
+            ],
+            form.validate_configuration()
+        );
+    }
+
+    #[test]
+    fn test_result_diff() {
+        let named_form = |names: &[&str]| {
+            let mut form = Form::new();
+            let mut step = CompoundStep::new();
+            for name in names {
+                let mut input = TextInput::new("Enter a value.", false);
+                input.set_name(name);
+                input.add_to(&mut step);
+            }
+            step.add_to(&mut form);
+            form
+        };
+
+        let mut original = named_form(&["name", "scope"]);
+        type_text(&mut original, "dan");
+        press(&mut original, KeyCode::Tab);
+        type_text(&mut original, "ui");
+        let original = original.form_result();
+
+        let mut edited = named_form(&["name", "type"]);
+        type_text(&mut edited, "daniel");
+        press(&mut edited, KeyCode::Tab);
+        type_text(&mut edited, "feat");
+        let edited = edited.form_result();
+
+        let text = |text: &str| ControlValue::Text(text.to_string());
+        let changes = edited.diff(&original);
+        assert_eq!(
+            vec![
+                FieldChange::Changed {
+                    name: "name".to_string(),
+                    before: text("dan"),
+                    after: text("daniel"),
+                },
+                FieldChange::Removed {
+                    name: "scope".to_string(),
+                    value: text("ui"),
+                },
+                FieldChange::Added {
+                    name: "type".to_string(),
+                    value: text("feat"),
+                },
+            ],
+            changes
+        );
+        assert_eq!(
+            vec!["name", "scope", "type"],
+            changes.iter().map(FieldChange::name).collect::<Vec<_>>()
+        );
+
+        assert!(edited.diff(&edited).is_empty());
+    }
+
+    #[test]
+    fn test_describe() {
+        let mut form = Form::new();
+
+        let mut breaking =
+            YesNoStep::new("Is this a breaking change?", "Describe it.", "BREAKING: ");
+        breaking.set_name("breaking");
+        let breaking_id = breaking.set_evaluation(Evaluation::Equal("Yes".to_string()));
+        breaking.add_to(&mut form);
+
+        let mut step = CompoundStep::new();
+        let mut warning = StaticText::new("Bump the major version.");
+        warning.set_dependency(breaking_id, Action::Show);
+        warning.add_to(&mut step);
+        let mut scope = TextInput::new("Enter a scope.", false);
+        scope.set_name("scope");
+        scope.add_to(&mut step);
+        step.add_to(&mut form);
+
+        TextBlockStep::new("Enter a body.").add_to(&mut form);
+
+        let expected = format!(
+            "Form\n\
+             ├── YesNoStep breaking \"Is this a breaking change?\" evaluates {id}\n\
+             ├── CompoundStep\n\
+             │   ├── Static \"Bump the major version.\" shown by {id}\n\
+             │   └── Text scope \"Enter a scope.\"\n\
+             └── TextBlockStep \"Enter a body.\"",
+            id = breaking_id
+        );
+        assert_eq!(expected, form.describe());
+        assert_eq!(expected, format!("{:?}", form));
+
+        assert_eq!("Form", Form::new().describe());
+    }
+
+    #[test]
+    fn test_max_fps() {
+        let key = |code| InputEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+        let execute = |max_fps: Option<u8>| {
+            let mut form = Form::new();
+            let mut step = CompoundStep::new();
+            TextInput::new("Enter a value.", false).add_to(&mut step);
+            step.add_to(&mut form);
+            if let Some(max_fps) = max_fps {
+                form.set_max_fps(max_fps);
+            }
+
+            let (sender, mut device) = ChannelInputDevice::channel();
+            for ch in "abcdefghij".chars() {
+                sender.send(key(KeyCode::Char(ch))).unwrap();
+            }
+
+            let handle = thread::spawn(move || {
+                thread::sleep(Duration::from_millis(300));
+                sender.send(key(KeyCode::Enter)).unwrap();
+            });
+
+            let mut backend = SizedBackend::new(80, 24);
+            let result = form.execute(&mut backend, &mut device).unwrap();
+            handle.join().unwrap();
+
+            (result, backend.frames)
+        };
+
+        let (result, uncapped_frames) = execute(None);
+        assert_eq!("abcdefghij", result);
+        assert!(uncapped_frames > 10);
+
+        // Rapid input is coalesced into a frame once the cap permits it
+        let (result, capped_frames) = execute(Some(10));
+        assert_eq!("abcdefghij", result);
+        assert!(capped_frames <= 4);
+    }
+
+    #[test]
+    fn test_cursor_style() {
+        let execute = |cursor_style: Option<CursorStyle>, exit: InputEvent| {
+            let mut form = Form::new();
+            let mut step = CompoundStep::new();
+            TextInput::new("Enter a value.", false).add_to(&mut step);
+            step.add_to(&mut form);
+            if let Some(cursor_style) = cursor_style {
+                form.set_cursor_style(cursor_style);
+            }
+
+            let (sender, mut device) = ChannelInputDevice::channel();
+            sender.send(exit).unwrap();
+
+            let mut backend = SizedBackend::new(80, 24);
+            let result = form.execute(&mut backend, &mut device);
+            (result, backend.cursor_styles)
+        };
+
+        let key = |code, modifiers| InputEvent::Key(KeyEvent::new(code, modifiers));
+        let bar = CursorStyle::new(CursorShape::Bar, false);
+
+        // The terminal's default is restored whether the form is submitted or canceled
+        let submit = key(KeyCode::Enter, KeyModifiers::NONE);
+        let (result, cursor_styles) = execute(Some(bar), submit);
+        assert!(result.is_ok());
+        assert_eq!(vec![Some(bar), None], cursor_styles);
+
+        let cancel = key(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        let (result, cursor_styles) = execute(Some(bar), cancel);
+        assert!(matches!(result, Err(Error::Canceled)));
+        assert_eq!(vec![Some(bar), None], cursor_styles);
+
+        // Unless configured, the cursor's style is left alone
+        let submit = key(KeyCode::Enter, KeyModifiers::NONE);
+        let (_, cursor_styles) = execute(None, submit);
+        assert!(cursor_styles.is_empty());
+    }
+
+    #[test]
+    fn test_reject_feedback() {
+        let digits_form = |feedback: Option<RejectFeedback>| {
+            let mut form = Form::new();
+            let mut step = CompoundStep::new();
+            let mut input = TextInput::new("Enter a number.", false);
+            input.set_allowed_chars(CharFilter::Digits);
+            input.add_to(&mut step);
+            step.add_to(&mut form);
+            if let Some(feedback) = feedback {
+                form.set_reject_feedback(feedback);
+            }
+            form
+        };
+
+        let mut form = digits_form(Some(RejectFeedback::Flash));
+        type_text(&mut form, "12");
+        assert!(form.until_flash_ends().is_none());
+
+        // A filtered character is dropped, and the help line flashes briefly
+        type_text(&mut form, "a");
+        assert_eq!("12", form.result());
+        assert!(form.until_flash_ends().is_some());
+
+        thread::sleep(FLASH_DURATION);
+        assert!(form.until_flash_ends().is_none());
+
+        // Rejections are otherwise silent by default
+        let mut form = digits_form(None);
+        type_text(&mut form, "a");
+        assert_eq!("", form.result());
+        assert!(form.until_flash_ends().is_none());
+
+        // The bell is rung through the backend once per rejection
+        let ring = |feedback: Option<RejectFeedback>| {
+            let form = digits_form(feedback);
+            let mut backend = SizedBackend::new(40, 10);
+            let (sender, mut device) = ChannelInputDevice::channel();
+            for code in [KeyCode::Char('a'), KeyCode::Char('1'), KeyCode::Char('b')] {
+                sender
+                    .send(InputEvent::Key(KeyEvent::new(code, KeyModifiers::NONE)))
+                    .unwrap();
+            }
+            sender
+                .send(InputEvent::Key(KeyEvent::new(
+                    KeyCode::Enter,
+                    KeyModifiers::NONE,
+                )))
+                .unwrap();
+
+            assert_eq!("1", form.execute(&mut backend, &mut device).unwrap());
+            backend.bells
+        };
+
+        assert_eq!(2, ring(Some(RejectFeedback::Bell)));
+        assert_eq!(0, ring(Some(RejectFeedback::Flash)));
+        assert_eq!(0, ring(None));
+    }
+
+    #[test]
+    fn test_compat_mode() {
+        let mut form = Form::new();
+        form.set_compat_mode(true);
+
+        let mut step = CompoundStep::new();
+        let options = vec![
+            ("feat", "A feature."),
+            ("fix", "A fix."),
+            ("ci", "A CI change."),
+        ];
+        SelectInput::new("Select a type.", options).add_to(&mut step);
+        step.add_to(&mut form);
+
+        let mut step = CompoundStep::new();
+        TextInput::new("Enter a description.", false).add_to(&mut step);
+        step.add_to(&mut form);
+
+        // The select's open drawer makes for the tallest render
+        let mut backend = SizedBackend::new(40, 10);
+        let tallest = form.render_at(&mut backend, pos!(0, 0), true);
+        assert!(backend.row(tallest - 1).contains("ci"));
+
+        // A shorter render after it is padded to the tallest height, clearing the stale rows
+        press(&mut form, KeyCode::Enter);
+        type_text(&mut form, "Add a type");
+        assert_eq!(tallest, form.render_at(&mut backend, pos!(0, 0), true));
+        assert_eq!(tallest, form.tallest_height);
+
+        let rows: Vec<String> = (0..tallest).map(|row| backend.row(row)).collect();
+        assert!(
+            rows.iter().any(|row| row.contains("Add a type")),
+            "{:?}",
+            rows
+        );
+        assert!(!rows.iter().any(|row| row.contains("ci")), "{:?}", rows);
+        assert_eq!("", backend.row(tallest - 1));
+
+        // The region is reset before the final render, which then takes only the rows it needs
+        form.reset_region(&mut backend, pos!(0, 0));
+        assert_eq!(0, form.tallest_height);
+        assert!((0..tallest).all(|row| backend.row(row).is_empty()));
+
+        let height = form.render_at(&mut backend, pos!(0, 0), false);
+        assert!(height < tallest);
+        assert_eq!(height, form.last_height);
+    }
+
+    #[test]
+    fn test_step_timing() {
+        const PAUSE: Duration = Duration::from_millis(20);
+
+        let mut form = Form::new();
+        form.set_record_timing(true);
+        for _ in 0..2 {
+            let mut step = CompoundStep::new();
+            TextInput::new("Enter a value.", false).add_to(&mut step);
+            step.add_to(&mut form);
+        }
+
+        let before = SystemTime::now();
+        type_text(&mut form, "a");
+        thread::sleep(PAUSE);
+        press(&mut form, KeyCode::Enter);
+        let first_completed_at = form.form_result().timings()[0].completed_at().unwrap();
+
+        // Retreating leaves the second step incomplete, and refocusing the first resumes its timing
+        thread::sleep(PAUSE);
+        press(&mut form, KeyCode::Esc);
+        assert_eq!(None, form.form_result().timings()[1].completed_at());
+        thread::sleep(PAUSE);
+        press(&mut form, KeyCode::Enter);
+        assert_eq!(FormStatus::Submitted, press(&mut form, KeyCode::Enter));
+        let after = SystemTime::now();
+
+        let result = form.form_result();
+        let timings = result.timings();
+        assert_eq!(2, timings.len());
+
+        // Steps keep when they were first started, and record when they were last completed
+        let started_at = timings[0].started_at().unwrap();
+        assert!(before <= started_at && started_at <= first_completed_at);
+        assert!(timings[0].completed_at().unwrap() > first_completed_at);
+        assert!(timings[1].started_at().unwrap() >= first_completed_at);
+        assert!(timings[1].completed_at().unwrap() <= after);
+
+        // Each step's duration accumulates across the times it was focused
+        assert!(timings[0].duration() >= PAUSE * 2);
+        assert!(timings[1].duration() >= PAUSE);
+        assert!(
+            timings[0].duration() + timings[1].duration() <= after.duration_since(before).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_skip_static_steps() {
+        let mut form = Form::new();
+        for (index, text) in ["Header", "Middle", "Footer"].into_iter().enumerate() {
+            let mut step = CompoundStep::new();
+            StaticText::new(text).add_to(&mut step);
+            step.add_to(&mut form);
+
+            if index < 2 {
+                let mut step = CompoundStep::new();
+                TextInput::new("Enter a value.", false).add_to(&mut step);
+                step.add_to(&mut form);
+            }
+        }
+
+        // The leading static step is skipped, as is the middle one in both directions
+        type_text(&mut form, "a");
+        assert_eq!(FormStatus::Active, press(&mut form, KeyCode::Enter));
+        assert_eq!(3, form.state().active_step());
+        assert_eq!(FormStatus::Active, press(&mut form, KeyCode::Esc));
+        assert_eq!(1, form.state().active_step());
+        assert_eq!(FormStatus::Active, press(&mut form, KeyCode::Enter));
+        type_text(&mut form, "b");
+
+        // The trailing static step is skipped by submitting, yet included in the result
+        assert_eq!(FormStatus::Submitted, press(&mut form, KeyCode::Enter));
+        assert_eq!("Header\na\nMiddle\nb\nFooter", form.result());
+
+        // Retreating from the first step accepting input exits the form
+        let mut form = Form::new();
+        let mut step = CompoundStep::new();
+        StaticText::new("Header").add_to(&mut step);
+        step.add_to(&mut form);
+        let mut step = CompoundStep::new();
+        TextInput::new("Enter a value.", false).add_to(&mut step);
+        step.add_to(&mut form);
+
+        assert_eq!(FormStatus::Canceled, press(&mut form, KeyCode::BackTab));
+    }
+
+    #[test]
+    fn test_event_interceptor() {
+        let mut form = Form::new();
+        let mut step = CompoundStep::new();
+        let mut input = TextInput::new("Enter a name.", false);
+        input.set_name("name");
+        input.add_to(&mut step);
+        step.add_to(&mut form);
+
+        form.set_event_interceptor(|event| match event.code {
+            KeyCode::Char('x') => Intercept::Swallow,
+            KeyCode::Char('s') if event.modifiers.contains(KeyModifiers::CONTROL) => {
+                Intercept::Remap(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))
+            }
+            _ => Intercept::Pass,
+        });
+
+        type_text(&mut form, "xaxbx");
+        assert_eq!(Some("ab"), form.values().get_text("name"));
+
+        assert_eq!(FormStatus::Submitted, press_ctrl(&mut form, 's'));
+    }
+
+    #[test]
+    fn test_submit_from_anywhere() {
+        let mut form = Form::new();
+        let mut bindings = KeyBindings::new();
+        bindings.set_submit_keys(vec![KeyBinding::ctrl(KeyCode::Char('s'))]);
+        form.set_key_bindings(bindings);
+
+        let mut step = CompoundStep::new();
+        let mut input = TextInput::new("Enter a summary.", false);
+        input.set_value("Fix the build");
+        input.add_to(&mut step);
+        step.add_to(&mut form);
+
+        let mut step = CompoundStep::new();
+        let mut input = TextInput::new("Enter a ticket.", false);
+        input.set_name("ticket");
+        input.set_required(true);
+        input.add_to(&mut step);
+        step.add_to(&mut form);
+
+        TextBlockStep::new("Enter a body.").add_to(&mut form);
+
+        // Unvisited steps are validated, focusing the first error
+        assert_eq!(FormStatus::Active, press_ctrl(&mut form, 's'));
+        type_text(&mut form, "#1");
+        assert_eq!(Some("#1"), form.values().get_text("ticket"));
+
+        // Once valid, the remaining steps are accepted as they are
+        assert_eq!(FormStatus::Submitted, press_ctrl(&mut form, 's'));
+        assert_eq!("Fix the build\n#1", form.result());
+    }
+
+    #[test]
+    fn test_cancel_keys() {
+        let cancel_form = || {
+            let mut form = Form::new();
+            let mut bindings = KeyBindings::new();
+            bindings.set_cancel_keys(Vec::new());
+            bindings.set_double_press_cancel_keys(vec![KeyBinding::key(KeyCode::Esc)]);
+            bindings.set_read_only_cancel_keys(vec![KeyBinding::key(KeyCode::Char('q'))]);
+            form.set_key_bindings(bindings);
+
+            let mut step = CompoundStep::new();
+            let mut input = TextInput::new("Enter a name.", false);
+            input.set_name("name");
+            input.add_to(&mut step);
+            step.add_to(&mut form);
+
+            let mut step = CompoundStep::new();
+            SelectInput::new("Select a size.", vec![("small", ""), ("large", "")])
+                .add_to(&mut step);
+            step.add_to(&mut form);
+
+            form
+        };
+
+        // Ctrl+C passes through to the step rather than canceling, and `q` is typed into steps
+        // accepting text
+        let mut form = cancel_form();
+        assert_eq!(FormStatus::Active, press_ctrl(&mut form, 'c'));
+        assert_eq!(FormStatus::Active, press(&mut form, KeyCode::Char('q')));
+        assert_eq!(Some("cq"), form.values().get_text("name"));
+
+        // A single Esc only arms cancellation, leaving the step in place
+        press(&mut form, KeyCode::Enter);
+        assert_eq!(FormStatus::Active, press(&mut form, KeyCode::Esc));
+        assert_eq!(1, form.active_step);
+
+        // Another key disarms it, so the next Esc only arms cancellation again
+        assert_eq!(FormStatus::Active, press(&mut form, KeyCode::Down));
+        assert_eq!(FormStatus::Active, press(&mut form, KeyCode::Esc));
+        assert_eq!(1, form.active_step);
+
+        // A second Esc within the interval cancels
+        assert_eq!(FormStatus::Canceled, press(&mut form, KeyCode::Esc));
+
+        // `q` cancels on a step without text input
+        let mut form = cancel_form();
+        press(&mut form, KeyCode::Enter);
+        assert_eq!(FormStatus::Canceled, press(&mut form, KeyCode::Char('q')));
+    }
+
+    #[test]
+    fn test_long_help() {
+        let mut form = Form::new();
+
+        let mut step = CompoundStep::new();
+        let mut scope = TextInput::new("Enter a scope.", false);
+        scope.set_long_help(vec![
+            Text::new("A scope names the affected component.".to_string()).as_segment(),
+            Text::new("For example, \"ui\" or \"parser\".".to_string()).as_segment(),
+        ]);
+        scope.add_to(&mut step);
+        TextInput::new("Enter a summary.", false).add_to(&mut step);
+        step.add_to(&mut form);
+
+        TextBlockStep::new("Enter a body.").add_to(&mut form);
+
+        let shows_long_help = |form: &mut Form| {
+            let contents = render(form).screen_contents();
+            contents.contains("names the affected component") && contents.contains("\"parser\"")
+        };
+
+        // Either toggle key opens and closes the focused control's long help
+        assert!(!shows_long_help(&mut form));
+        press(&mut form, KeyCode::F(1));
+        assert!(shows_long_help(&mut form));
+        type_text(&mut form, "ui");
+        assert!(shows_long_help(&mut form));
+        press_ctrl(&mut form, 'h');
+        assert!(!shows_long_help(&mut form));
+
+        // Controls without long help show their drawer as usual
+        press(&mut form, KeyCode::F(1));
+        press(&mut form, KeyCode::Tab);
+        assert!(!shows_long_help(&mut form));
+
+        // Advancing closes the long help
+        press(&mut form, KeyCode::BackTab);
+        press(&mut form, KeyCode::F(1));
+        press(&mut form, KeyCode::Tab);
+        press(&mut form, KeyCode::Enter);
+        assert_eq!(1, form.active_step);
+        press(&mut form, KeyCode::Esc);
+        assert!(!shows_long_help(&mut form));
+    }
+
+    #[test]
+    fn test_insert_and_remove_steps() {
+        let mut form = Form::new();
+        TextBlockStep::new("Enter a body.").add_to(&mut form);
+
+        // Before the form begins, steps may be inserted or removed anywhere
+        assert!(!form.insert_step(2, Box::new(TextBlockStep::new("Enter a footer."))));
+        let mut step = CompoundStep::new();
+        let mut components = TextInput::new("Enter the components.", false);
+        components.set_name("components");
+        components.add_to(&mut step);
+        assert!(form.insert_step(0, Box::new(step)));
+        assert!(form.remove_step(1).is_some());
+        assert!(form.remove_step(1).is_none());
+
+        form.set_router(|form, completed_step| {
+            if completed_step != 0 || form.step(1).is_some() {
+                return;
+            }
+
+            let components = form.values().get_text("components").unwrap_or_default();
+            let prompts: Vec<String> = components
+                .split_whitespace()
+                .map(|component| format!("Describe {}.", component))
+                .collect();
+
+            for (index, prompt) in prompts.iter().enumerate() {
+                assert!(form.insert_step(index + 1, Box::new(TextBlockStep::new(prompt))));
+            }
+        });
+
+        type_text(&mut form, "ui db");
+        press(&mut form, KeyCode::Enter);
+        assert_eq!(1, form.active_step);
+        assert!(form.step(2).is_some() && form.step(3).is_none());
+
+        // Once begun, only steps after the active step may be changed
+        assert!(!form.insert_step(1, Box::new(TextBlockStep::new("Enter a note."))));
+        assert!(form.remove_step(1).is_none());
+
+        let prompts: Vec<String> = (1..3)
+            .map(|index| {
+                let help = form.step(index).unwrap().help();
+                help.iter().map(|text| text.content()).collect()
+            })
+            .collect();
+        assert_eq!(vec!["Describe ui.", "Describe db."], prompts);
+
+        assert!(form.remove_step(3).is_none());
+        assert!(form.insert_step(3, Box::new(TextBlockStep::new("Enter a note."))));
+        assert!(form.remove_step(3).is_some());
+
+        // A removed step's evaluations are forgotten
+        let mut step = CompoundStep::new();
+        let mut note = TextInput::new("Enter a note.", false);
+        let empty_note = note.set_evaluation(Evaluation::IsEmpty);
+        note.add_to(&mut step);
+        assert!(form.insert_step(3, Box::new(step)));
+        assert_eq!(Some((3, 0)), form.dependency_state().source(&empty_note));
+        assert_eq!(Some(true), form.dependency_state().get(&empty_note));
+
+        assert!(form.remove_step(3).is_some());
+        assert_eq!(None, form.dependency_state().source(&empty_note));
+        assert_eq!(None, form.dependency_state().get(&empty_note));
+
+        // In batch mode, steps appended once begun are shown with the others
+        let mut form = Form::new();
+        form.set_navigation_mode(NavigationMode::Batch);
+        TextBlockStep::new("Enter a body.").add_to(&mut form);
+        TextBlockStep::new("Enter a footer.").add_to(&mut form);
+        type_text(&mut form, "Body");
+        assert_eq!(1, form.max_step);
+
+        assert!(form.insert_step(2, Box::new(TextBlockStep::new("Enter a note."))));
+        assert_eq!(2, form.max_step);
+        assert!(form.remove_step(2).is_some());
+        assert_eq!(1, form.max_step);
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        let messages = [
+            "feat(ui): add a button",
+            "fix: handle empty input\n\nFirst paragraph.\n\nSecond paragraph.",
+            "feat(ui)!: add a button\n\nIt's blue.\n\nRefs: #1\nReviewed-by: Jane\nBREAKING CHANGE: Gone.",
+        ];
+
+        for message in messages {
+            let state = prefab::conventional_commit().parse(message).unwrap();
+
+            let mut form = prefab::conventional_commit();
+            form.restore(&state);
+            assert_eq!(message, form.result());
+        }
+
+        let form = prefab::conventional_commit();
+        let state = form.parse("fix: a\n\nBody.\n\nRefs: #1").unwrap();
+        assert_eq!(Some("Body."), state.values().get_text("commit_body"));
+        assert_eq!(
+            Some(&[("Refs".to_string(), "#1".to_string())][..]),
+            state.values().get_pairs("commit_trailers")
+        );
+
+        // Text the steps can't produce isn't parsed
+        let mut form = Form::new();
+        let mut breaking = YesNoStep::new("Breaking?", "Describe it.", "BREAKING CHANGE");
+        breaking.set_omit_if_no(false);
+        breaking.add_to(&mut form);
+        assert!(form.parse("BREAKING CHANGE: Gone.").is_some());
+        assert!(form.parse("Unrelated.").is_none());
+    }
+
+    fn optional_scope_form() -> Form {
+        let mut form = Form::new();
+
+        let mut step = CompoundStep::new();
+        let mut input = TextInput::new("Enter a scope.", false);
+        input.set_name("scope");
+        input.set_required(true);
+        input.add_to(&mut step);
+        step.set_optional(true);
+        step.add_to(&mut form);
+
+        let mut step = CompoundStep::new();
+        TextInput::new("Enter a summary.", false).add_to(&mut step);
+        step.add_to(&mut form);
+
+        form
+    }
+
+    #[test]
+    fn test_skip_optional_step() {
+        let mut form = optional_scope_form();
+
+        type_text(&mut form, "ui");
+
+        let terminal = render(&mut form);
+        terminal.expect_screen_contains("Enter a scope. (Ctrl+N to skip)");
+
+        // The skipped step is left empty, even though it was required
+        assert_eq!(FormStatus::Active, press_ctrl(&mut form, 'n'));
+        assert_eq!(None, form.values().get_text("scope"));
+
+        type_text(&mut form, "Fix the build");
+        assert_eq!(FormStatus::Submitted, press(&mut form, KeyCode::Enter));
+
+        let result = form.form_result();
+        assert_eq!("Fix the build", result.text());
+        assert!(result.is_skipped(0));
+        assert!(!result.is_skipped(1));
+    }
+
+    #[test]
+    fn test_stream_skipped_step() {
+        let (sender, mut device) = ChannelInputDevice::channel();
+        let key = |code, modifiers| InputEvent::Key(KeyEvent::new(code, modifiers));
+        let mut events = vec![
+            key(KeyCode::Char('u'), KeyModifiers::NONE),
+            key(KeyCode::Char('n'), KeyModifiers::CONTROL),
+        ];
+        events.extend(
+            "Fix"
+                .chars()
+                .map(|ch| key(KeyCode::Char(ch), KeyModifiers::NONE)),
+        );
+        events.push(key(KeyCode::Enter, KeyModifiers::NONE));
+        for event in events {
+            sender.send(event).unwrap();
+        }
+
+        // A skipped step's content is streamed no more than it's included in the result
+        let mut output = Vec::new();
+        let mut backend = SizedBackend::new(80, 24);
+        optional_scope_form()
+            .execute_to(&mut backend, &mut device, &mut output)
+            .unwrap();
+
+        let mut form = optional_scope_form();
+        type_text(&mut form, "u");
+        press_ctrl(&mut form, 'n');
+        type_text(&mut form, "Fix");
+        press(&mut form, KeyCode::Enter);
+
+        assert_eq!("Fix", form.result());
+        assert_eq!(form.result(), String::from_utf8(output).unwrap());
+    }
+
+    #[test]
+    fn test_write_result() {
+        let whitespace_form = |post_process: bool| {
+            let mut form = Form::new();
+            for text in ["\n  ", "  Header  ", "", "\n\nBody\n", "", " \n"] {
+                let mut step = CompoundStep::new();
+                match text {
+                    "" => TextInput::new("Enter a note.", false).add_to(&mut step),
+                    text => StaticText::new(text).add_to(&mut step),
+                };
+                step.add_to(&mut form);
+            }
+
+            if post_process {
+                form.add_post_processor(postprocess::ensure_trailing_newline);
+            }
+
+            form
+        };
+
+        let submit = || {
+            let (sender, device) = ChannelInputDevice::channel();
+            for _ in 0..3 {
+                let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+                sender.send(InputEvent::Key(enter)).unwrap();
+            }
+
+            device
+        };
+
+        for post_process in [false, true] {
+            // Whitespace around the content and across empty steps is trimmed as in the result
+            let form = whitespace_form(post_process);
+            let mut output = Vec::new();
+            form.write_result(&mut output).unwrap();
+            assert_eq!(form.result(), String::from_utf8(output).unwrap());
+
+            let mut output = Vec::new();
+            let mut backend = SizedBackend::new(80, 24);
+            whitespace_form(post_process)
+                .execute_to(&mut backend, &mut submit(), &mut output)
+                .unwrap();
+
+            let mut backend = SizedBackend::new(80, 24);
+            let result = whitespace_form(post_process)
+                .execute(&mut backend, &mut submit())
+                .unwrap();
+            assert_eq!(result, String::from_utf8(output).unwrap());
+        }
+
+        assert_eq!("Header  \n\n\n\nBody", whitespace_form(false).result());
+        assert_eq!("Header  \n\n\n\nBody\n", whitespace_form(true).result());
+    }
+
+    #[test]
+    fn test_viewport_height() {
+        let mut form = Form::new();
+        form.set_viewport_height(6);
+        for step_index in 0..30 {
+            let mut step = CompoundStep::new();
+            StaticText::new(&format!("Step {}: ", step_index)).add_to(&mut step);
+            TextInput::new("Enter a value.", false).add_to(&mut step);
+            step.add_to(&mut form);
+        }
+
+        for _ in 0..20 {
+            press(&mut form, KeyCode::Enter);
+        }
+        press(&mut form, KeyCode::Esc);
+        press(&mut form, KeyCode::Esc);
+
+        // Render twice so that the steps' heights are known
+        let mut terminal = VirtualTerminal::new();
+        let mut interface = Interface::new_relative(&mut terminal).unwrap();
+        form.render_at(&mut interface, pos!(0, 0), true);
+        let height = form.render_at(&mut interface, pos!(0, 0), true);
+        interface.apply().unwrap();
+        drop(interface);
+
+        assert_eq!(7, height);
+        let screen = terminal.screen_contents();
+        assert!(screen.contains("⋯ 16 earlier steps"));
+        assert!(screen.contains("Step 16: \nStep 17: \nStep 18: \nStep 19: "));
+        assert!(screen.contains("⋯ 1 later steps"));
+        assert!(!screen.contains("Step 15:"));
+        assert!(!screen.contains("Step 20:"));
+    }
+
+    #[test]
+    fn test_collapse_completed() {
+        let mut form = Form::new();
+        form.set_collapse_completed(true);
+
+        let mut step = CompoundStep::new();
+        StaticText::new("Type: ").add_to(&mut step);
+        TextInput::new("Enter a type.", false).add_to(&mut step);
+        step.add_to(&mut form);
+        TextBlockStep::new("Enter a body.").add_to(&mut form);
+
+        type_text(&mut form, "feat");
+        press(&mut form, KeyCode::Enter);
+        type_text(&mut form, "First line");
+
+        let screen = render(&mut form).screen_contents();
+        assert!(screen.contains("✓ Type: feat\nFirst line"));
+
+        // Refocusing the step expands it again
+        press(&mut form, KeyCode::Esc);
+        let screen = render(&mut form).screen_contents();
+        assert!(!screen.contains('✓'));
+        assert!(screen.contains("Type: feat\nFirst line"));
+    }
+
+    #[test]
+    fn test_status_gutter() {
+        let mut form = Form::new();
+        form.set_status_gutter(true);
+
+        for prompt in ["First", "Second", "Third"] {
+            let mut step = CompoundStep::new();
+            StaticText::new(&format!("{}: ", prompt)).add_to(&mut step);
+            TextInput::new("Enter a value.", false).add_to(&mut step);
+            step.set_max_line_length(10);
+            step.add_to(&mut form);
+        }
+
+        type_text(&mut form, "ok");
+        press(&mut form, KeyCode::Enter);
+        type_text(&mut form, "too long");
+        press(&mut form, KeyCode::Enter);
+
+        let terminal = render(&mut form);
+
+        let screen = terminal.screen_contents();
+        assert!(screen.contains("✓ First: ok\n! Second: too long\n● Third: "));
+    }
+
+    #[test]
+    fn test_jump_to_error() {
+        let mut form = Form::new();
+        for name in ["first", "second"] {
+            let mut step = CompoundStep::new();
+            let mut input = TextInput::new("Enter a value.", false);
+            input.set_name(name);
+            input.set_required(true);
+            input.add_to(&mut step);
+            step.add_to(&mut form);
+        }
+
+        type_text(&mut form, "a");
+        press(&mut form, KeyCode::Enter);
+        type_text(&mut form, "b");
+
+        // Clear the first value from elsewhere, leaving its step invalid
+        let handle = form.values_handle();
+        handle.set("first", ControlValue::Text(String::new()));
+        thread::sleep(WATCH_INTERVAL);
+        form.tick_if_due();
+
+        // Submission is blocked and focuses the first error
+        assert_eq!(FormStatus::Active, press(&mut form, KeyCode::Enter));
+        assert_eq!(0, form.state().active_step());
+
+        let terminal = render(&mut form);
+        assert!(terminal
+            .screen_contents()
+            .contains("Resolve 1 error(s) before submitting: A value is required."));
+
+        // Repeat presses cycle through the remaining errors
+        handle.set("second", ControlValue::Text(String::new()));
+        thread::sleep(WATCH_INTERVAL);
+        form.tick_if_due();
+
+        press(&mut form, KeyCode::F(8));
+        assert_eq!(1, form.state().active_step());
+        press(&mut form, KeyCode::F(8));
+        assert_eq!(0, form.state().active_step());
+    }
+
+    #[test]
+    fn test_min_terminal_size() {
+        let build_form = || {
+            let mut form = Form::new();
+            let mut step = CompoundStep::new();
+            let mut scope = TextInput::new("Enter a scope.", false);
+            scope.set_name("scope");
+            scope.add_to(&mut step);
+            step.add_to(&mut form);
+            form.set_min_size(40, 10);
+            form
+        };
+
+        let run = |form: &mut Form, backend: &mut SizedBackend, keys: &[KeyEvent]| {
+            let (sender, mut device) = ChannelInputDevice::channel();
+            for key in keys {
+                sender.send(InputEvent::Key(*key)).unwrap();
+            }
+
+            form.run(backend, &mut device)
+        };
+
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        let keys = [
+            key(KeyCode::Char('a')),
+            key(KeyCode::Enter),
+            KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL),
+        ];
+
+        // A small terminal shows the message in place of the form
+        let mut form = build_form();
+        let mut backend = SizedBackend::new(30, 5);
+        form.render_running(&mut backend);
+        assert_eq!("Please enlarge your terminal (need 40x10)", backend.row(0));
+        assert_eq!(None, backend.cursor);
+
+        // Input other than cancellation is paused
+        let result = run(&mut form, &mut backend, &keys);
+        assert!(matches!(result, Err(Error::Canceled)));
+        assert_eq!(Some(""), form.values().get_text("scope"));
+
+        let mut form = build_form();
+        let mut backend = SizedBackend::new(40, 10);
+        run(&mut form, &mut backend, &keys[..2]).unwrap();
+        assert_eq!(Some("a"), form.values().get_text("scope"));
+
+        // Resize events update the size reported by a caller-sized backend
+        let mut form = build_form();
+        let mut backend = TerminalSizeBackend::new(SizedBackend::new(40, 10), (30, 5));
+        let (sender, mut device) = ChannelInputDevice::channel();
+        sender.send(InputEvent::Resize(40, 10)).unwrap();
+        for key in &keys[..2] {
+            sender.send(InputEvent::Key(*key)).unwrap();
+        }
+        form.run(&mut backend, &mut device).unwrap();
+        assert_eq!((Some(40), Some(10)), (backend.width(), backend.height()));
+        assert_eq!(Some("a"), form.values().get_text("scope"));
+    }
+
+    #[test]
+    fn test_debug_overlay() {
+        let mut form = Form::new();
+        let mut step = CompoundStep::new();
+        StaticText::new("Scope: ").add_to(&mut step);
+        TextInput::new("Enter a scope.", false).add_to(&mut step);
+        step.add_to(&mut form);
+        TextBlockStep::new("Enter a body.").add_to(&mut form);
+        form.set_debug(true);
+
+        type_text(&mut form, "ui");
+        press(&mut form, KeyCode::Enter);
+
+        let mut backend = SizedBackend::new(60, 10);
+        form.render_at(&mut backend, pos!(0, 0), true);
+
+        // The overlay is right-aligned, describing each rendered step and the focus
+        assert!(backend.row(0).ends_with("step 1/2 max 1"));
+        assert!(backend
+            .row(1)
+            .ends_with(" #0 rows 1+1 control 1/2 max 1 Text"));
+        assert!(backend.row(2).ends_with("> #1 rows 2+1"));
+        assert_eq!(60, backend.row(1).chars().count());
+
+        form.set_debug(false);
+        let mut backend = SizedBackend::new(60, 10);
+        form.render_at(&mut backend, pos!(0, 0), true);
+        assert_eq!("Enter a body.", backend.row(0));
+        assert_eq!("Scope: ui", backend.row(1));
+    }
+
+    #[test]
+    fn test_controls_meta() {
+        let mut form = Form::new();
+        let mut step = CompoundStep::new();
+        StaticText::new("Scope: ").add_to(&mut step);
+        let mut scope = TextInput::new("Enter a scope.", false);
+        scope.set_name("scope");
+        scope.set_required(true);
+        scope.add_to(&mut step);
+        step.add_to(&mut form);
+
+        let mut step = CompoundStep::new();
+        let mut kind = SelectInput::new("Select a type.", vec![("feat", ""), ("fix", "")]);
+        kind.set_name("type");
+        kind.add_to(&mut step);
+        step.add_to(&mut form);
+
+        let expected = vec![
+            ControlMeta::new(ControlKind::Static),
+            ControlMeta::new(ControlKind::Text)
+                .with_name(Some("scope"))
+                .with_required(true),
+            ControlMeta::new(ControlKind::Select).with_name(Some("type")),
+        ];
+        assert_eq!(expected, form.controls_meta());
+        assert_eq!(expected, form.form_result().controls());
+
+        // A required control may not be advanced past while empty
+        assert_eq!(FormStatus::Active, press(&mut form, KeyCode::Enter));
+        assert_eq!(0, form.active_step);
+
+        type_text(&mut form, "ui");
+        assert_eq!(FormStatus::Active, press(&mut form, KeyCode::Enter));
+        assert_eq!(1, form.active_step);
+
+        // The debug overlay identifies the focused control
+        form.set_debug(true);
+        let mut backend = SizedBackend::new(60, 10);
+        form.render_at(&mut backend, pos!(0, 0), true);
+        assert!(backend.row(2).ends_with(" control 0/1 max 0 Select type"));
     }
 }
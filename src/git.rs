@@ -0,0 +1,275 @@
+//! Integration with Git's commit message hooks, running a form to compose the commit message.
+//! Enabled by the `git` feature.
+
+use std::{
+    fs,
+    io::stdout,
+    path::{Path, PathBuf},
+};
+
+use tty_interface::Interface;
+
+use crate::{
+    backend::TerminalSizeBackend,
+    device::StdinDevice,
+    postprocess::trailer_key,
+    state::FormState,
+    value::{ControlValue, ValueStore},
+    Error, Form, Result,
+};
+
+/// The line below which Git ignores a commit message's contents, such as a verbose commit's diff.
+const SCISSORS_LINE: &str = "# ------------------------ >8 ------------------------";
+
+/// A commit message parsed into its subject, body, and trailers. Git's comment lines are omitted.
+///
+/// # Examples
+/// ```
+/// use tty_form::git::CommitMessage;
+///
+/// let message = CommitMessage::parse("Fix the build\n\nIt was broken.\n\nRefs: #1\n# Comment\n");
+///
+/// assert_eq!("Fix the build", message.subject());
+/// assert_eq!("It was broken.", message.body());
+/// assert_eq!(&[("Refs".to_string(), "#1".to_string())], message.trailers());
+/// ```
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct CommitMessage {
+    subject: String,
+    body: String,
+    trailers: Vec<(String, String)>,
+}
+
+impl CommitMessage {
+    /// Parse the specified commit message, ignoring comment lines.
+    pub fn parse(message: &str) -> Self {
+        let content: Vec<&str> = message
+            .lines()
+            .take_while(|line| *line != SCISSORS_LINE)
+            .filter(|line| !line.starts_with('#'))
+            .collect();
+
+        let content = content.join("\n");
+        let content = content.trim();
+
+        let (subject, rest) = match content.split_once('\n') {
+            Some((subject, rest)) => (subject, rest.trim()),
+            None => (content, ""),
+        };
+
+        // The last paragraph holds trailers if every line is one
+        let (body, trailers) = match rest.rsplit_once("\n\n") {
+            Some((body, last)) if is_trailer_paragraph(last) => (body.trim(), last),
+            None if is_trailer_paragraph(rest) => ("", rest),
+            _ => (rest, ""),
+        };
+
+        let trailers = trailers
+            .lines()
+            .filter_map(|line| {
+                let (key, value) = line.split_once(": ")?;
+                Some((key.to_string(), value.to_string()))
+            })
+            .collect();
+
+        Self {
+            subject: subject.trim().to_string(),
+            body: body.to_string(),
+            trailers,
+        }
+    }
+
+    /// The message's first line.
+    pub fn subject(&self) -> &str {
+        &self.subject
+    }
+
+    /// The message's content between its subject and trailers.
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+
+    /// The message's trailing `Key: value` pairs.
+    pub fn trailers(&self) -> &[(String, String)] {
+        &self.trailers
+    }
+}
+
+/// Runs a form as a `prepare-commit-msg` or `commit-msg` hook. The hook's message file is parsed
+/// to populate the form's named fields, and the form's result is written back to it, followed by
+/// the file's original comment lines. If no terminal is available, such as when Git is run by
+/// another tool, the message file is left as-is.
+///
+/// # Examples
+/// ```no_run
+/// use tty_form::{git::CommitHook, prefab};
+///
+/// // In a prepare-commit-msg hook, whose first argument is the message file's path
+/// let path = std::env::args().nth(1).unwrap();
+///
+/// let mut hook = CommitHook::new(path);
+/// hook.set_body_field("commit_body");
+/// hook.set_trailers_field("commit_trailers");
+/// hook.execute(prefab::conventional_commit())?;
+/// # Ok::<(), tty_form::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct CommitHook {
+    message_path: PathBuf,
+    subject_field: Option<String>,
+    body_field: Option<String>,
+    trailers_field: Option<String>,
+}
+
+impl CommitHook {
+    /// Create a hook for the commit message file at the specified path.
+    pub fn new(message_path: impl Into<PathBuf>) -> Self {
+        Self {
+            message_path: message_path.into(),
+            subject_field: None,
+            body_field: None,
+            trailers_field: None,
+        }
+    }
+
+    /// The commit message file's path.
+    pub fn message_path(&self) -> &Path {
+        &self.message_path
+    }
+
+    /// Set the name of the textual field populated with an existing message's subject.
+    pub fn set_subject_field(&mut self, name: &str) {
+        self.subject_field = Some(name.to_string());
+    }
+
+    /// Set the name of the textual field populated with an existing message's body.
+    pub fn set_body_field(&mut self, name: &str) {
+        self.body_field = Some(name.to_string());
+    }
+
+    /// Set the name of the key-value field populated with an existing message's trailers.
+    pub fn set_trailers_field(&mut self, name: &str) {
+        self.trailers_field = Some(name.to_string());
+    }
+
+    /// Populate and execute the specified form in the terminal, writing its result to the message
+    /// file. Returns whether the form was executed, being false if no terminal was available.
+    pub fn execute(&self, mut form: Form) -> Result<bool> {
+        if !has_terminal() {
+            return Ok(false);
+        }
+
+        let original = match fs::read_to_string(&self.message_path) {
+            Ok(original) => original,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(Error::Output(err)),
+        };
+
+        form.restore(&FormState::new(
+            0,
+            self.values(&CommitMessage::parse(&original)),
+        ));
+
+        let size = crossterm::terminal::size().map_err(Error::TerminalSize)?;
+
+        let mut stdout = stdout();
+        let mut backend = TerminalSizeBackend::new(Interface::new_relative(&mut stdout)?, size);
+        let result = form.execute(&mut backend, &mut StdinDevice);
+        backend.inner_mut().exit()?;
+
+        let mut message = result?;
+        message.push('\n');
+
+        // Retain Git's comments, such as the commit's status and a verbose commit's diff
+        let comments_start = original
+            .lines()
+            .position(|line| line.starts_with('#'))
+            .unwrap_or(usize::MAX);
+        let comments: Vec<&str> = original.lines().skip(comments_start).collect();
+        if !comments.is_empty() {
+            message.push('\n');
+            message.push_str(&comments.join("\n"));
+            message.push('\n');
+        }
+
+        fs::write(&self.message_path, message).map_err(Error::Output)?;
+
+        Ok(true)
+    }
+
+    /// The form values to populate from the specified message, for its non-empty parts.
+    fn values(&self, message: &CommitMessage) -> ValueStore {
+        let mut values = ValueStore::new();
+
+        let text_fields = [
+            (&self.subject_field, message.subject()),
+            (&self.body_field, message.body()),
+        ];
+        for (name, text) in text_fields {
+            if let (Some(name), false) = (name, text.is_empty()) {
+                values.set(name, ControlValue::Text(text.to_string()));
+            }
+        }
+
+        if let (Some(name), false) = (&self.trailers_field, message.trailers().is_empty()) {
+            values.set(name, ControlValue::Pairs(message.trailers().to_vec()));
+        }
+
+        values
+    }
+}
+
+/// Whether every line of the specified paragraph is a trailer.
+fn is_trailer_paragraph(paragraph: &str) -> bool {
+    !paragraph.is_empty() && paragraph.lines().all(|line| trailer_key(line).is_some())
+}
+
+/// Whether a terminal is available for the form's input and output. Hooks' standard input is
+/// typically redirected, so the controlling terminal is checked instead where supported.
+fn has_terminal() -> bool {
+    #[cfg(unix)]
+    {
+        fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open("/dev/tty")
+            .is_ok()
+    }
+
+    #[cfg(not(unix))]
+    {
+        use std::io::IsTerminal;
+        std::io::stdin().is_terminal() && stdout().is_terminal()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CommitMessage;
+
+    #[test]
+    fn test_parse_commit_message() {
+        let message = CommitMessage::parse(
+            "feat: add\n\nFirst.\n\nSecond.\n\nRefs: #1\nSigned-off-by: A\n\n# Comment\n",
+        );
+        assert_eq!("feat: add", message.subject());
+        assert_eq!("First.\n\nSecond.", message.body());
+        assert_eq!(
+            &[
+                ("Refs".to_string(), "#1".to_string()),
+                ("Signed-off-by".to_string(), "A".to_string()),
+            ],
+            message.trailers()
+        );
+
+        let message = CommitMessage::parse("fix: it\n\nRefs: #2\n");
+        assert_eq!("", message.body());
+        assert_eq!(
+            &[("Refs".to_string(), "#2".to_string())],
+            message.trailers()
+        );
+
+        let message = CommitMessage::parse("# Only comments\n");
+        assert_eq!(CommitMessage::default(), message);
+    }
+}
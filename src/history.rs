@@ -0,0 +1,134 @@
+//! Values chosen in a form's previous executions, such as to preselect a control's last choice.
+
+use std::collections::BTreeMap;
+
+use crate::state::{escape, unescape};
+
+/// The header identifying a serialized history.
+const HISTORY_HEADER: &str = "tty-form-history 1";
+
+/// The values chosen for a form's named controls across its previous executions, each with the
+/// number of times it was chosen. A form with history enabled lets its controls recall their
+/// entries as it begins, and records their values once it's submitted.
+///
+/// # Examples
+/// ```
+/// use tty_form::history::History;
+///
+/// let mut history = History::new();
+/// history.record("commit_type", "feat");
+/// history.record("commit_type", "fix");
+/// history.record("commit_type", "feat");
+///
+/// let restored = History::deserialize(&history.serialize()).unwrap();
+///
+/// assert_eq!(Some("feat"), restored.last("commit_type"));
+/// assert_eq!(2, restored.count("commit_type", "feat"));
+/// assert_eq!(1, restored.count("commit_type", "fix"));
+/// ```
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct History {
+    entries: BTreeMap<String, Vec<HistoryEntry>>,
+}
+
+impl History {
+    /// Create a new, empty history.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The values chosen for the specified control, most recently chosen first.
+    pub fn entries(&self, name: &str) -> &[HistoryEntry] {
+        self.entries.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    /// The value last chosen for the specified control, if any.
+    pub fn last(&self, name: &str) -> Option<&str> {
+        self.entries(name).first().map(HistoryEntry::value)
+    }
+
+    /// The number of times the specified value was chosen for the specified control.
+    pub fn count(&self, name: &str, value: &str) -> usize {
+        self.entries(name)
+            .iter()
+            .find(|entry| entry.value == value)
+            .map_or(0, HistoryEntry::count)
+    }
+
+    /// Record that the specified value was chosen for the specified control.
+    pub fn record(&mut self, name: &str, value: &str) {
+        let entries = self.entries.entry(name.to_string()).or_default();
+
+        let mut entry = match entries.iter().position(|entry| entry.value == value) {
+            Some(index) => entries.remove(index),
+            None => HistoryEntry {
+                value: value.to_string(),
+                count: 0,
+            },
+        };
+
+        entry.count += 1;
+        entries.insert(0, entry);
+    }
+
+    /// Serialize this history to text, one tab-separated entry per line.
+    pub fn serialize(&self) -> String {
+        let mut lines = vec![HISTORY_HEADER.to_string()];
+
+        for (name, entries) in &self.entries {
+            for entry in entries {
+                let count = entry.count.to_string();
+                let fields = [name.as_str(), entry.value.as_str(), count.as_str()];
+                let fields: Vec<_> = fields.into_iter().map(escape).collect();
+                lines.push(fields.join("\t"));
+            }
+        }
+
+        lines.join("\n")
+    }
+
+    /// Deserialize a history previously serialized by [History::serialize], if it's valid.
+    pub fn deserialize(serialized: &str) -> Option<Self> {
+        let mut lines = serialized.lines();
+        if lines.next()? != HISTORY_HEADER {
+            return None;
+        }
+
+        let mut history = Self::new();
+
+        for line in lines {
+            let fields: Vec<String> = line.split('\t').map(unescape).collect();
+            let entry = HistoryEntry {
+                value: fields.get(1)?.clone(),
+                count: fields.get(2)?.parse().ok()?,
+            };
+
+            history
+                .entries
+                .entry(fields.first()?.clone())
+                .or_default()
+                .push(entry);
+        }
+
+        Some(history)
+    }
+}
+
+/// A value chosen for a control in a form's previous executions.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct HistoryEntry {
+    value: String,
+    count: usize,
+}
+
+impl HistoryEntry {
+    /// The chosen value.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// The number of times the value was chosen.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
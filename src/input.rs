@@ -0,0 +1,331 @@
+//! Input events received by a form, independent of the terminal library which produced them.
+//!
+//! # Examples
+//! ```
+//! use tty_form::input::{InputEvent, KeyCode, KeyEvent, KeyModifiers};
+//!
+//! let event = InputEvent::Key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL));
+//! ```
+
+use std::{
+    any::Any,
+    collections::VecDeque,
+    ops::BitOr,
+    sync::{Arc, Mutex},
+};
+
+/// An input event received by a form, either from its input device or injected by the application.
+#[derive(Debug)]
+pub enum InputEvent {
+    /// A key was pressed.
+    Key(KeyEvent),
+    /// The terminal was resized to the specified columns and rows.
+    Resize(u16, u16),
+    /// Tick the focused step immediately, regardless of its tick interval.
+    Tick,
+    /// Re-render the form, such as after its state was changed elsewhere.
+    Refresh,
+    /// An application-defined event, passed to the form's external event handler.
+    External(Box<dyn Any + Send>),
+}
+
+/// A handle by which other threads may inject events into an executing form, such as to wake it
+/// once background work completes. Injected events are handled as the form next ticks.
+///
+/// # Examples
+/// ```
+/// use std::thread;
+/// use tty_form::{Form, input::InputEvent};
+///
+/// let mut form = Form::new();
+/// form.set_external_handler(|_form, event| {
+///     if let Some(message) = event.downcast_ref::<String>() {
+///         println!("{}", message);
+///     }
+/// });
+///
+/// let injector = form.event_injector();
+/// thread::spawn(move || injector.inject(InputEvent::External(Box::new("done".to_string()))))
+///     .join()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EventInjector {
+    events: Arc<Mutex<VecDeque<InputEvent>>>,
+}
+
+impl EventInjector {
+    /// Create a new injector with no pending events.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue the specified event for the form to handle.
+    pub fn inject(&self, event: InputEvent) {
+        if let Ok(mut events) = self.events.lock() {
+            events.push_back(event);
+        }
+    }
+
+    /// Take the events queued since they were last taken, in order.
+    pub(crate) fn take(&self) -> Vec<InputEvent> {
+        self.events
+            .lock()
+            .map(|mut events| events.drain(..).collect())
+            .unwrap_or_default()
+    }
+}
+
+/// How a form handles a key event, as decided by its event interceptor before the event reaches
+/// the form's bindings and steps.
+///
+/// # Examples
+/// ```
+/// use tty_form::{
+///     Form,
+///     input::{Intercept, KeyCode, KeyEvent, KeyModifiers},
+/// };
+///
+/// let mut form = Form::new();
+/// form.set_event_interceptor(|event| match event.code {
+///     // Ignore function keys, and treat Ctrl+J as Enter
+///     KeyCode::F(_) => Intercept::Swallow,
+///     KeyCode::Char('j') if event.modifiers.contains(KeyModifiers::CONTROL) => {
+///         Intercept::Remap(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))
+///     }
+///     _ => Intercept::Pass,
+/// });
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Intercept {
+    /// Handle the event as received.
+    Pass,
+    /// Discard the event.
+    Swallow,
+    /// Handle the specified event in place of the one received.
+    Remap(KeyEvent),
+    /// Submit the form from the active step, as with the form's submit keys.
+    Submit,
+}
+
+/// A key press and the modifiers held while it was pressed.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct KeyEvent {
+    /// The pressed key.
+    pub code: KeyCode,
+    /// The modifiers held while the key was pressed.
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyEvent {
+    /// Create a new key event for the specified key and modifiers.
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self { code, modifiers }
+    }
+}
+
+/// A key which may be pressed.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum KeyCode {
+    /// Backspace.
+    Backspace,
+    /// Enter.
+    Enter,
+    /// The left arrow.
+    Left,
+    /// The right arrow.
+    Right,
+    /// The up arrow.
+    Up,
+    /// The down arrow.
+    Down,
+    /// Home.
+    Home,
+    /// End.
+    End,
+    /// Page up.
+    PageUp,
+    /// Page down.
+    PageDown,
+    /// Tab.
+    Tab,
+    /// Shift+Tab.
+    BackTab,
+    /// Delete.
+    Delete,
+    /// Insert.
+    Insert,
+    /// The numbered function key, such as F1.
+    F(u8),
+    /// A character key.
+    Char(char),
+    /// Escape.
+    Esc,
+}
+
+/// The modifier keys held during a key press, which may be combined with `|`.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub struct KeyModifiers(u8);
+
+impl KeyModifiers {
+    /// No modifiers.
+    pub const NONE: Self = Self(0);
+    /// The shift key.
+    pub const SHIFT: Self = Self(0b001);
+    /// The control key.
+    pub const CONTROL: Self = Self(0b010);
+    /// The alt key.
+    pub const ALT: Self = Self(0b100);
+
+    /// Whether all of the specified modifiers are held.
+    pub fn contains(&self, modifiers: KeyModifiers) -> bool {
+        self.0 & modifiers.0 == modifiers.0
+    }
+
+    /// Whether no modifiers are held.
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl BitOr for KeyModifiers {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+/// Convert a crossterm input event to the equivalent form input event, if one exists.
+pub(crate) fn from_crossterm(event: crossterm::event::Event) -> Option<InputEvent> {
+    use crossterm::event::{Event, KeyCode as CrosstermCode, KeyModifiers as CrosstermModifiers};
+
+    let key_event = match event {
+        Event::Key(key_event) => key_event,
+        Event::Resize(columns, rows) => return Some(InputEvent::Resize(columns, rows)),
+        _ => return None,
+    };
+
+    let code = match key_event.code {
+        CrosstermCode::Backspace => KeyCode::Backspace,
+        CrosstermCode::Enter => KeyCode::Enter,
+        CrosstermCode::Left => KeyCode::Left,
+        CrosstermCode::Right => KeyCode::Right,
+        CrosstermCode::Up => KeyCode::Up,
+        CrosstermCode::Down => KeyCode::Down,
+        CrosstermCode::Home => KeyCode::Home,
+        CrosstermCode::End => KeyCode::End,
+        CrosstermCode::PageUp => KeyCode::PageUp,
+        CrosstermCode::PageDown => KeyCode::PageDown,
+        CrosstermCode::Tab => KeyCode::Tab,
+        CrosstermCode::BackTab => KeyCode::BackTab,
+        CrosstermCode::Delete => KeyCode::Delete,
+        CrosstermCode::Insert => KeyCode::Insert,
+        CrosstermCode::F(number) => KeyCode::F(number),
+        CrosstermCode::Char(ch) => KeyCode::Char(ch),
+        CrosstermCode::Esc => KeyCode::Esc,
+        _ => return None,
+    };
+
+    let mut modifiers = KeyModifiers::NONE;
+    for (crossterm_modifier, modifier) in [
+        (CrosstermModifiers::SHIFT, KeyModifiers::SHIFT),
+        (CrosstermModifiers::CONTROL, KeyModifiers::CONTROL),
+        (CrosstermModifiers::ALT, KeyModifiers::ALT),
+    ] {
+        if key_event.modifiers.contains(crossterm_modifier) {
+            modifiers = modifiers | modifier;
+        }
+    }
+
+    Some(InputEvent::Key(KeyEvent::new(code, modifiers)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc, thread};
+
+    use crate::{
+        control::{Control, TextInput},
+        step::{CompoundStep, Step},
+        Form, FormStatus,
+    };
+
+    use super::{from_crossterm, InputEvent, KeyCode, KeyEvent, KeyModifiers};
+
+    #[test]
+    fn test_injected_events() {
+        let mut form = Form::new();
+        let mut step = CompoundStep::new();
+        let mut branch = TextInput::new("Enter a branch.", false);
+        branch.set_name("branch");
+        branch.add_to(&mut step);
+        step.add_to(&mut form);
+
+        let received = Rc::new(RefCell::new(Vec::new()));
+        let handler_received = received.clone();
+        form.set_external_handler(move |_form, event| {
+            if let Some(branch) = event.downcast_ref::<&str>() {
+                handler_received.borrow_mut().push(branch.to_string());
+            }
+        });
+
+        let injector = form.event_injector();
+        thread::spawn(move || injector.inject(InputEvent::External(Box::new("main"))))
+            .join()
+            .unwrap();
+
+        // Injected events are only handled once the form ticks
+        assert!(received.borrow().is_empty());
+        assert_eq!(FormStatus::Active, form.tick_if_due());
+        assert_eq!(vec!["main".to_string()], *received.borrow());
+
+        form.inject_event(InputEvent::Key(KeyEvent::new(
+            KeyCode::Enter,
+            KeyModifiers::NONE,
+        )));
+        assert_eq!(FormStatus::Submitted, form.tick_if_due());
+    }
+
+    #[test]
+    fn test_from_crossterm() {
+        use crossterm::event::{
+            Event, KeyCode as CrosstermCode, KeyEvent as CrosstermEvent,
+            KeyModifiers as CrosstermModifiers,
+        };
+
+        let key = |code, modifiers| Event::Key(CrosstermEvent::new(code, modifiers));
+
+        let event = from_crossterm(key(
+            CrosstermCode::Char('c'),
+            CrosstermModifiers::CONTROL | CrosstermModifiers::ALT,
+        ));
+        let expected = KeyEvent::new(
+            KeyCode::Char('c'),
+            KeyModifiers::CONTROL | KeyModifiers::ALT,
+        );
+        assert!(matches!(event, Some(InputEvent::Key(event)) if event == expected));
+
+        let event = from_crossterm(key(CrosstermCode::F(2), CrosstermModifiers::SHIFT));
+        let expected = KeyEvent::new(KeyCode::F(2), KeyModifiers::SHIFT);
+        assert!(matches!(event, Some(InputEvent::Key(event)) if event == expected));
+
+        let event = from_crossterm(Event::Resize(80, 24));
+        assert!(matches!(event, Some(InputEvent::Resize(80, 24))));
+
+        // Events without a form equivalent are dropped
+        assert!(from_crossterm(key(CrosstermCode::Null, CrosstermModifiers::NONE)).is_none());
+        assert!(from_crossterm(Event::FocusGained).is_none());
+    }
+
+    #[test]
+    fn test_key_modifiers() {
+        let modifiers = KeyModifiers::CONTROL | KeyModifiers::SHIFT;
+        assert!(modifiers.contains(KeyModifiers::CONTROL));
+        assert!(modifiers.contains(KeyModifiers::CONTROL | KeyModifiers::SHIFT));
+        assert!(!modifiers.contains(KeyModifiers::ALT));
+        assert!(!modifiers.is_empty());
+        assert!(KeyModifiers::NONE.is_empty());
+        assert_eq!(KeyModifiers::NONE, KeyModifiers::default());
+    }
+}
@@ -0,0 +1,72 @@
+use std::fmt;
+
+/// A problem with a form's configuration, detected before execution by
+/// [Form::validate_configuration](crate::Form::validate_configuration).
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ConfigIssue {
+    /// The step has no focusable controls, so it can't receive input.
+    NoFocusableControls {
+        /// The step's index.
+        step: usize,
+    },
+    /// An element of the step depends on an evaluation which no step or control provides.
+    UnregisteredDependency {
+        /// The dependent step's index.
+        step: usize,
+    },
+    /// A form-level dependency references a control which doesn't belong to any of the form's
+    /// compound steps.
+    UnknownControl,
+    /// Multiple controls or steps share a name, so only one of their values is retrievable.
+    DuplicateName {
+        /// The shared name.
+        name: String,
+    },
+    /// The step's static text alone exceeds its maximum line length.
+    StaticTextTooLong {
+        /// The step's index.
+        step: usize,
+        /// The static text's total width.
+        width: usize,
+        /// The step's maximum line length.
+        max_line_length: u16,
+    },
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigIssue::NoFocusableControls { step } => write!(
+                f,
+                "step {} has no focusable controls; add an input or remove the step",
+                step
+            ),
+            ConfigIssue::UnregisteredDependency { step } => write!(
+                f,
+                "step {} depends on an evaluation no step or control provides; set the evaluation \
+                 on a control added to the form",
+                step
+            ),
+            ConfigIssue::UnknownControl => write!(
+                f,
+                "a form-level dependency references a control not in any compound step; add the \
+                 control to a step of this form"
+            ),
+            ConfigIssue::DuplicateName { name } => write!(
+                f,
+                "multiple controls or steps are named \"{}\"; give each a unique name",
+                name
+            ),
+            ConfigIssue::StaticTextTooLong {
+                step,
+                width,
+                max_line_length,
+            } => write!(
+                f,
+                "step {}'s static text is {} columns, exceeding its maximum line length of {}; \
+                 shorten the text or raise the limit",
+                step, width, max_line_length
+            ),
+        }
+    }
+}
@@ -0,0 +1,327 @@
+//! Configurable key bindings for form-level behavior such as cancellation.
+
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+use crate::input::{KeyCode, KeyEvent, KeyModifiers};
+
+/// A key combination which may be bound to a form behavior.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct KeyBinding {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    /// Create a binding for the specified key and modifiers.
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self { code, modifiers }
+    }
+
+    /// Create a binding for the specified key without modifiers.
+    pub fn key(code: KeyCode) -> Self {
+        Self::new(code, KeyModifiers::NONE)
+    }
+
+    /// Create a binding for the specified key with the control modifier.
+    pub fn ctrl(code: KeyCode) -> Self {
+        Self::new(code, KeyModifiers::CONTROL)
+    }
+
+    /// This binding's key.
+    pub fn code(&self) -> KeyCode {
+        self.code
+    }
+
+    /// This binding's modifiers.
+    pub fn modifiers(&self) -> KeyModifiers {
+        self.modifiers
+    }
+
+    /// Whether the specified key event matches this binding.
+    pub fn matches(&self, event: &KeyEvent) -> bool {
+        self.code == event.code && self.modifiers == event.modifiers
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    /// Describe this binding for display, such as "Ctrl+N" or "F8".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, label) in [
+            (KeyModifiers::CONTROL, "Ctrl+"),
+            (KeyModifiers::ALT, "Alt+"),
+            (KeyModifiers::SHIFT, "Shift+"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                f.write_str(label)?;
+            }
+        }
+
+        match self.code {
+            KeyCode::Char(' ') => f.write_str("Space"),
+            KeyCode::Char(ch) => write!(f, "{}", ch.to_uppercase()),
+            KeyCode::F(number) => write!(f, "F{}", number),
+            KeyCode::BackTab => f.write_str("Shift+Tab"),
+            KeyCode::PageUp => f.write_str("PgUp"),
+            KeyCode::PageDown => f.write_str("PgDn"),
+            code => write!(f, "{:?}", code),
+        }
+    }
+}
+
+/// A form's key bindings. By default, Ctrl+C cancels the form, Ctrl+H or F1 toggles the focused
+/// control's extended documentation, F8 focuses the next error, Ctrl+N skips an optional step, and
+/// Ctrl+P applies the next preset.
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+///
+/// use tty_form::{Form, input::KeyCode, keybinding::{KeyBinding, KeyBindings}};
+///
+/// // Cancel with a double-Esc rather than Ctrl+C, and with `q` on read-only steps
+/// let mut bindings = KeyBindings::new();
+/// bindings.set_cancel_keys(Vec::new());
+/// bindings.set_double_press_cancel_keys(vec![KeyBinding::key(KeyCode::Esc)]);
+/// bindings.set_double_press_interval(Duration::from_millis(500));
+/// bindings.set_read_only_cancel_keys(vec![KeyBinding::key(KeyCode::Char('q'))]);
+///
+/// let mut form = Form::new();
+/// form.set_key_bindings(bindings);
+/// ```
+#[derive(Debug, Clone)]
+pub struct KeyBindings {
+    cancel: Vec<KeyBinding>,
+    double_press_cancel: Vec<KeyBinding>,
+    double_press_interval: Duration,
+    read_only_cancel: Vec<KeyBinding>,
+    toggle_help: Vec<KeyBinding>,
+    next_error: Vec<KeyBinding>,
+    submit: Vec<KeyBinding>,
+    skip: Vec<KeyBinding>,
+    apply_preset: Vec<KeyBinding>,
+}
+
+impl Default for KeyBindings {
+    /// Create the default key bindings, where Ctrl+C cancels the form, Ctrl+H or F1 toggles the
+    /// focused control's extended documentation, F8 focuses the next error, Ctrl+N skips an
+    /// optional step, and Ctrl+P applies the next preset.
+    fn default() -> Self {
+        Self {
+            cancel: vec![KeyBinding::ctrl(KeyCode::Char('c'))],
+            double_press_cancel: Vec::new(),
+            double_press_interval: Duration::from_millis(500),
+            read_only_cancel: Vec::new(),
+            toggle_help: vec![
+                KeyBinding::ctrl(KeyCode::Char('h')),
+                KeyBinding::key(KeyCode::F(1)),
+            ],
+            next_error: vec![KeyBinding::key(KeyCode::F(8))],
+            submit: Vec::new(),
+            skip: vec![KeyBinding::ctrl(KeyCode::Char('n'))],
+            apply_preset: vec![KeyBinding::ctrl(KeyCode::Char('p'))],
+        }
+    }
+}
+
+impl KeyBindings {
+    /// Create the default key bindings, where Ctrl+C cancels the form, Ctrl+H or F1 toggles the
+    /// focused control's extended documentation, F8 focuses the next error, Ctrl+N skips an
+    /// optional step, and Ctrl+P applies the next preset.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the keys which immediately cancel the form. Keys which aren't bound to cancellation are
+    /// passed through to the active step.
+    pub fn set_cancel_keys(&mut self, keys: Vec<KeyBinding>) {
+        self.cancel = keys;
+    }
+
+    /// The keys which immediately cancel the form.
+    pub fn cancel_keys(&self) -> &[KeyBinding] {
+        &self.cancel
+    }
+
+    /// Set the keys which cancel the form when pressed twice within the double-press interval.
+    /// The first press only arms cancellation, showing "Press … again to cancel", and isn't passed
+    /// to the active step, so an armed key such as Esc no longer closes drawers or retreats.
+    pub fn set_double_press_cancel_keys(&mut self, keys: Vec<KeyBinding>) {
+        self.double_press_cancel = keys;
+    }
+
+    /// The keys which cancel the form when pressed twice within the double-press interval.
+    pub fn double_press_cancel_keys(&self) -> &[KeyBinding] {
+        &self.double_press_cancel
+    }
+
+    /// Set the maximum interval between presses for a double-press.
+    pub fn set_double_press_interval(&mut self, interval: Duration) {
+        self.double_press_interval = interval;
+    }
+
+    /// The maximum interval between presses for a double-press.
+    pub fn double_press_interval(&self) -> Duration {
+        self.double_press_interval
+    }
+
+    /// Set the keys which cancel the form while a read-only step is active.
+    pub fn set_read_only_cancel_keys(&mut self, keys: Vec<KeyBinding>) {
+        self.read_only_cancel = keys;
+    }
+
+    /// The keys which cancel the form while a read-only step is active.
+    pub fn read_only_cancel_keys(&self) -> &[KeyBinding] {
+        &self.read_only_cancel
+    }
+
+    /// Set the keys which toggle the focused control's extended documentation.
+    pub fn set_toggle_help_keys(&mut self, keys: Vec<KeyBinding>) {
+        self.toggle_help = keys;
+    }
+
+    /// The keys which toggle the focused control's extended documentation.
+    pub fn toggle_help_keys(&self) -> &[KeyBinding] {
+        &self.toggle_help
+    }
+
+    /// Whether the specified key event toggles the focused control's extended documentation.
+    pub(crate) fn is_toggle_help(&self, event: &KeyEvent) -> bool {
+        self.toggle_help
+            .iter()
+            .any(|binding| binding.matches(event))
+    }
+
+    /// Set the keys which focus the next step or control with a validation error, cycling through
+    /// the form's errors on repeat presses.
+    pub fn set_next_error_keys(&mut self, keys: Vec<KeyBinding>) {
+        self.next_error = keys;
+    }
+
+    /// The keys which focus the next step or control with a validation error.
+    pub fn next_error_keys(&self) -> &[KeyBinding] {
+        &self.next_error
+    }
+
+    /// Whether the specified key event focuses the next step or control with a validation error.
+    pub(crate) fn is_next_error(&self, event: &KeyEvent) -> bool {
+        self.next_error.iter().any(|binding| binding.matches(event))
+    }
+
+    /// Set the keys which submit the form from any step, such as Ctrl+S, once every step is valid,
+    /// accepting the remaining steps' current values. If any step has errors, the first of them is
+    /// focused instead. Unbound by default.
+    pub fn set_submit_keys(&mut self, keys: Vec<KeyBinding>) {
+        self.submit = keys;
+    }
+
+    /// The keys which submit the form from any step.
+    pub fn submit_keys(&self) -> &[KeyBinding] {
+        &self.submit
+    }
+
+    /// Whether the specified key event submits the form from any step.
+    pub(crate) fn is_submit(&self, event: &KeyEvent) -> bool {
+        self.submit.iter().any(|binding| binding.matches(event))
+    }
+
+    /// Set the keys which skip an optional step, leaving it empty.
+    pub fn set_skip_keys(&mut self, keys: Vec<KeyBinding>) {
+        self.skip = keys;
+    }
+
+    /// The keys which skip an optional step.
+    pub fn skip_keys(&self) -> &[KeyBinding] {
+        &self.skip
+    }
+
+    /// Whether the specified key event skips an optional step.
+    pub(crate) fn is_skip(&self, event: &KeyEvent) -> bool {
+        self.skip.iter().any(|binding| binding.matches(event))
+    }
+
+    /// Set the keys which apply the form's next preset, cycling through its presets with each
+    /// press. See [crate::Form::set_presets].
+    pub fn set_apply_preset_keys(&mut self, keys: Vec<KeyBinding>) {
+        self.apply_preset = keys;
+    }
+
+    /// The keys which apply the form's next preset.
+    pub fn apply_preset_keys(&self) -> &[KeyBinding] {
+        &self.apply_preset
+    }
+
+    /// Whether the specified key event applies the form's next preset.
+    pub(crate) fn is_apply_preset(&self, event: &KeyEvent) -> bool {
+        self.apply_preset
+            .iter()
+            .any(|binding| binding.matches(event))
+    }
+}
+
+/// How a key press affects a form's cancellation.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum CancelPress {
+    /// The press doesn't cancel the form and should be handled normally.
+    Pass,
+    /// The press is the first of a double-press cancellation, awaiting confirmation.
+    Armed,
+    /// The press cancels the form.
+    Cancel,
+}
+
+/// Tracks key presses against a form's bindings to detect cancellation.
+#[derive(Debug, Default)]
+pub(crate) struct CancelTracker {
+    last_press: Option<(KeyBinding, Instant)>,
+}
+
+impl CancelTracker {
+    /// Record the specified key event, returning how it affects the form's cancellation.
+    pub(crate) fn press(
+        &mut self,
+        bindings: &KeyBindings,
+        event: &KeyEvent,
+        step_is_read_only: bool,
+    ) -> CancelPress {
+        if bindings.cancel.iter().any(|binding| binding.matches(event)) {
+            return CancelPress::Cancel;
+        }
+
+        if step_is_read_only
+            && bindings
+                .read_only_cancel
+                .iter()
+                .any(|binding| binding.matches(event))
+        {
+            return CancelPress::Cancel;
+        }
+
+        let binding = match bindings
+            .double_press_cancel
+            .iter()
+            .find(|binding| binding.matches(event))
+        {
+            Some(binding) => *binding,
+            None => {
+                self.last_press = None;
+                return CancelPress::Pass;
+            }
+        };
+
+        let now = Instant::now();
+        if let Some((last_binding, last_time)) = self.last_press.take() {
+            if last_binding == binding
+                && now.duration_since(last_time) <= bindings.double_press_interval
+            {
+                return CancelPress::Cancel;
+            }
+        }
+
+        self.last_press = Some((binding, now));
+
+        CancelPress::Armed
+    }
+}
@@ -3,17 +3,54 @@
 //! Provides simple TTY-based user input form capabilities including multi-step forms and complex input types.
 //!
 
+#[macro_use]
+mod diagnostics;
+
 mod form;
-pub use form::Form;
+pub use form::{FieldChange, Form, FormResult, FormStatus, RejectFeedback, StepTiming};
+
+mod chain;
+pub use chain::FormChain;
+
+mod issue;
+pub use issue::ConfigIssue;
 
+pub mod backend;
+pub mod context;
 pub mod control;
 pub mod dependency;
 pub mod device;
+pub mod history;
+pub mod input;
+pub mod keybinding;
+pub mod navigation;
+pub mod overrides;
+pub mod pattern;
+pub mod postprocess;
+pub mod prefab;
+pub mod preset;
+pub mod prompts;
+pub mod recording;
+pub mod state;
 pub mod step;
+pub mod storage;
 pub mod style;
+pub mod terminal;
 pub mod test;
 pub mod text;
+pub mod validation;
+pub mod value;
+
+#[cfg(feature = "ratatui")]
+pub mod ratatui;
+
+#[cfg(feature = "git")]
+pub mod git;
+
+#[cfg(feature = "signals")]
+pub(crate) mod signal;
 
+pub(crate) mod markdown;
 pub(crate) mod utility;
 
 mod result;
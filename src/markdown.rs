@@ -0,0 +1,269 @@
+//! Lightweight Markdown styling and normalization for text block content.
+
+use tty_interface::Style;
+
+use crate::{
+    style::{
+        markdown_bold_style, markdown_code_style, markdown_heading_style, markdown_marker_style,
+    },
+    text::{Segment, Text},
+};
+
+/// Whether the specified line opens or closes a fenced code block.
+pub(crate) fn is_code_fence(line: &str) -> bool {
+    line.trim_start().starts_with("```")
+}
+
+/// Style a line of Markdown, given whether it falls within a fenced code block. The line's
+/// content is preserved exactly so that cursor positions remain accurate.
+pub(crate) fn style_line(line: &str, in_code_block: bool) -> Segment {
+    if line.is_empty() {
+        return Segment::new();
+    }
+
+    if in_code_block || is_code_fence(line) {
+        return Text::new_styled(line.to_string(), markdown_code_style()).as_segment();
+    }
+
+    if is_heading(line) {
+        return Text::new_styled(line.to_string(), markdown_heading_style()).as_segment();
+    }
+
+    let mut segment = Segment::new();
+
+    let marker_length = list_marker_length(line);
+    if marker_length > 0 {
+        let (marker, _) = line.split_at(marker_length);
+        segment.push(Text::new_styled(
+            marker.to_string(),
+            markdown_marker_style(),
+        ));
+    }
+
+    style_inline(&line[marker_length..], &mut segment);
+
+    segment
+}
+
+/// Normalize list items' bullets to dashes and renumber ordered lists sequentially. Content
+/// within fenced code blocks is left as-is.
+pub(crate) fn normalize_lists(value: &str) -> String {
+    let mut lines = Vec::new();
+    let mut in_code_block = false;
+
+    // The next number for an ordered list at each indentation
+    let mut counters: Vec<(usize, usize)> = Vec::new();
+
+    for line in value.split('\n') {
+        if is_code_fence(line) {
+            in_code_block = !in_code_block;
+            counters.clear();
+            lines.push(line.to_string());
+            continue;
+        }
+
+        if in_code_block {
+            lines.push(line.to_string());
+            continue;
+        }
+
+        let content = line.trim_start();
+        let indentation = line.len() - content.len();
+
+        if let Some(item) = strip_bullet(content) {
+            counters.retain(|(counter_indentation, _)| *counter_indentation < indentation);
+            lines.push(format!("{}- {}", &line[..indentation], item));
+        } else if let Some(item) = strip_number(content) {
+            counters.retain(|(counter_indentation, _)| *counter_indentation <= indentation);
+
+            let number = match counters.last_mut() {
+                Some((counter_indentation, next)) if *counter_indentation == indentation => {
+                    *next += 1;
+                    *next - 1
+                }
+                _ => {
+                    counters.push((indentation, 2));
+                    1
+                }
+            };
+
+            lines.push(format!("{}{}. {}", &line[..indentation], number, item));
+        } else {
+            if content.is_empty() || indentation == 0 {
+                counters.clear();
+            }
+
+            lines.push(line.to_string());
+        }
+    }
+
+    lines.join("\n")
+}
+
+/// The prefix continuing the specified line onto a new line: its indentation and, if it's a list
+/// item, its marker with any number incremented. Returns nothing if the line is a list item without
+/// content, whose marker should be removed rather than continued.
+pub(crate) fn continuation_prefix(line: &str) -> Option<String> {
+    let content = line.trim_start();
+    let indentation = &line[..line.len() - content.len()];
+
+    if let Some(item) = strip_bullet(content) {
+        if item.trim().is_empty() {
+            return None;
+        }
+
+        return Some(format!("{}{}", indentation, &content[..2]));
+    }
+
+    if let Some(item) = strip_number(content) {
+        if item.trim().is_empty() {
+            return None;
+        }
+
+        let digits = content.len() - item.len() - ". ".len();
+        let number: usize = content[..digits].parse().unwrap_or_default();
+        return Some(format!("{}{}. ", indentation, number + 1));
+    }
+
+    Some(indentation.to_string())
+}
+
+/// Whether the specified line is an ATX heading.
+fn is_heading(line: &str) -> bool {
+    let level = line.chars().take_while(|ch| *ch == '#').count();
+    (1..=6).contains(&level) && (line.len() == level || line[level..].starts_with(' '))
+}
+
+/// The byte length of the specified line's list marker, including indentation and the following
+/// space, or zero if it isn't a list item.
+fn list_marker_length(line: &str) -> usize {
+    let content = line.trim_start();
+
+    match strip_bullet(content).or_else(|| strip_number(content)) {
+        Some(item) => line.len() - item.len(),
+        None => 0,
+    }
+}
+
+/// The content of an unordered list item, if the specified content is one.
+fn strip_bullet(content: &str) -> Option<&str> {
+    ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|bullet| content.strip_prefix(bullet))
+}
+
+/// The content of an ordered list item, if the specified content is one.
+fn strip_number(content: &str) -> Option<&str> {
+    let digits = content.chars().take_while(char::is_ascii_digit).count();
+    if digits == 0 {
+        return None;
+    }
+
+    content[digits..].strip_prefix(". ")
+}
+
+/// Append the specified text to a segment, styling bold and inline code spans.
+fn style_inline(mut text: &str, segment: &mut Segment) {
+    while !text.is_empty() {
+        let bold = text
+            .find("**")
+            .map(|index| (index, "**", markdown_bold_style()));
+        let code = text
+            .find('`')
+            .map(|index| (index, "`", markdown_code_style()));
+
+        let span = match (bold, code) {
+            (Some(bold), Some(code)) => Some(if code.0 < bold.0 { code } else { bold }),
+            (bold, code) => bold.or(code),
+        };
+
+        let (start, delimiter, style) = match span {
+            Some(span) => span,
+            None => break,
+        };
+
+        let inner_start = start + delimiter.len();
+        let end = match text[inner_start..].find(delimiter) {
+            Some(inner_length) => inner_start + inner_length + delimiter.len(),
+            None => break,
+        };
+
+        push_text(segment, &text[..start], None);
+        push_text(segment, &text[start..end], Some(style));
+        text = &text[end..];
+    }
+
+    push_text(segment, text, None);
+}
+
+/// Append non-empty text to a segment with optional styling.
+fn push_text(segment: &mut Segment, text: &str, style: Option<Style>) {
+    if text.is_empty() {
+        return;
+    }
+
+    segment.push(match style {
+        Some(style) => Text::new_styled(text.to_string(), style),
+        None => Text::new(text.to_string()),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        style::{markdown_bold_style, markdown_code_style, markdown_marker_style},
+        text::Text,
+    };
+
+    use super::{continuation_prefix, normalize_lists, style_line};
+
+    #[test]
+    fn test_style_line_list_item_with_inline_spans() {
+        assert_eq!(
+            vec![
+                Text::new_styled("- ".to_string(), markdown_marker_style()),
+                Text::new("Fix ".to_string()),
+                Text::new_styled("**crash**".to_string(), markdown_bold_style()),
+                Text::new(" in ".to_string()),
+                Text::new_styled("`parse`".to_string(), markdown_code_style()),
+            ],
+            style_line("- Fix **crash** in `parse`", false)
+        );
+    }
+
+    #[test]
+    fn test_style_line_unterminated_span() {
+        assert_eq!(
+            vec![Text::new("Some **unfinished".to_string())],
+            style_line("Some **unfinished", false)
+        );
+    }
+
+    #[test]
+    fn test_normalize_lists() {
+        let value = "* First\n+ Second\n  3. Nested\n  7. Nested again\n\n5. Restart\n9. Next";
+
+        assert_eq!(
+            "- First\n- Second\n  1. Nested\n  2. Nested again\n\n1. Restart\n2. Next",
+            normalize_lists(value)
+        );
+    }
+
+    #[test]
+    fn test_normalize_lists_ignores_code_blocks() {
+        let value = "```\n* literal\n3. literal\n```";
+
+        assert_eq!(value, normalize_lists(value));
+    }
+
+    #[test]
+    fn test_continuation_prefix() {
+        assert_eq!(Some("  ".to_string()), continuation_prefix("  indented"));
+        assert_eq!(Some("- ".to_string()), continuation_prefix("- item"));
+        assert_eq!(Some("  * ".to_string()), continuation_prefix("  * nested"));
+        assert_eq!(Some("10. ".to_string()), continuation_prefix("9. ninth"));
+        assert_eq!(Some(String::new()), continuation_prefix("plain"));
+        assert_eq!(None, continuation_prefix("- "));
+        assert_eq!(None, continuation_prefix("  3. "));
+    }
+}
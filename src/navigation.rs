@@ -0,0 +1,233 @@
+//! How a form is navigated: whether its steps are revealed one at a time, and how its backward
+//! navigation keys, Esc and Shift+Tab, behave within its steps.
+
+use crate::input::KeyCode;
+
+/// How a form's steps are revealed and moved between.
+///
+/// # Examples
+/// ```
+/// use tty_form::{
+///     Form,
+///     navigation::NavigationMode,
+/// };
+///
+/// let mut form = Form::new();
+/// form.set_navigation_mode(NavigationMode::Batch);
+/// ```
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum NavigationMode {
+    /// Steps are revealed as they're reached, each completed in turn like a wizard.
+    #[default]
+    Wizard,
+    /// Every step is shown from the start and Up or Down moves focus between them freely, unless
+    /// the focused step uses those keys itself. Completing the last step submits the form.
+    Batch,
+}
+
+/// What a backward navigation key does when pressed.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum BackAction {
+    /// Retreat through the step's own elements, such as its controls, fields, or pairs, before
+    /// retreating to the previous step.
+    #[default]
+    Element,
+    /// Retreat directly to the previous step, regardless of the focused element.
+    Step,
+    /// Do nothing.
+    Ignore,
+}
+
+/// How a form's backward navigation keys behave. By default, both Esc and Shift+Tab retreat
+/// through each step's elements before retreating to the previous step. A form's policy may be
+/// overridden by each of its steps.
+///
+/// # Examples
+/// ```
+/// use tty_form::{
+///     Form,
+///     navigation::{BackAction, NavigationPolicy},
+///     step::{Step, TextBlockStep},
+/// };
+///
+/// // Esc always returns to the previous step, while Shift+Tab moves between elements
+/// let mut policy = NavigationPolicy::new();
+/// policy.set_escape(BackAction::Step);
+///
+/// let mut form = Form::new();
+/// form.set_navigation_policy(policy);
+///
+/// // This step ignores Esc entirely
+/// let mut step = TextBlockStep::new("Enter your story:");
+/// step.set_navigation_policy(NavigationPolicy::new().with_escape(BackAction::Ignore));
+/// step.add_to(&mut form);
+/// ```
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct NavigationPolicy {
+    escape: BackAction,
+    back_tab: BackAction,
+}
+
+impl NavigationPolicy {
+    /// Create the default policy, where both keys retreat through a step's elements.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set what Esc does.
+    pub fn set_escape(&mut self, action: BackAction) {
+        self.escape = action;
+    }
+
+    /// This policy with Esc's action replaced.
+    pub fn with_escape(mut self, action: BackAction) -> Self {
+        self.escape = action;
+        self
+    }
+
+    /// What Esc does.
+    pub fn escape(&self) -> BackAction {
+        self.escape
+    }
+
+    /// Set what Shift+Tab does.
+    pub fn set_back_tab(&mut self, action: BackAction) {
+        self.back_tab = action;
+    }
+
+    /// This policy with Shift+Tab's action replaced.
+    pub fn with_back_tab(mut self, action: BackAction) -> Self {
+        self.back_tab = action;
+        self
+    }
+
+    /// What Shift+Tab does.
+    pub fn back_tab(&self) -> BackAction {
+        self.back_tab
+    }
+
+    /// The action for the specified key, if it's a backward navigation key.
+    pub(crate) fn action_for(&self, code: KeyCode) -> Option<BackAction> {
+        match code {
+            KeyCode::Esc => Some(self.escape),
+            KeyCode::BackTab => Some(self.back_tab),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        control::{Control, SelectInput, StaticText, TextInput},
+        input::KeyCode,
+        step::{CompoundStep, Step, TextBlockStep},
+        test::{press, render, type_text},
+        Form,
+    };
+
+    use super::{BackAction, NavigationMode, NavigationPolicy};
+
+    /// A form of two steps, each of two named text inputs, with the second step's policy, if any.
+    fn policy_form(policy: NavigationPolicy, step_policy: Option<NavigationPolicy>) -> Form {
+        let mut form = Form::new();
+        form.set_navigation_policy(policy);
+
+        for (index, names) in [["a", "b"], ["c", "d"]].into_iter().enumerate() {
+            let mut step = CompoundStep::new();
+            for name in names {
+                let mut input = TextInput::new("Enter a value.", false);
+                input.set_name(name);
+                input.add_to(&mut step);
+            }
+
+            if let (1, Some(step_policy)) = (index, step_policy) {
+                step.set_navigation_policy(step_policy);
+            }
+
+            step.add_to(&mut form);
+        }
+
+        // Focus the second step's second input
+        for _ in 0..3 {
+            press(&mut form, KeyCode::Tab);
+        }
+
+        form
+    }
+
+    #[test]
+    fn test_batch_navigation() {
+        let mut form = Form::new();
+        form.set_navigation_mode(NavigationMode::Batch);
+
+        let mut step = CompoundStep::new();
+        StaticText::new("Name: ").add_to(&mut step);
+        TextInput::new("Enter a name.", false).add_to(&mut step);
+        step.add_to(&mut form);
+
+        let mut step = CompoundStep::new();
+        StaticText::new("Type: ").add_to(&mut step);
+        SelectInput::new("Select a type.", vec![("feat", ""), ("fix", "")]).add_to(&mut step);
+        step.add_to(&mut form);
+
+        TextBlockStep::new("Enter a body.").add_to(&mut form);
+
+        // Every step is rendered from the start
+        press(&mut form, KeyCode::Down);
+        assert_eq!(1, form.state().active_step());
+        let terminal = render(&mut form);
+        assert!(terminal.screen_contents().contains("Name: \nType: feat\n"));
+
+        // Arrows used by the focused control don't move between steps
+        press(&mut form, KeyCode::Down);
+        assert_eq!(1, form.state().active_step());
+        press(&mut form, KeyCode::Enter);
+        assert_eq!(2, form.state().active_step());
+
+        // A text block's arrows leave it only from its first or last line
+        type_text(&mut form, "line");
+        press(&mut form, KeyCode::Enter);
+        press(&mut form, KeyCode::Up);
+        assert_eq!(2, form.state().active_step());
+        press(&mut form, KeyCode::Up);
+        assert_eq!(1, form.state().active_step());
+        press(&mut form, KeyCode::Up);
+        assert_eq!(1, form.state().active_step());
+    }
+
+    #[test]
+    fn test_navigation_policy() {
+        // By default, both keys retreat through a step's controls before its previous step
+        let mut form = policy_form(NavigationPolicy::new(), None);
+        press(&mut form, KeyCode::Esc);
+        type_text(&mut form, "x");
+        assert_eq!(1, form.state().active_step());
+        assert_eq!(Some("x"), form.values().get_text("c"));
+        press(&mut form, KeyCode::BackTab);
+        assert_eq!(0, form.state().active_step());
+
+        // The form's policy may retreat directly to the previous step
+        let policy = NavigationPolicy::new().with_escape(BackAction::Step);
+        let mut form = policy_form(policy, None);
+        press(&mut form, KeyCode::Esc);
+        assert_eq!(0, form.state().active_step());
+
+        // Shift+Tab keeps its own action
+        let mut form = policy_form(policy, None);
+        press(&mut form, KeyCode::BackTab);
+        type_text(&mut form, "x");
+        assert_eq!(Some("x"), form.values().get_text("c"));
+
+        // A step's policy overrides the form's
+        let step_policy = NavigationPolicy::new()
+            .with_escape(BackAction::Ignore)
+            .with_back_tab(BackAction::Step);
+        let mut form = policy_form(policy, Some(step_policy));
+        press(&mut form, KeyCode::Esc);
+        type_text(&mut form, "x");
+        assert_eq!(Some("x"), form.values().get_text("d"));
+        press(&mut form, KeyCode::BackTab);
+        assert_eq!(0, form.state().active_step());
+    }
+}
@@ -0,0 +1,251 @@
+//! Values for a form's named controls provided by its embedding command-line interface, such as
+//! `--set commit_scope=ui`, which pre-fill the form before it executes.
+
+use crate::{value::ControlValue, Error, Result};
+
+/// The flag preceding each override in a command line's arguments.
+const SET_FLAG: &str = "--set";
+
+/// Values for a form's named controls, applied as it begins to pre-fill them, optionally locking
+/// them against editing. See [crate::Form::set_overrides].
+///
+/// # Examples
+/// ```
+/// use tty_form::overrides::Overrides;
+///
+/// let args = ["--verbose", "--set", "commit_type=fix", "--set=commit_scope=ui"];
+/// let mut overrides = Overrides::from_args(args)?;
+/// overrides.set_read_only(true);
+///
+/// assert_eq!(2, overrides.len());
+/// assert_eq!(Some("ui"), overrides.get("commit_scope"));
+/// # Ok::<(), tty_form::Error>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Overrides {
+    values: Vec<(String, String)>,
+    read_only: bool,
+}
+
+impl Overrides {
+    /// Create a new, empty set of overrides.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse overrides from `name=value` pairs, such as those collected by the embedding
+    /// application's argument parser.
+    pub fn from_pairs<I, S>(pairs: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut overrides = Self::new();
+        for pair in pairs {
+            let (name, value) = parse_pair(pair.as_ref())?;
+            overrides.set(name, value);
+        }
+
+        Ok(overrides)
+    }
+
+    /// Parse overrides from a command line's `--set name=value` or `--set=name=value` arguments,
+    /// ignoring any others.
+    pub fn from_args<I, S>(args: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut overrides = Self::new();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let arg = arg.as_ref();
+            let pair = if arg == SET_FLAG {
+                args.next()
+                    .map(|pair| pair.as_ref().to_string())
+                    .ok_or_else(|| {
+                        Error::Override(format!("{} requires a name=value pair", SET_FLAG))
+                    })?
+            } else {
+                match arg
+                    .strip_prefix(SET_FLAG)
+                    .and_then(|rest| rest.strip_prefix('='))
+                {
+                    Some(pair) => pair.to_string(),
+                    None => continue,
+                }
+            };
+
+            let (name, value) = parse_pair(&pair)?;
+            overrides.set(name, value);
+        }
+
+        Ok(overrides)
+    }
+
+    /// Set the value overriding the named control, replacing any earlier override of it.
+    pub fn set(&mut self, name: &str, value: &str) {
+        match self.values.iter_mut().find(|(n, _)| n == name) {
+            Some((_, existing)) => *existing = value.to_string(),
+            None => self.values.push((name.to_string(), value.to_string())),
+        }
+    }
+
+    /// The value overriding the named control, if any.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The overridden names and their values, in the order they were first set.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, ControlValue)> {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.as_str(), ControlValue::Text(value.clone())))
+    }
+
+    /// Set whether overridden controls are locked against editing, such that focus passes over
+    /// them and steps left without editable input are skipped. Defaults to false, leaving the
+    /// values editable.
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
+    /// Whether overridden controls are locked against editing.
+    pub fn read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// The number of overridden controls.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Whether no controls are overridden.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+/// Split a `name=value` pair at its first equals sign.
+fn parse_pair(pair: &str) -> Result<(&str, &str)> {
+    match pair.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => Ok((name.trim(), value)),
+        _ => Err(Error::Override(format!(
+            "expected a name=value pair, found \"{}\"",
+            pair
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        control::{Control, SelectInput, TextInput},
+        device::ChannelInputDevice,
+        input::KeyCode,
+        step::{CompoundStep, Step},
+        test::{press, type_text, SizedBackend},
+        Error, Form, FormStatus,
+    };
+
+    use super::Overrides;
+
+    #[test]
+    fn test_command_line_overrides() {
+        let overrides_form = || {
+            let mut form = Form::new();
+
+            let mut step = CompoundStep::new();
+            let mut scope = TextInput::new("Enter a scope.", false);
+            scope.set_name("scope");
+            scope.add_to(&mut step);
+            step.add_to(&mut form);
+
+            let mut step = CompoundStep::new();
+            let mut summary = TextInput::new("Enter a summary.", false);
+            summary.set_name("summary");
+            summary.add_to(&mut step);
+            step.add_to(&mut form);
+
+            form
+        };
+
+        let args = [
+            "commit",
+            "--set",
+            "scope=api",
+            "--message",
+            "--set=scope=ui",
+        ];
+        let mut overrides = Overrides::from_args(args).unwrap();
+        assert_eq!(1, overrides.len());
+        assert_eq!(Some("ui"), overrides.get("scope"));
+
+        // Editable overrides are pre-filled but still focused
+        let mut form = overrides_form();
+        form.set_overrides(overrides.clone()).unwrap();
+        type_text(&mut form, "s");
+        assert_eq!(Some("uis"), form.values().get_text("scope"));
+
+        // Read-only overrides lock their controls, skipping steps left without input
+        overrides.set_read_only(true);
+        let mut form = overrides_form();
+        form.set_overrides(overrides).unwrap();
+        type_text(&mut form, "Fix the build");
+        assert_eq!(FormStatus::Submitted, press(&mut form, KeyCode::Enter));
+
+        let result = form.form_result();
+        assert_eq!("ui\nFix the build", result.text());
+        assert!(result.is_prefilled("scope"));
+        assert!(!result.is_prefilled("summary"));
+
+        let unknown = Overrides::from_pairs(["colour=blue"]).unwrap();
+        assert!(overrides_form().set_overrides(unknown).is_err());
+        assert!(Overrides::from_pairs(["scope"]).is_err());
+        assert!(Overrides::from_args(["--set"]).is_err());
+    }
+
+    #[test]
+    fn test_rejected_overrides() {
+        let overrides_form = |read_only: bool| {
+            let mut form = Form::new();
+
+            let mut step = CompoundStep::new();
+            let mut kind = SelectInput::new("Select a type.", vec![("feat", ""), ("fix", "")]);
+            kind.set_name("type");
+            kind.add_to(&mut step);
+            step.add_to(&mut form);
+
+            let mut step = CompoundStep::new();
+            let mut summary = TextInput::new("Enter a summary.", false);
+            summary.set_name("summary");
+            summary.add_to(&mut step);
+            step.add_to(&mut form);
+
+            let mut overrides = Overrides::from_args(["--set", "type=bogus"]).unwrap();
+            overrides.set_read_only(read_only);
+            form.set_overrides(overrides).unwrap();
+
+            form
+        };
+
+        // A value the control doesn't accept fails the form's execution
+        let (_sender, mut device) = ChannelInputDevice::channel();
+        let mut backend = SizedBackend::new(80, 24);
+        match overrides_form(false).execute(&mut backend, &mut device) {
+            Err(Error::Override(message)) => assert!(message.contains("bogus")),
+            result => panic!("expected an override error, found {:?}", result),
+        }
+
+        // A rejected read-only override still locks its control, skipping its step
+        let mut form = overrides_form(true);
+        press(&mut form, KeyCode::Down);
+        type_text(&mut form, "Fix the build");
+        assert_eq!(Some("feat"), form.values().get_text("type"));
+        assert_eq!(Some("Fix the build"), form.values().get_text("summary"));
+        assert!(!form.form_result().is_prefilled("type"));
+    }
+}
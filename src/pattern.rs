@@ -0,0 +1,212 @@
+//! Text patterns supporting a subset of regular expression syntax, such as to evaluate dependencies.
+
+use crate::{control::CharClass, Error, Result};
+
+/// A pattern supporting a common subset of regular expression syntax: literals, `.`, bracketed
+/// character classes, the `\d`, `\w`, and `\s` shorthands, the `*`, `+`, and `?` quantifiers, and
+/// `^` and `$` anchors. Other syntax, such as alternation, groups, and counted repetition, is
+/// rejected when parsed unless escaped.
+///
+/// # Examples
+/// ```
+/// use tty_form::pattern::Pattern;
+///
+/// let ticket = Pattern::parse(r"^[A-Z]+-\d+$")?;
+/// assert!(ticket.is_match("PROJ-12"));
+///
+/// assert!(Pattern::parse("fix|feat").is_err());
+/// # Ok::<(), tty_form::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    elements: Vec<(Atom, Quantifier)>,
+    start_anchored: bool,
+    end_anchored: bool,
+}
+
+/// A pattern element matching a single character.
+#[derive(Debug, Clone)]
+enum Atom {
+    Any,
+    Literal(char),
+    Class(CharClass),
+    Digit,
+    Word,
+    Space,
+}
+
+/// How many times a pattern element may repeat.
+#[derive(Debug, Clone)]
+enum Quantifier {
+    One,
+    ZeroOrOne,
+    ZeroOrMore,
+    OneOrMore,
+}
+
+impl Pattern {
+    /// Parse the specified pattern, failing if it uses unsupported syntax.
+    pub fn parse(pattern: &str) -> Result<Self> {
+        let unsupported = |syntax: &str| {
+            Err(Error::Pattern(format!(
+                "\"{}\" uses unsupported syntax \"{}\"",
+                pattern, syntax
+            )))
+        };
+
+        let mut chars = pattern.chars().peekable();
+        let start_anchored = chars.next_if_eq(&'^').is_some();
+
+        let mut elements = Vec::new();
+        let mut end_anchored = false;
+        while let Some(ch) = chars.next() {
+            let atom = match ch {
+                '$' if chars.peek().is_none() => {
+                    end_anchored = true;
+                    break;
+                }
+                '.' => Atom::Any,
+                '\\' => match chars.next() {
+                    Some('d') => Atom::Digit,
+                    Some('w') => Atom::Word,
+                    Some('s') => Atom::Space,
+                    Some(escaped) if escaped.is_alphanumeric() => {
+                        return unsupported(&format!("\\{}", escaped))
+                    }
+                    Some(escaped) => Atom::Literal(escaped),
+                    None => return unsupported("\\"),
+                },
+                '[' => {
+                    let mut class = String::new();
+                    while let Some(ch) = chars.next_if(|&ch| ch != ']') {
+                        class.push(ch);
+                        if ch == '\\' {
+                            class.extend(chars.next());
+                        }
+                    }
+
+                    if chars.next().is_none() {
+                        return unsupported("[");
+                    }
+
+                    Atom::Class(CharClass::parse(&class))
+                }
+                '|' | '(' | ')' | '{' | '}' | '^' | '$' | '?' | '*' | '+' => {
+                    return unsupported(&ch.to_string())
+                }
+                ch => Atom::Literal(ch),
+            };
+
+            let quantifier = match chars.next_if(|&ch| matches!(ch, '?' | '*' | '+')) {
+                Some('?') => Quantifier::ZeroOrOne,
+                Some('*') => Quantifier::ZeroOrMore,
+                Some('+') => Quantifier::OneOrMore,
+                _ => Quantifier::One,
+            };
+
+            elements.push((atom, quantifier));
+        }
+
+        Ok(Self {
+            elements,
+            start_anchored,
+            end_anchored,
+        })
+    }
+
+    /// Whether this pattern matches anywhere in the specified text, or at its anchors.
+    pub fn is_match(&self, text: &str) -> bool {
+        let chars: Vec<char> = text.chars().collect();
+        if self.start_anchored {
+            return self.matches_at(&self.elements, &chars);
+        }
+
+        (0..=chars.len()).any(|start| self.matches_at(&self.elements, &chars[start..]))
+    }
+
+    /// Whether the specified elements match from the start of the specified characters.
+    fn matches_at(&self, elements: &[(Atom, Quantifier)], chars: &[char]) -> bool {
+        let Some(((atom, quantifier), rest)) = elements.split_first() else {
+            return !self.end_anchored || chars.is_empty();
+        };
+
+        let (min, max) = match quantifier {
+            Quantifier::One => (1, 1),
+            Quantifier::ZeroOrOne => (0, 1),
+            Quantifier::ZeroOrMore => (0, usize::MAX),
+            Quantifier::OneOrMore => (1, usize::MAX),
+        };
+
+        // Greedily match as many characters as possible, backtracking as needed
+        let available = chars
+            .iter()
+            .take(max)
+            .take_while(|&&ch| atom.matches(ch))
+            .count();
+
+        (min..=available)
+            .rev()
+            .any(|count| self.matches_at(rest, &chars[count..]))
+    }
+}
+
+impl Atom {
+    /// Whether this atom matches the specified character.
+    fn matches(&self, ch: char) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Literal(literal) => ch == *literal,
+            Self::Class(class) => class.contains(ch),
+            Self::Digit => ch.is_ascii_digit(),
+            Self::Word => ch.is_alphanumeric() || ch == '_',
+            Self::Space => ch.is_whitespace(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Error;
+
+    use super::Pattern;
+
+    #[test]
+    fn test_pattern() {
+        let ticket = Pattern::parse(r"^[A-Z]+-\d+$").unwrap();
+        assert!(ticket.is_match("PROJ-123"));
+        assert!(!ticket.is_match("PROJ-"));
+        assert!(!ticket.is_match("see PROJ-123"));
+
+        let unanchored = Pattern::parse(r"fixe?s?.*#\d").unwrap();
+        assert!(unanchored.is_match("this fixes issue #4"));
+        assert!(unanchored.is_match("fix #1"));
+        assert!(!unanchored.is_match("fix issue"));
+
+        let optional = Pattern::parse("^colou?r$").unwrap();
+        assert!(optional.is_match("color") && optional.is_match("colour"));
+        assert!(!optional.is_match("colouur"));
+
+        let escaped = Pattern::parse(r"a\.b\(\)").unwrap();
+        assert!(escaped.is_match("a.b()"));
+        assert!(!escaped.is_match("axb()"));
+    }
+
+    #[test]
+    fn test_unsupported_syntax() {
+        for pattern in [
+            "fix|feat",
+            "(fix)",
+            r"\d{3}",
+            "a^b",
+            "a$b",
+            "*a",
+            "a+*",
+            "[abc",
+            r"\bword",
+            "trailing\\",
+        ] {
+            let result = Pattern::parse(pattern);
+            assert!(matches!(result, Err(Error::Pattern(_))), "{}", pattern);
+        }
+    }
+}
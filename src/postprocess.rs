@@ -0,0 +1,158 @@
+//! Common post-processors for a form's final result, applied in order through
+//! [Form::add_post_processor](crate::Form::add_post_processor).
+//!
+//! # Examples
+//! ```
+//! use tty_form::{Form, postprocess};
+//!
+//! let mut form = Form::new();
+//! form.add_post_processor(postprocess::wrap_lines(72));
+//! form.add_post_processor(postprocess::normalize_trailer_casing);
+//! form.add_post_processor(postprocess::ensure_trailing_newline);
+//! ```
+
+use unicode_width::UnicodeWidthStr;
+
+/// Create a post-processor which wraps lines exceeding the specified width at word boundaries.
+/// Words longer than the width are left intact.
+pub fn wrap_lines(width: usize) -> impl Fn(String) -> String {
+    move |result| {
+        let lines: Vec<String> = result
+            .split('\n')
+            .map(|line| wrap_line(line, width))
+            .collect();
+        lines.join("\n")
+    }
+}
+
+/// Ensure the result ends with a single newline.
+pub fn ensure_trailing_newline(result: String) -> String {
+    format!("{}\n", result.trim_end_matches('\n'))
+}
+
+/// Normalize the casing of the keys in the result's trailing paragraph of `Key: value` trailers,
+/// such that only each key's first letter is capitalized, e.g. `signed-off-by` becomes
+/// `Signed-off-by`. Fully-uppercase keys, such as `BREAKING-CHANGE`, are left as-is. The result is
+/// unchanged if its last paragraph isn't entirely trailers or is its only paragraph.
+pub fn normalize_trailer_casing(result: String) -> String {
+    let trimmed_length = result.trim_end().len();
+    let (content, trailing) = result.split_at(trimmed_length);
+
+    let trailers_start = match content.rfind("\n\n") {
+        Some(index) => index + 2,
+        None => return result,
+    };
+
+    let trailers = &content[trailers_start..];
+    if !trailers.lines().all(|line| trailer_key(line).is_some()) {
+        return result;
+    }
+
+    let normalized: Vec<String> = trailers
+        .lines()
+        .map(|line| {
+            let key = trailer_key(line).unwrap_or_default();
+            format!("{}{}", normalize_key(key), &line[key.len()..])
+        })
+        .collect();
+
+    format!(
+        "{}{}{}",
+        &content[..trailers_start],
+        normalized.join("\n"),
+        trailing
+    )
+}
+
+/// Wrap a single line at word boundaries to the specified width.
+fn wrap_line(line: &str, width: usize) -> String {
+    if line.width() <= width {
+        return line.to_string();
+    }
+
+    let mut wrapped = String::new();
+    let mut current_width = 0;
+    for word in line.split(' ') {
+        let word_width = word.width();
+
+        if current_width > 0 && current_width + 1 + word_width > width {
+            wrapped.push('\n');
+            current_width = 0;
+        } else if current_width > 0 || !wrapped.is_empty() {
+            wrapped.push(' ');
+            current_width += 1;
+        }
+
+        wrapped.push_str(word);
+        current_width += word_width;
+    }
+
+    wrapped
+}
+
+/// The key of the specified trailer line, if it is one.
+pub(crate) fn trailer_key(line: &str) -> Option<&str> {
+    let (key, _) = line.split_once(": ")?;
+
+    let is_key = !key.is_empty()
+        && key.starts_with(|ch: char| ch.is_ascii_alphabetic())
+        && key
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-');
+
+    is_key.then_some(key)
+}
+
+/// Capitalize only the first letter of the specified key, unless it's fully uppercase.
+fn normalize_key(key: &str) -> String {
+    if key.chars().all(|ch| !ch.is_ascii_lowercase()) {
+        return key.to_string();
+    }
+
+    let mut normalized = key[..1].to_ascii_uppercase();
+    normalized.push_str(&key[1..].to_ascii_lowercase());
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ensure_trailing_newline, normalize_trailer_casing, wrap_lines};
+
+    #[test]
+    fn test_wrap_lines() {
+        let wrap = wrap_lines(10);
+
+        assert_eq!(
+            "short\none two\nthree four\nsupercalifragilistic",
+            wrap("short\none two three four\nsupercalifragilistic".to_string())
+        );
+    }
+
+    #[test]
+    fn test_ensure_trailing_newline() {
+        assert_eq!("text\n", ensure_trailing_newline("text".to_string()));
+        assert_eq!("text\n", ensure_trailing_newline("text\n\n".to_string()));
+    }
+
+    #[test]
+    fn test_normalize_trailer_casing() {
+        assert_eq!(
+            "feat: add\n\nbody: text\n\nSigned-off-by: A\nBREAKING-CHANGE: B\n",
+            normalize_trailer_casing(
+                "feat: add\n\nbody: text\n\nsigned-Off-By: A\nBREAKING-CHANGE: B\n".to_string()
+            )
+        );
+
+        // The only paragraph isn't treated as trailers
+        assert_eq!(
+            "feat: add",
+            normalize_trailer_casing("feat: add".to_string())
+        );
+
+        // Nor is a paragraph which isn't entirely trailers
+        assert_eq!(
+            "feat: add\n\nsee: this\nand this",
+            normalize_trailer_casing("feat: add\n\nsee: this\nand this".to_string())
+        );
+    }
+}
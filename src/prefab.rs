@@ -0,0 +1,380 @@
+//! Prebuilt forms for common use cases.
+
+use crate::{
+    backend::RenderBackend,
+    control::{Control, SelectInput, StaticText, TextInput},
+    dependency::{Action, Evaluation},
+    device::InputDevice,
+    step::{CompoundStep, KeyValueStep, Step, TextBlockStep, YesNoStep},
+    value::ValueStore,
+    Error, Form, Result,
+};
+
+const TYPE_NAME: &str = "commit_type";
+const SCOPE_NAME: &str = "commit_scope";
+const DESCRIPTION_NAME: &str = "commit_description";
+const BODY_NAME: &str = "commit_body";
+const TRAILERS_NAME: &str = "commit_trailers";
+const BREAKING_NAME: &str = "commit_breaking_change";
+
+/// The footer token identifying a breaking change's description.
+const BREAKING_CHANGE_TOKEN: &str = "BREAKING CHANGE";
+
+/// Configuration for a [conventional commit](https://www.conventionalcommits.org) form.
+pub struct ConventionalCommitConfig {
+    types: Vec<(String, String)>,
+    max_subject_length: u16,
+    max_body_line_length: u16,
+}
+
+impl Default for ConventionalCommitConfig {
+    /// Create a configuration with the conventional commit types and common length limits.
+    fn default() -> Self {
+        let types = vec![
+            ("feat", "implemented a new feature"),
+            ("fix", "fixed existing behavior"),
+            ("docs", "added or updated documentation"),
+            ("style", "formatting changes without behavioral effect"),
+            ("refactor", "restructured code without behavioral effect"),
+            ("perf", "improved performance"),
+            ("test", "added or updated tests"),
+            ("build", "changed the build system or dependencies"),
+            ("ci", "changed continuous integration configuration"),
+            ("chore", "other non-source changes"),
+            ("revert", "reverted a previous commit"),
+        ];
+
+        Self {
+            types: types
+                .into_iter()
+                .map(|(name, description)| (name.to_string(), description.to_string()))
+                .collect(),
+            max_subject_length: 72,
+            max_body_line_length: 100,
+        }
+    }
+}
+
+impl ConventionalCommitConfig {
+    /// Create a configuration with the conventional commit types and common length limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the selectable commit types and their descriptions.
+    pub fn set_types(&mut self, types: Vec<(&str, &str)>) {
+        self.types = types
+            .into_iter()
+            .map(|(name, description)| (name.to_string(), description.to_string()))
+            .collect();
+    }
+
+    /// Set the maximum length of the commit's subject line, which is enforced before advancing.
+    pub fn set_max_subject_length(&mut self, max_length: u16) {
+        self.max_subject_length = max_length;
+    }
+
+    /// Set the maximum length of the commit body's lines, which are highlighted if exceeded.
+    pub fn set_max_body_line_length(&mut self, max_length: u16) {
+        self.max_body_line_length = max_length;
+    }
+}
+
+/// Create a conventional commit form with the default configuration.
+///
+/// # Examples
+/// ```
+/// use tty_form::prefab::{conventional_commit, ConventionalCommit};
+///
+/// let form = conventional_commit();
+///
+/// let commit = ConventionalCommit::from_values(form.values()).unwrap();
+/// assert_eq!("feat", commit.commit_type);
+/// ```
+pub fn conventional_commit() -> Form {
+    conventional_commit_with(&ConventionalCommitConfig::default())
+}
+
+/// Create a conventional commit form with the specified configuration. Upon completion, its
+/// structured result may be read with [ConventionalCommit::from_values].
+pub fn conventional_commit_with(config: &ConventionalCommitConfig) -> Form {
+    let mut form = Form::new();
+
+    let mut summary = CompoundStep::new();
+    summary.set_max_line_length(config.max_subject_length);
+    summary.set_enforce_max_line_length(true);
+
+    let types = config
+        .types
+        .iter()
+        .map(|(name, description)| (name.as_str(), description.as_str()))
+        .collect();
+    let mut type_input = SelectInput::new("Select the commit type.", types);
+    type_input.set_name(TYPE_NAME);
+    type_input.add_to(&mut summary);
+
+    let opening_paren = summary.add(StaticText::new("("));
+
+    let mut scope_input = TextInput::new("Enter the commit's scope.", true);
+    scope_input.set_name(SCOPE_NAME);
+    let scope_input = summary.add(scope_input);
+
+    let closing_paren = summary.add(StaticText::new(")"));
+    let breaking_bang = summary.add(StaticText::new("!"));
+    StaticText::new(": ").add_to(&mut summary);
+
+    let mut description_input = TextInput::new("Enter the commit's description.", false);
+    description_input.set_name(DESCRIPTION_NAME);
+    description_input.add_to(&mut summary);
+
+    let mut body = TextBlockStep::new("Enter a long-form commit description.");
+    body.set_name(BODY_NAME);
+    body.set_margins(Some(1), Some(1));
+    body.set_max_line_length(config.max_body_line_length);
+
+    let mut trailers = KeyValueStep::new("Enter any key-value trailers, such as tickets.");
+    trailers.set_name(TRAILERS_NAME);
+
+    let mut breaking = YesNoStep::new(
+        "Is this commit a breaking change?",
+        "Enter a description of the breaking change.",
+        BREAKING_CHANGE_TOKEN,
+    );
+    breaking.set_name(BREAKING_NAME);
+
+    // The scope's parentheses are hidden while it's empty
+    let scope_empty = summary
+        .get_mut(&scope_input)
+        .map(|scope_input| scope_input.set_evaluation(Evaluation::IsEmpty));
+    if let Some(scope_empty) = scope_empty {
+        for paren in [&opening_paren, &closing_paren] {
+            if let Some(paren) = summary.get_mut(paren) {
+                paren.set_dependency(scope_empty, Action::Hide);
+            }
+        }
+    }
+
+    let breaking_change = breaking.set_evaluation(Evaluation::NotEqual("No".to_string()));
+    if let Some(breaking_bang) = summary.get_mut(&breaking_bang) {
+        breaking_bang.set_dependency(breaking_change, Action::Show);
+    }
+
+    summary.add_to(&mut form);
+    body.add_to(&mut form);
+    trailers.add_to(&mut form);
+    breaking.add_to(&mut form);
+
+    form
+}
+
+/// The structured result of a conventional commit form.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ConventionalCommit {
+    /// The commit's type, such as `feat` or `fix`.
+    pub commit_type: String,
+    /// The commit's optional scope.
+    pub scope: Option<String>,
+    /// The commit's short description.
+    pub description: String,
+    /// The commit's optional long-form body.
+    pub body: Option<String>,
+    /// Whether this commit is a breaking change.
+    pub is_breaking: bool,
+    /// The breaking change's description, if provided.
+    pub breaking_change: Option<String>,
+    /// The commit's footer trailers, such as ticket references.
+    pub trailers: Vec<(String, String)>,
+}
+
+impl ConventionalCommit {
+    /// Execute a conventional commit form with the specified configuration and return the
+    /// resulting commit.
+    pub fn execute<B: RenderBackend, D: InputDevice>(
+        config: &ConventionalCommitConfig,
+        interface: &mut B,
+        input_device: &mut D,
+    ) -> Result<Self> {
+        let mut form = conventional_commit_with(config);
+        form.run(interface, input_device)?;
+
+        let values = form.values();
+        let required = |name: &str| {
+            values
+                .get_text(name)
+                .ok_or_else(|| Error::MissingValue(name.to_string()))
+        };
+
+        // The breaking change is read from its step, as its value conflates the answer with the
+        // description
+        let breaking = (0..)
+            .map_while(|index| form.step(index))
+            .find_map(|step| step.downcast_ref::<YesNoStep>())
+            .ok_or_else(|| Error::MissingValue(BREAKING_NAME.to_string()))?;
+
+        let non_empty = |name| {
+            values
+                .get_text(name)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+
+        Ok(Self {
+            commit_type: required(TYPE_NAME)?.to_string(),
+            scope: non_empty(SCOPE_NAME),
+            description: required(DESCRIPTION_NAME)?.trim().to_string(),
+            body: non_empty(BODY_NAME),
+            is_breaking: breaking.is_yes(),
+            breaking_change: breaking.description(),
+            trailers: values
+                .get_pairs(TRAILERS_NAME)
+                .ok_or_else(|| Error::MissingValue(TRAILERS_NAME.to_string()))?
+                .to_vec(),
+        })
+    }
+
+    /// Read a commit from the values of a form created by [conventional_commit_with], if the
+    /// values are from such a form. A breaking change described as exactly "Yes" or "No" can't be
+    /// told apart from the answer itself, which [ConventionalCommit::execute] reads from its step.
+    pub fn from_values(values: &ValueStore) -> Option<Self> {
+        let non_empty = |name| {
+            values
+                .get_text(name)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+
+        let breaking = values.get_text(BREAKING_NAME)?;
+        let is_breaking = breaking != "No";
+        let breaking_change = Some(breaking.trim())
+            .filter(|description| is_breaking && *description != "Yes" && !description.is_empty())
+            .map(str::to_string);
+
+        Some(Self {
+            commit_type: values.get_text(TYPE_NAME)?.to_string(),
+            scope: non_empty(SCOPE_NAME),
+            description: values.get_text(DESCRIPTION_NAME)?.trim().to_string(),
+            body: non_empty(BODY_NAME),
+            is_breaking,
+            breaking_change,
+            trailers: values.get_pairs(TRAILERS_NAME)?.to_vec(),
+        })
+    }
+
+    /// This commit's subject line, such as `feat(parser)!: support arrays`.
+    pub fn subject(&self) -> String {
+        let mut subject = self.commit_type.clone();
+
+        if let Some(scope) = &self.scope {
+            subject.push_str(&format!("({})", scope));
+        }
+
+        if self.is_breaking {
+            subject.push('!');
+        }
+
+        subject.push_str(&format!(": {}", self.description));
+
+        subject
+    }
+
+    /// This commit's complete message, with the body and footers separated by blank lines.
+    ///
+    /// # Examples
+    /// ```
+    /// use tty_form::prefab::ConventionalCommit;
+    ///
+    /// let commit = ConventionalCommit {
+    ///     commit_type: "fix".to_string(),
+    ///     scope: Some("parser".to_string()),
+    ///     description: "handle empty input".to_string(),
+    ///     body: None,
+    ///     is_breaking: true,
+    ///     breaking_change: Some("Empty input is now an error.".to_string()),
+    ///     trailers: vec![("Refs".to_string(), "#42".to_string())],
+    /// };
+    ///
+    /// assert_eq!(
+    ///     "fix(parser)!: handle empty input\n\nBREAKING CHANGE: Empty input is now an error.\nRefs: #42",
+    ///     commit.message(),
+    /// );
+    /// ```
+    pub fn message(&self) -> String {
+        let mut message = self.subject();
+
+        if let Some(body) = &self.body {
+            message.push_str("\n\n");
+            message.push_str(body);
+        }
+
+        let mut footers = Vec::new();
+
+        if let Some(breaking_change) = &self.breaking_change {
+            footers.push(format!("{}: {}", BREAKING_CHANGE_TOKEN, breaking_change));
+        }
+
+        for (key, value) in &self.trailers {
+            if value.is_empty() {
+                footers.push(key.to_string());
+            } else {
+                footers.push(format!("{}: {}", key, value));
+            }
+        }
+
+        if !footers.is_empty() {
+            message.push_str("\n\n");
+            message.push_str(&footers.join("\n"));
+        }
+
+        message
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        device::ChannelInputDevice,
+        input::{InputEvent, KeyCode, KeyEvent, KeyModifiers},
+        test::SizedBackend,
+    };
+
+    use super::{ConventionalCommit, ConventionalCommitConfig};
+
+    #[test]
+    fn test_execute_conventional_commit() {
+        let (sender, mut device) = ChannelInputDevice::channel();
+        let send = |code| {
+            let event = InputEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+            sender.send(event).unwrap();
+        };
+
+        // Select the type, skip the scope, and describe the change
+        send(KeyCode::Down);
+        send(KeyCode::Enter);
+        send(KeyCode::Enter);
+        "handle empty input"
+            .chars()
+            .for_each(|ch| send(KeyCode::Char(ch)));
+        send(KeyCode::Enter);
+
+        // Leave the body and trailers empty
+        send(KeyCode::Enter);
+        send(KeyCode::Enter);
+        send(KeyCode::Enter);
+
+        // A breaking change whose description reads like an answer is still breaking
+        send(KeyCode::Down);
+        "No".chars().for_each(|ch| send(KeyCode::Char(ch)));
+        send(KeyCode::Enter);
+
+        let config = ConventionalCommitConfig::default();
+        let mut backend = SizedBackend::new(80, 24);
+        let commit = ConventionalCommit::execute(&config, &mut backend, &mut device).unwrap();
+
+        assert_eq!("fix!: handle empty input", commit.subject());
+        assert!(commit.is_breaking);
+        assert_eq!(Some("No".to_string()), commit.breaking_change);
+        assert!(commit.trailers.is_empty());
+    }
+}
@@ -0,0 +1,319 @@
+//! Named sets of answers which fill a form's controls, such as for repetitive entries whose fields
+//! are mostly constant, loaded from a per-user configuration file.
+
+use std::{
+    env, fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    value::{ControlValue, ValueStore},
+    Error, Result,
+};
+
+/// The name of the file presets are loaded from within an application's configuration directory.
+const PRESETS_FILE_NAME: &str = "form-presets.toml";
+
+/// A form's presets, each a named set of values for its named controls. Presets are written in a
+/// subset of TOML: each preset is a table whose keys name controls, with string, boolean, or
+/// numeric values.
+///
+/// # Examples
+/// ```
+/// use tty_form::preset::Presets;
+///
+/// let presets = Presets::parse(r#"
+///     [deps]
+///     commit_type = "chore"
+///     commit_scope = "deps"
+///     breaking = false
+/// "#)?;
+///
+/// let deps = presets.get("deps").unwrap();
+/// assert_eq!(Some("chore"), deps.values().get_text("commit_type"));
+/// assert_eq!(Some(false), deps.values().get_bool("breaking"));
+/// # Ok::<(), tty_form::Error>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Presets {
+    presets: Vec<Preset>,
+}
+
+impl Presets {
+    /// Create a new, empty set of presets.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse presets from the specified text.
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut presets = Self::new();
+
+        for (line_index, line) in text.lines().enumerate() {
+            let error =
+                |message: &str| Error::Preset(format!("line {}: {}", line_index + 1, message));
+
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(header) = line.strip_prefix('[') {
+                let name = header
+                    .strip_suffix(']')
+                    .ok_or_else(|| error("unterminated table header"))?;
+                let name = parse_key(name.trim()).ok_or_else(|| error("invalid table name"))?;
+
+                presets.add(Preset::new(&name));
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| error("expected a key and value"))?;
+            let key = parse_key(key.trim()).ok_or_else(|| error("invalid key"))?;
+            let value = parse_value(value.trim()).ok_or_else(|| error("invalid value"))?;
+
+            let preset = presets
+                .presets
+                .last_mut()
+                .ok_or_else(|| error("expected a [preset] table before its values"))?;
+            preset.set(&key, value);
+        }
+
+        Ok(presets)
+    }
+
+    /// Load presets from the specified file, or no presets if it doesn't exist.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Self::new()),
+            Err(err) => Err(Error::Output(err)),
+        }
+    }
+
+    /// Load the current user's presets for the specified application from its configuration
+    /// directory, or no presets if they haven't written any. See [Presets::user_path].
+    pub fn load_user(application: &str) -> Result<Self> {
+        match Self::user_path(application) {
+            Some(path) => Self::load(path),
+            None => Ok(Self::new()),
+        }
+    }
+
+    /// The path of the current user's presets file for the specified application:
+    /// `form-presets.toml` in `$XDG_CONFIG_HOME/<application>`, or in `~/.config/<application>` if
+    /// that isn't set. Nothing is returned if the user's home directory is unknown.
+    pub fn user_path(application: &str) -> Option<PathBuf> {
+        let config_directory = match env::var_os("XDG_CONFIG_HOME").filter(|dir| !dir.is_empty()) {
+            Some(directory) => PathBuf::from(directory),
+            None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+        };
+
+        Some(config_directory.join(application).join(PRESETS_FILE_NAME))
+    }
+
+    /// Add a preset, replacing any existing preset of the same name.
+    pub fn add(&mut self, preset: Preset) {
+        match self.presets.iter_mut().find(|p| p.name == preset.name) {
+            Some(existing) => *existing = preset,
+            None => self.presets.push(preset),
+        }
+    }
+
+    /// The preset with the specified name, if any.
+    pub fn get(&self, name: &str) -> Option<&Preset> {
+        self.presets.iter().find(|preset| preset.name == name)
+    }
+
+    /// The presets in the order they were added.
+    pub fn iter(&self) -> impl Iterator<Item = &Preset> {
+        self.presets.iter()
+    }
+
+    /// The number of presets.
+    pub fn len(&self) -> usize {
+        self.presets.len()
+    }
+
+    /// Whether there are no presets.
+    pub fn is_empty(&self) -> bool {
+        self.presets.is_empty()
+    }
+}
+
+/// A named set of values for a form's named controls.
+#[derive(Debug, Clone)]
+pub struct Preset {
+    name: String,
+    values: ValueStore,
+}
+
+impl Preset {
+    /// Create a new preset with the specified name and no values.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            values: ValueStore::new(),
+        }
+    }
+
+    /// This preset's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The values this preset fills its form's named controls with.
+    pub fn values(&self) -> &ValueStore {
+        &self.values
+    }
+
+    /// Set the value this preset fills the named control with.
+    pub fn set(&mut self, name: &str, value: ControlValue) {
+        self.values.set(name, value);
+    }
+}
+
+/// Remove a line's trailing comment, if it has one outside of a string.
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut escaped = false;
+
+    for (index, ch) in line.char_indices() {
+        match (quote, ch) {
+            (Some('"'), '\\') if !escaped => {
+                escaped = true;
+                continue;
+            }
+            (Some(open), ch) if ch == open && !escaped => quote = None,
+            (None, '"' | '\'') => quote = Some(ch),
+            (None, '#') => return &line[..index],
+            _ => {}
+        }
+
+        escaped = false;
+    }
+
+    line
+}
+
+/// Parse a bare or quoted key.
+fn parse_key(key: &str) -> Option<String> {
+    if key.starts_with('"') || key.starts_with('\'') {
+        return parse_string(key);
+    }
+
+    let is_bare = !key.is_empty()
+        && key
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-');
+
+    is_bare.then(|| key.to_string())
+}
+
+/// Parse a string, boolean, or number value.
+fn parse_value(value: &str) -> Option<ControlValue> {
+    match value {
+        "true" => Some(ControlValue::Bool(true)),
+        "false" => Some(ControlValue::Bool(false)),
+        _ if value.starts_with('"') || value.starts_with('\'') => {
+            parse_string(value).map(ControlValue::Text)
+        }
+        _ if value.parse::<f64>().is_ok() => Some(ControlValue::Text(value.to_string())),
+        _ => None,
+    }
+}
+
+/// Parse a basic string, with escapes, or a literal string, without.
+fn parse_string(value: &str) -> Option<String> {
+    if let Some(literal) = value.strip_prefix('\'') {
+        let literal = literal.strip_suffix('\'')?;
+        return (!literal.contains('\'')).then(|| literal.to_string());
+    }
+
+    let basic = value.strip_prefix('"')?.strip_suffix('"')?;
+
+    let mut parsed = String::new();
+    let mut chars = basic.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' => return None,
+            '\\' => match chars.next()? {
+                'n' => parsed.push('\n'),
+                't' => parsed.push('\t'),
+                'r' => parsed.push('\r'),
+                '"' => parsed.push('"'),
+                '\\' => parsed.push('\\'),
+                'u' => {
+                    let code: String = chars.by_ref().take(4).collect();
+                    parsed.push(char::from_u32(u32::from_str_radix(&code, 16).ok()?)?);
+                }
+                _ => return None,
+            },
+            ch => parsed.push(ch),
+        }
+    }
+
+    Some(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        control::{Control, StaticText, TextInput},
+        step::{CompoundStep, Step},
+        test::{press_ctrl, render},
+        Form, FormStatus,
+    };
+
+    use super::Presets;
+
+    #[test]
+    fn test_apply_presets() {
+        let presets = Presets::parse(
+            r#"
+            # Routine entries
+            [deps]
+            scope = "deps" # trailing comment
+            summary = 'Bump "crossterm"'
+
+            ["docs only"]
+            scope = "docs"
+            summary = "Fix a typo\tin #README"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(2, presets.len());
+
+        let mut form = Form::new();
+        let mut step = CompoundStep::new();
+        let mut scope = TextInput::new("Enter a scope.", false);
+        scope.set_name("scope");
+        scope.add_to(&mut step);
+        StaticText::new(": ").add_to(&mut step);
+        let mut summary = TextInput::new("Enter a summary.", false);
+        summary.set_name("summary");
+        summary.add_to(&mut step);
+        step.add_to(&mut form);
+        form.set_presets(presets);
+
+        // Each press applies the next preset, cycling back to the first
+        assert_eq!(FormStatus::Active, press_ctrl(&mut form, 'p'));
+        assert_eq!("deps: Bump \"crossterm\"", form.result());
+
+        assert_eq!(FormStatus::Active, press_ctrl(&mut form, 'p'));
+        assert_eq!("docs: Fix a typo\tin #README", form.result());
+
+        let terminal = render(&mut form);
+        terminal.expect_screen_contains("Applied preset \"docs only\" (2/2)");
+
+        assert_eq!(FormStatus::Active, press_ctrl(&mut form, 'p'));
+        assert_eq!(Some("deps"), form.values().get_text("scope"));
+
+        assert!(!form.apply_preset("missing"));
+        assert!(Presets::parse("scope = \"deps\"").is_err());
+        assert!(Presets::parse("[deps]\nscope = deps").is_err());
+    }
+}
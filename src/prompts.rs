@@ -0,0 +1,153 @@
+//! One-shot prompts for simple input, each executing a minimal single-step form.
+//!
+//! # Examples
+//! ```no_run
+//! use tty_form::prompts;
+//!
+//! let name = prompts::input("Name:")?;
+//! if prompts::confirm("Proceed?")? {
+//!     let food = prompts::select("Pick:", vec![("Pizza", "A slice."), ("Fries", "A side.")])?;
+//! }
+//! # Ok::<(), tty_form::Error>(())
+//! ```
+
+use std::io::stdout;
+
+use tty_interface::{pos, Interface, Position};
+
+use crate::{
+    backend::{RenderBackend, TerminalSizeBackend},
+    control::{Control, SelectInput, StaticText, TextInput},
+    device::{InputDevice, StdinDevice},
+    step::{CompoundStep, Step},
+    terminal, Error, Form, Result,
+};
+
+/// The name of each prompt's input control.
+const VALUE_NAME: &str = "value";
+
+/// Ask a yes-or-no question in the terminal, returning whether it was confirmed.
+pub fn confirm(prompt: &str) -> Result<bool> {
+    with_terminal(|interface, input_device| confirm_with(prompt, interface, input_device))
+}
+
+/// Ask a yes-or-no question through the specified interface and input device, returning whether
+/// it was confirmed.
+///
+/// # Examples
+/// ```
+/// # use tty_interface::{Interface, test::VirtualDevice};
+/// # use tty_form::{Error, test::VirtualInputDevice};
+/// # let mut device = VirtualDevice::new();
+/// # let mut interface = Interface::new_relative(&mut device)?;
+/// # let mut stdin = VirtualInputDevice;
+/// use tty_form::prompts;
+///
+/// let proceed = prompts::confirm_with("Proceed?", &mut interface, &mut stdin)?;
+/// # Ok::<(), Error>(())
+/// ```
+pub fn confirm_with<B: RenderBackend, D: InputDevice>(
+    prompt: &str,
+    interface: &mut B,
+    input_device: &mut D,
+) -> Result<bool> {
+    Ok(execute_prompt(prompt, confirm_input(), interface, input_device, false)? == "Yes")
+}
+
+/// Ask a yes-or-no question, clearing the prompt from the interface once it's answered.
+pub(crate) fn confirm_transient<B: RenderBackend, D: InputDevice>(
+    prompt: &str,
+    interface: &mut B,
+    input_device: &mut D,
+) -> Result<bool> {
+    Ok(execute_prompt(prompt, confirm_input(), interface, input_device, true)? == "Yes")
+}
+
+/// Ask for a line of text in the terminal.
+pub fn input(prompt: &str) -> Result<String> {
+    with_terminal(|interface, input_device| input_with(prompt, interface, input_device))
+}
+
+/// Ask for a line of text through the specified interface and input device.
+pub fn input_with<B: RenderBackend, D: InputDevice>(
+    prompt: &str,
+    interface: &mut B,
+    input_device: &mut D,
+) -> Result<String> {
+    let mut input = TextInput::new(prompt, false);
+    input.set_name(VALUE_NAME);
+
+    execute_prompt(prompt, input, interface, input_device, false)
+}
+
+/// Ask for one of several options in the terminal, returning the selected option's value.
+pub fn select(prompt: &str, options: Vec<(&str, &str)>) -> Result<String> {
+    with_terminal(|interface, input_device| select_with(prompt, options, interface, input_device))
+}
+
+/// Ask for one of several options through the specified interface and input device, returning the
+/// selected option's value.
+pub fn select_with<B: RenderBackend, D: InputDevice>(
+    prompt: &str,
+    options: Vec<(&str, &str)>,
+    interface: &mut B,
+    input_device: &mut D,
+) -> Result<String> {
+    let mut input = SelectInput::new(prompt, options);
+    input.set_name(VALUE_NAME);
+
+    execute_prompt(prompt, input, interface, input_device, false)
+}
+
+/// Create the yes-or-no input for a confirmation prompt.
+fn confirm_input() -> SelectInput {
+    let options = vec![("Yes", "Confirm."), ("No", "Decline.")];
+    let mut input = SelectInput::new("Select yes or no.", options);
+    input.set_name(VALUE_NAME);
+    input
+}
+
+/// Execute a single-step form of the prompt followed by the specified input, returning the
+/// input's final value. The input must be named [VALUE_NAME]. A transient prompt is cleared from
+/// the interface once answered.
+fn execute_prompt<C: Control, B: RenderBackend, D: InputDevice>(
+    prompt: &str,
+    input: C,
+    interface: &mut B,
+    input_device: &mut D,
+    transient: bool,
+) -> Result<String> {
+    let mut step = CompoundStep::new();
+    StaticText::new(&format!("{} ", prompt)).add_to(&mut step);
+    input.add_to(&mut step);
+
+    let mut form = Form::new();
+    step.add_to(&mut form);
+
+    form.run(interface, input_device)?;
+
+    if transient {
+        form.clear_region(interface, pos!(0, 0));
+        interface.apply()?;
+    }
+
+    let value = form.values().get_text(VALUE_NAME).unwrap_or_default();
+    Ok(value.to_string())
+}
+
+/// Run a prompt in a relative interface on the standard output, restoring it afterward. Fails
+/// before rendering if the standard input and output aren't a supported terminal.
+fn with_terminal<T>(
+    prompt: impl FnOnce(&mut TerminalSizeBackend<Interface<'_>>, &mut StdinDevice) -> Result<T>,
+) -> Result<T> {
+    terminal::check(None)?;
+    let size = crossterm::terminal::size().map_err(Error::TerminalSize)?;
+
+    let mut stdout = stdout();
+    let mut backend = TerminalSizeBackend::new(Interface::new_relative(&mut stdout)?, size);
+
+    let result = prompt(&mut backend, &mut StdinDevice);
+    backend.inner_mut().exit()?;
+
+    result
+}
@@ -0,0 +1,395 @@
+//! An adapter for embedding forms within [ratatui](https://ratatui.rs) applications. Enabled by the
+//! `ratatui` feature.
+//!
+//! Forms are rendered directly into a ratatui buffer through a [RenderBackend], and input events
+//! from ratatui's crossterm re-export are converted and forwarded to the form.
+
+use ::ratatui::{
+    buffer::Buffer,
+    crossterm::event as ratatui_event,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::Widget,
+    Frame,
+};
+use tty_interface::{pos, Position};
+
+use crate::{
+    backend::RenderBackend,
+    input::{InputEvent, KeyCode, KeyEvent, KeyModifiers},
+    Form, FormStatus, Result,
+};
+
+/// A widget rendering a form into a ratatui buffer.
+///
+/// # Examples
+/// ```
+/// use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+///
+/// use tty_form::{
+///     Form,
+///     ratatui::FormWidget,
+///     step::{Step, TextBlockStep},
+/// };
+///
+/// let mut form = Form::new();
+/// TextBlockStep::new("Enter your story:").add_to(&mut form);
+///
+/// let area = Rect::new(0, 0, 40, 10);
+/// let mut buffer = Buffer::empty(area);
+/// FormWidget::new(&mut form).render(area, &mut buffer);
+/// ```
+pub struct FormWidget<'a> {
+    form: &'a mut Form,
+    is_focused: bool,
+    cursor: Option<&'a mut Option<(u16, u16)>>,
+}
+
+impl<'a> FormWidget<'a> {
+    /// Create a new, focused widget for the specified form.
+    pub fn new(form: &'a mut Form) -> Self {
+        Self {
+            form,
+            is_focused: true,
+            cursor: None,
+        }
+    }
+
+    /// Set whether the form is focused and should highlight its active step.
+    pub fn focused(mut self, is_focused: bool) -> Self {
+        self.is_focused = is_focused;
+        self
+    }
+
+    /// Capture the form's absolute cursor position, if visible, into the specified location when
+    /// rendered.
+    pub fn cursor(mut self, cursor: &'a mut Option<(u16, u16)>) -> Self {
+        self.cursor = Some(cursor);
+        self
+    }
+}
+
+impl Widget for FormWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut backend = BufferBackend::new(buf, area);
+        self.form
+            .render_at(&mut backend, pos!(0, 0), self.is_focused);
+
+        if let Some(cursor) = self.cursor {
+            *cursor = backend.cursor;
+        }
+    }
+}
+
+/// A rendering backend drawing into a region of a ratatui buffer. Positions are relative to the
+/// region's top-left corner and content outside of the region is clipped.
+pub struct BufferBackend<'a> {
+    buffer: &'a mut Buffer,
+    area: Rect,
+    cursor: Option<(u16, u16)>,
+}
+
+impl<'a> BufferBackend<'a> {
+    /// Create a new backend drawing into the specified region of a buffer.
+    pub fn new(buffer: &'a mut Buffer, area: Rect) -> Self {
+        Self {
+            buffer,
+            area,
+            cursor: None,
+        }
+    }
+
+    /// The last-set cursor position, in absolute buffer coordinates, if visible.
+    pub fn cursor(&self) -> Option<(u16, u16)> {
+        self.cursor
+    }
+
+    /// Write the specified text into the buffer, clipping it to this backend's region.
+    fn write(&mut self, position: Position, text: &str, style: Style) {
+        if position.x() >= self.area.width || position.y() >= self.area.height {
+            return;
+        }
+
+        let max_width = (self.area.width - position.x()) as usize;
+        self.buffer.set_stringn(
+            self.area.x + position.x(),
+            self.area.y + position.y(),
+            text,
+            max_width,
+            style,
+        );
+    }
+}
+
+impl RenderBackend for BufferBackend<'_> {
+    fn set(&mut self, position: Position, text: &str) {
+        self.write(position, text, Style::default());
+    }
+
+    fn set_styled(&mut self, position: Position, text: &str, style: tty_interface::Style) {
+        self.write(position, text, convert_style(style));
+    }
+
+    fn clear_line(&mut self, line: u16) {
+        self.clear_rest_of_line(pos!(0, line));
+    }
+
+    fn clear_rest_of_line(&mut self, from: Position) {
+        let width = self.area.width.saturating_sub(from.x());
+        self.write(from, &" ".repeat(width.into()), Style::default());
+    }
+
+    fn set_cursor(&mut self, position: Option<Position>) {
+        self.cursor = position
+            .filter(|position| position.x() < self.area.width && position.y() < self.area.height)
+            .map(|position| (self.area.x + position.x(), self.area.y + position.y()));
+    }
+
+    fn apply(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn width(&self) -> Option<u16> {
+        Some(self.area.width)
+    }
+
+    fn height(&self) -> Option<u16> {
+        Some(self.area.height)
+    }
+}
+
+/// Render the specified form into a frame's area, positioning the frame's cursor if focused.
+pub fn render_form(frame: &mut Frame, form: &mut Form, area: Rect, is_focused: bool) {
+    let mut cursor = None;
+    frame.render_widget(
+        FormWidget::new(form)
+            .focused(is_focused)
+            .cursor(&mut cursor),
+        area,
+    );
+
+    if let Some(position) = cursor.filter(|_| is_focused) {
+        frame.set_cursor_position(position);
+    }
+}
+
+/// Forward a ratatui input event to the specified form, returning the form's resulting status.
+/// Events without an equivalent form input, including key releases, are ignored.
+pub fn forward_event(form: &mut Form, event: &ratatui_event::Event) -> FormStatus {
+    match convert_event(event) {
+        Some(event) => form.update(event),
+        None => FormStatus::Active,
+    }
+}
+
+/// Convert a ratatui input event to the equivalent form input event, if one exists.
+pub fn convert_event(event: &ratatui_event::Event) -> Option<InputEvent> {
+    let key_event = match event {
+        ratatui_event::Event::Key(key_event) => key_event,
+        _ => return None,
+    };
+
+    if key_event.kind == ratatui_event::KeyEventKind::Release {
+        return None;
+    }
+
+    let code = match key_event.code {
+        ratatui_event::KeyCode::Backspace => KeyCode::Backspace,
+        ratatui_event::KeyCode::Enter => KeyCode::Enter,
+        ratatui_event::KeyCode::Left => KeyCode::Left,
+        ratatui_event::KeyCode::Right => KeyCode::Right,
+        ratatui_event::KeyCode::Up => KeyCode::Up,
+        ratatui_event::KeyCode::Down => KeyCode::Down,
+        ratatui_event::KeyCode::Home => KeyCode::Home,
+        ratatui_event::KeyCode::End => KeyCode::End,
+        ratatui_event::KeyCode::PageUp => KeyCode::PageUp,
+        ratatui_event::KeyCode::PageDown => KeyCode::PageDown,
+        ratatui_event::KeyCode::Tab => KeyCode::Tab,
+        ratatui_event::KeyCode::BackTab => KeyCode::BackTab,
+        ratatui_event::KeyCode::Delete => KeyCode::Delete,
+        ratatui_event::KeyCode::Insert => KeyCode::Insert,
+        ratatui_event::KeyCode::F(number) => KeyCode::F(number),
+        ratatui_event::KeyCode::Char(ch) => KeyCode::Char(ch),
+        ratatui_event::KeyCode::Esc => KeyCode::Esc,
+        _ => return None,
+    };
+
+    let mut modifiers = KeyModifiers::NONE;
+    for (ratatui_modifier, modifier) in [
+        (ratatui_event::KeyModifiers::SHIFT, KeyModifiers::SHIFT),
+        (ratatui_event::KeyModifiers::CONTROL, KeyModifiers::CONTROL),
+        (ratatui_event::KeyModifiers::ALT, KeyModifiers::ALT),
+    ] {
+        if key_event.modifiers.contains(ratatui_modifier) {
+            modifiers = modifiers | modifier;
+        }
+    }
+
+    Some(InputEvent::Key(KeyEvent::new(code, modifiers)))
+}
+
+/// Convert a form style to the equivalent ratatui style.
+fn convert_style(style: tty_interface::Style) -> Style {
+    let mut converted = Style::default();
+
+    if let Some(color) = style.foreground() {
+        converted = converted.fg(convert_color(color));
+    }
+
+    if let Some(color) = style.background() {
+        converted = converted.bg(convert_color(color));
+    }
+
+    if style.is_bold() {
+        converted = converted.add_modifier(Modifier::BOLD);
+    }
+
+    if style.is_italic() {
+        converted = converted.add_modifier(Modifier::ITALIC);
+    }
+
+    if style.is_underlined() {
+        converted = converted.add_modifier(Modifier::UNDERLINED);
+    }
+
+    converted
+}
+
+/// Convert a form color to the equivalent ratatui color.
+fn convert_color(color: tty_interface::Color) -> Color {
+    match color {
+        tty_interface::Color::Black => Color::Black,
+        tty_interface::Color::DarkGrey => Color::DarkGray,
+        tty_interface::Color::Red => Color::LightRed,
+        tty_interface::Color::DarkRed => Color::Red,
+        tty_interface::Color::Green => Color::LightGreen,
+        tty_interface::Color::DarkGreen => Color::Green,
+        tty_interface::Color::Yellow => Color::LightYellow,
+        tty_interface::Color::DarkYellow => Color::Yellow,
+        tty_interface::Color::Blue => Color::LightBlue,
+        tty_interface::Color::DarkBlue => Color::Blue,
+        tty_interface::Color::Magenta => Color::LightMagenta,
+        tty_interface::Color::DarkMagenta => Color::Magenta,
+        tty_interface::Color::Cyan => Color::LightCyan,
+        tty_interface::Color::DarkCyan => Color::Cyan,
+        tty_interface::Color::White => Color::White,
+        tty_interface::Color::Grey => Color::Gray,
+        tty_interface::Color::Reset => Color::Reset,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::ratatui::{
+        buffer::Buffer,
+        crossterm::event as ratatui_event,
+        layout::Rect,
+        style::{Color, Modifier, Style},
+    };
+    use tty_interface::{pos, Position};
+
+    use crate::{
+        backend::RenderBackend,
+        input::{InputEvent, KeyCode, KeyEvent, KeyModifiers},
+    };
+
+    use super::{convert_color, convert_event, convert_style, BufferBackend};
+
+    /// The text of the specified row of a buffer.
+    fn row(buffer: &Buffer, y: u16) -> String {
+        (0..buffer.area.width)
+            .map(|x| buffer[(x, y)].symbol())
+            .collect()
+    }
+
+    #[test]
+    fn test_buffer_backend() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 10, 4));
+        let mut backend = BufferBackend::new(&mut buffer, Rect::new(2, 1, 5, 2));
+        assert_eq!((Some(5), Some(2)), (backend.width(), backend.height()));
+
+        // Text is offset into the region and clipped at its edges
+        backend.set(pos!(0, 0), "Hello, world");
+        backend.set(pos!(3, 1), "abcdef");
+        backend.set(pos!(5, 0), "x");
+        backend.set(pos!(0, 2), "y");
+
+        // The cursor is offset likewise, and hidden outside the region
+        backend.set_cursor(Some(pos!(4, 1)));
+        assert_eq!(Some((6, 2)), backend.cursor());
+        for position in [pos!(5, 1), pos!(0, 2)] {
+            backend.set_cursor(Some(position));
+            assert_eq!(None, backend.cursor());
+        }
+        backend.set_cursor(None);
+        assert_eq!(None, backend.cursor());
+
+        assert_eq!("          ", row(&buffer, 0));
+        assert_eq!("  Hello   ", row(&buffer, 1));
+        assert_eq!("     ab   ", row(&buffer, 2));
+        assert_eq!("          ", row(&buffer, 3));
+
+        // Clearing is clipped to the region
+        let mut backend = BufferBackend::new(&mut buffer, Rect::new(2, 1, 5, 2));
+        backend.clear_rest_of_line(pos!(1, 0));
+        backend.clear_line(1);
+        assert_eq!("  H       ", row(&buffer, 1));
+        assert_eq!("          ", row(&buffer, 2));
+    }
+
+    #[test]
+    fn test_convert_event() {
+        let event = |kind| {
+            ratatui_event::Event::Key(ratatui_event::KeyEvent::new_with_kind(
+                ratatui_event::KeyCode::Char('a'),
+                ratatui_event::KeyModifiers::CONTROL | ratatui_event::KeyModifiers::SHIFT,
+                kind,
+            ))
+        };
+
+        let expected = KeyEvent::new(
+            KeyCode::Char('a'),
+            KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+        );
+        for kind in [
+            ratatui_event::KeyEventKind::Press,
+            ratatui_event::KeyEventKind::Repeat,
+        ] {
+            let converted = convert_event(&event(kind));
+            assert!(matches!(converted, Some(InputEvent::Key(key)) if key == expected));
+        }
+
+        // Releases and events without a form equivalent are dropped
+        assert!(convert_event(&event(ratatui_event::KeyEventKind::Release)).is_none());
+        assert!(convert_event(&ratatui_event::Event::FocusGained).is_none());
+    }
+
+    #[test]
+    fn test_convert_style() {
+        let style = tty_interface::Style::new()
+            .set_foreground(tty_interface::Color::Red)
+            .set_background(tty_interface::Color::DarkBlue)
+            .set_bold(true)
+            .set_underline(true);
+
+        let expected = Style::new()
+            .fg(Color::LightRed)
+            .bg(Color::Blue)
+            .add_modifier(Modifier::BOLD)
+            .add_modifier(Modifier::UNDERLINED);
+        assert_eq!(expected, convert_style(style));
+        assert_eq!(Style::default(), convert_style(tty_interface::Style::new()));
+
+        // Terminal colors' dark variants are ratatui's base colors
+        let colors = [
+            (tty_interface::Color::DarkGrey, Color::DarkGray),
+            (tty_interface::Color::Grey, Color::Gray),
+            (tty_interface::Color::Green, Color::LightGreen),
+            (tty_interface::Color::DarkGreen, Color::Green),
+            (tty_interface::Color::Reset, Color::Reset),
+        ];
+        for (color, expected) in colors {
+            assert_eq!(expected, convert_color(color));
+        }
+    }
+}
@@ -0,0 +1,259 @@
+//! Capture of a form's rendered output and timing for replay, such as for demo recordings or
+//! visual regression artifacts.
+
+use std::{
+    io::Write,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use tty_interface::{Device, Vector};
+
+use crate::{Error, Result};
+
+/// The terminal size recorded if the wrapped device's size can't be determined.
+const DEFAULT_SIZE: (u16, u16) = (80, 24);
+
+/// An output device which forwards all output to a wrapped device while recording it as
+/// timestamped frames, which may be written as an asciinema cast or a ttyrec recording. Output is
+/// recorded as a frame each time the device is flushed, such as when an interface is applied.
+///
+/// # Examples
+/// ```
+/// # use tty_interface::{Interface, test::VirtualDevice};
+/// # use tty_form::{Error, test::VirtualInputDevice};
+/// # let mut stdin = VirtualInputDevice;
+/// use tty_form::{
+///     Form,
+///     recording::FrameRecorder,
+///     step::{Step, CompoundStep},
+///     control::{Control, TextInput},
+/// };
+///
+/// let mut recorder = FrameRecorder::new(VirtualDevice::new());
+/// let mut interface = Interface::new_relative(&mut recorder)?;
+///
+/// let mut form = Form::new();
+/// let mut step = CompoundStep::new();
+/// TextInput::new("Enter your name:", false).add_to(&mut step);
+/// step.add_to(&mut form);
+///
+/// form.execute(&mut interface, &mut stdin)?;
+/// drop(interface);
+///
+/// let mut cast = Vec::new();
+/// recorder.write_cast(&mut cast)?;
+/// # Ok::<(), Error>(())
+/// ```
+pub struct FrameRecorder<D: Device> {
+    device: D,
+    started_at: Instant,
+    started_at_time: SystemTime,
+    frames: Vec<(Duration, Vec<u8>)>,
+    pending: Vec<u8>,
+}
+
+impl<D: Device> FrameRecorder<D> {
+    /// Create a recorder wrapping the specified output device, starting its recording's clock.
+    pub fn new(device: D) -> Self {
+        Self {
+            device,
+            started_at: Instant::now(),
+            started_at_time: SystemTime::now(),
+            frames: Vec::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    /// The wrapped output device.
+    pub fn device(&self) -> &D {
+        &self.device
+    }
+
+    /// The recorded frames' output and their time since the recording started.
+    pub fn frames(&self) -> &[(Duration, Vec<u8>)] {
+        &self.frames
+    }
+
+    /// Write the recording in asciinema's cast (v2) format.
+    pub fn write_cast<W: Write>(&mut self, writer: &mut W) -> Result<()> {
+        let (width, height) = self.size();
+        let timestamp = self
+            .started_at_time
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        writeln!(
+            writer,
+            "{{\"version\": 2, \"width\": {}, \"height\": {}, \"timestamp\": {}}}",
+            width, height, timestamp
+        )
+        .map_err(Error::Output)?;
+
+        for (elapsed, output) in &self.frames {
+            let data = escape_json(&String::from_utf8_lossy(output));
+            writeln!(
+                writer,
+                "[{:.6}, \"o\", \"{}\"]",
+                elapsed.as_secs_f64(),
+                data
+            )
+            .map_err(Error::Output)?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the recording in ttyrec's binary format.
+    pub fn write_ttyrec<W: Write>(&self, writer: &mut W) -> Result<()> {
+        for (elapsed, output) in &self.frames {
+            let time = self.started_at_time + *elapsed;
+            let time = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+
+            let mut header = Vec::with_capacity(12);
+            header.extend((time.as_secs() as u32).to_le_bytes());
+            header.extend(time.subsec_micros().to_le_bytes());
+            header.extend((output.len() as u32).to_le_bytes());
+
+            writer.write_all(&header).map_err(Error::Output)?;
+            writer.write_all(output).map_err(Error::Output)?;
+        }
+
+        Ok(())
+    }
+
+    /// The wrapped device's size in columns and rows, or a default if it can't be determined.
+    fn size(&mut self) -> (u16, u16) {
+        match self.device.get_terminal_size() {
+            Ok(size) => (size.x(), size.y()),
+            Err(_) => DEFAULT_SIZE,
+        }
+    }
+}
+
+impl<D: Device> Write for FrameRecorder<D> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.device.write(buf)?;
+        self.pending.extend(&buf[..written]);
+
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        if !self.pending.is_empty() {
+            let elapsed = self.started_at.elapsed();
+            self.frames
+                .push((elapsed, std::mem::take(&mut self.pending)));
+        }
+
+        self.device.flush()
+    }
+}
+
+impl<D: Device> Device for FrameRecorder<D> {
+    fn get_terminal_size(&mut self) -> tty_interface::Result<Vector> {
+        self.device.get_terminal_size()
+    }
+
+    fn enable_raw_mode(&mut self) -> tty_interface::Result<()> {
+        self.device.enable_raw_mode()
+    }
+
+    fn disable_raw_mode(&mut self) -> tty_interface::Result<()> {
+        self.device.disable_raw_mode()
+    }
+}
+
+/// Escape the specified text for inclusion in a JSON string.
+fn escape_json(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for ch in text.chars() {
+        match ch {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            ch if (ch as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => escaped.push(ch),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Write, time::UNIX_EPOCH};
+
+    use crate::test::VirtualTerminal;
+
+    use super::FrameRecorder;
+
+    /// A recorder of two frames, the first containing control characters and JSON syntax.
+    fn recorder() -> FrameRecorder<VirtualTerminal> {
+        let mut recorder = FrameRecorder::new(VirtualTerminal::with_size(40, 10));
+        for output in ["\x1b[1mA\"\\\r\n\tB", "second"] {
+            recorder.write_all(output.as_bytes()).unwrap();
+            recorder.flush().unwrap();
+        }
+
+        // Flushing without output records no frame
+        recorder.flush().unwrap();
+        recorder
+    }
+
+    #[test]
+    fn test_write_cast() {
+        let mut recorder = recorder();
+        let mut cast = Vec::new();
+        recorder.write_cast(&mut cast).unwrap();
+
+        let cast = String::from_utf8(cast).unwrap();
+        let lines: Vec<&str> = cast.lines().collect();
+        assert_eq!(3, lines.len());
+
+        let timestamp = recorder
+            .started_at_time
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let header = format!(
+            "{{\"version\": 2, \"width\": 40, \"height\": 10, \"timestamp\": {}}}",
+            timestamp
+        );
+        assert_eq!(header, lines[0]);
+
+        assert!(lines[1].starts_with('['));
+        assert!(lines[1].ends_with(r#", "o", "\u001b[1mA\"\\\r\n\tB"]"#));
+        assert!(lines[2].ends_with(r#", "o", "second"]"#));
+    }
+
+    #[test]
+    fn test_write_ttyrec() {
+        let recorder = recorder();
+        let mut ttyrec = Vec::new();
+        recorder.write_ttyrec(&mut ttyrec).unwrap();
+
+        let mut remaining = &ttyrec[..];
+        for (elapsed, output) in recorder.frames() {
+            let time = (recorder.started_at_time + *elapsed)
+                .duration_since(UNIX_EPOCH)
+                .unwrap();
+
+            // Each frame is preceded by its seconds, microseconds, and length, little-endian
+            let (header, rest) = remaining.split_at(12);
+            assert_eq!((time.as_secs() as u32).to_le_bytes(), header[0..4]);
+            assert_eq!(time.subsec_micros().to_le_bytes(), header[4..8]);
+            assert_eq!((output.len() as u32).to_le_bytes(), header[8..12]);
+
+            let (frame, rest) = rest.split_at(output.len());
+            assert_eq!(&output[..], frame);
+            remaining = rest;
+        }
+
+        assert_eq!(2, recorder.frames().len());
+        assert!(remaining.is_empty());
+    }
+}
@@ -9,7 +9,79 @@ pub enum Error {
     /// A terminal interface error.
     Interface(tty_interface::Error),
     /// A low-level terminal interaction error.
-    Terminal(crossterm::ErrorKind),
+    Terminal(std::io::Error),
+    /// Failed to write the form's result to its output, or to read a file populating the form.
+    Output(std::io::Error),
+    /// A storage backend failed to load or save data.
+    Storage(Box<dyn std::error::Error + Send + Sync>),
+    /// A form's values lacked the named value expected of them.
+    MissingValue(String),
+    /// A dependency was declared after the form was initialized, or on a control which doesn't
+    /// belong to any of its steps.
+    Dependency(String),
+    /// A form's presets couldn't be parsed.
+    Preset(String),
+    /// A command-line override couldn't be parsed or names no control of the form.
+    Override(String),
+    /// A pattern used syntax which isn't supported.
+    Pattern(String),
+    /// The standard input or output isn't a terminal, such as when piped or run by a script.
+    NoTerminal,
+    /// The terminal's type, from `TERM`, is unset or can't position the cursor, such as `dumb`.
+    UnsupportedTerminal(String),
+    /// The terminal's size couldn't be queried.
+    TerminalSize(std::io::Error),
+    /// The terminal is smaller than the form's minimum size.
+    TerminalTooSmall {
+        /// The terminal's width in columns.
+        columns: u16,
+        /// The terminal's height in rows.
+        rows: u16,
+        /// The form's minimum width in columns.
+        min_columns: u16,
+        /// The form's minimum height in rows.
+        min_rows: u16,
+    },
+}
+
+impl Error {
+    /// A suggested remedy for an environment which doesn't support interactive forms, detected by
+    /// [crate::terminal::check], such that it may be shown before falling back to a
+    /// non-interactive mode.
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            Error::NoTerminal => Some(
+                "run the command in an interactive terminal, or provide its input \
+                 non-interactively"
+                    .to_string(),
+            ),
+            Error::UnsupportedTerminal(term) if term.is_empty() => Some(
+                "set TERM to your terminal's type, such as xterm-256color, and ensure its \
+                 terminfo entry is installed"
+                    .to_string(),
+            ),
+            Error::UnsupportedTerminal(term) => Some(format!(
+                "TERM is \"{}\", which can't position the cursor; set TERM to a capable \
+                 terminal type, such as xterm-256color",
+                term
+            )),
+            Error::TerminalSize(_) => Some(
+                "run the command in a terminal emulator which reports its size, rather than \
+                 through a pipe or serial console"
+                    .to_string(),
+            ),
+            Error::TerminalTooSmall {
+                columns,
+                rows,
+                min_columns,
+                min_rows,
+            } => Some(format!(
+                "enlarge the terminal from {}x{} to at least {}x{}",
+                columns, rows, min_columns, min_rows
+            )),
+            _ => None,
+        }
+    }
 }
 
 impl From<tty_interface::Error> for Error {
@@ -18,8 +90,8 @@ impl From<tty_interface::Error> for Error {
     }
 }
 
-impl From<crossterm::ErrorKind> for Error {
-    fn from(err: crossterm::ErrorKind) -> Self {
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
         Error::Terminal(err)
     }
 }
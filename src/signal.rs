@@ -0,0 +1,81 @@
+//! Graceful handling of termination signals during form execution. Enabled by the `signals`
+//! feature.
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, OnceLock,
+    },
+    time::Duration,
+};
+
+use signal_hook::{
+    consts::{SIGINT, SIGTERM},
+    flag, SigId,
+};
+
+use crate::Result;
+
+/// The signals which cancel a running form.
+const TERMINATION_SIGNALS: [i32; 2] = [SIGINT, SIGTERM];
+
+/// How frequently to check for received signals while awaiting input.
+pub(crate) const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Whether termination signals should perform their default action. Registering a handler
+/// replaces the default action, so it's emulated while no form is running.
+static DEFAULT_ACTION_ENABLED: OnceLock<Arc<AtomicBool>> = OnceLock::new();
+
+/// Captures termination signals for the duration of a form's execution.
+pub(crate) struct SignalGuard {
+    received: Arc<AtomicBool>,
+    ids: Vec<SigId>,
+}
+
+impl SignalGuard {
+    /// Begin capturing termination signals until this guard is dropped.
+    pub(crate) fn register() -> Result<Self> {
+        let default_action_enabled = default_action_enabled()?;
+        default_action_enabled.store(false, Ordering::Relaxed);
+
+        let received = Arc::new(AtomicBool::new(false));
+
+        let mut ids = Vec::new();
+        for signal in TERMINATION_SIGNALS {
+            ids.push(flag::register(signal, Arc::clone(&received))?);
+        }
+
+        Ok(Self { received, ids })
+    }
+
+    /// Whether a termination signal has been received.
+    pub(crate) fn received(&self) -> bool {
+        self.received.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for SignalGuard {
+    fn drop(&mut self) {
+        for id in self.ids.drain(..) {
+            signal_hook::low_level::unregister(id);
+        }
+
+        if let Some(default_action_enabled) = DEFAULT_ACTION_ENABLED.get() {
+            default_action_enabled.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Retrieve the default-action flag, registering its conditional handlers on first use.
+fn default_action_enabled() -> Result<&'static Arc<AtomicBool>> {
+    if let Some(enabled) = DEFAULT_ACTION_ENABLED.get() {
+        return Ok(enabled);
+    }
+
+    let enabled = Arc::new(AtomicBool::new(true));
+    for signal in TERMINATION_SIGNALS {
+        flag::register_conditional_default(signal, Arc::clone(&enabled))?;
+    }
+
+    Ok(DEFAULT_ACTION_ENABLED.get_or_init(|| enabled))
+}
@@ -0,0 +1,211 @@
+//! Snapshots of a form's progress which may be saved and later restored.
+
+use crate::value::{ControlValue, ValueStore};
+
+/// The header identifying a serialized form state.
+const STATE_HEADER: &str = "tty-form-state 1";
+
+/// A snapshot of a form's progress: its active step and the values of its named controls and
+/// steps.
+///
+/// # Examples
+/// ```
+/// use tty_form::{state::FormState, value::{ControlValue, ValueStore}};
+///
+/// let mut values = ValueStore::new();
+/// values.set("summary", ControlValue::Text("Fix\tthe build".to_string()));
+///
+/// let state = FormState::new(1, values);
+/// let restored = FormState::deserialize(&state.serialize()).unwrap();
+///
+/// assert_eq!(1, restored.active_step());
+/// assert_eq!(Some("Fix\tthe build"), restored.values().get_text("summary"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct FormState {
+    active_step: usize,
+    values: ValueStore,
+}
+
+impl FormState {
+    /// Create a new state snapshot with the specified active step and values.
+    pub fn new(active_step: usize, values: ValueStore) -> Self {
+        Self {
+            active_step,
+            values,
+        }
+    }
+
+    /// The index of the step which was active.
+    pub fn active_step(&self) -> usize {
+        self.active_step
+    }
+
+    /// The values of the form's named controls and steps.
+    pub fn values(&self) -> &ValueStore {
+        &self.values
+    }
+
+    /// Serialize this state to text, one tab-separated record per line.
+    pub fn serialize(&self) -> String {
+        let mut lines = vec![
+            STATE_HEADER.to_string(),
+            format!("step\t{}", self.active_step),
+        ];
+
+        let mut values: Vec<_> = self.values.iter().collect();
+        values.sort_by_key(|(name, _)| *name);
+
+        for (name, value) in values {
+            let mut fields = Vec::new();
+            match value {
+                ControlValue::Text(text) => {
+                    fields.push("text");
+                    fields.push(name);
+                    fields.push(text);
+                }
+                ControlValue::Bool(value) => {
+                    fields.push("bool");
+                    fields.push(name);
+                    fields.push(if *value { "true" } else { "false" });
+                }
+                ControlValue::Pairs(pairs) => {
+                    fields.push("pairs");
+                    fields.push(name);
+                    for (key, value) in pairs {
+                        fields.push(key);
+                        fields.push(value);
+                    }
+                }
+            }
+
+            let fields: Vec<_> = fields.into_iter().map(escape).collect();
+            lines.push(fields.join("\t"));
+        }
+
+        lines.join("\n")
+    }
+
+    /// Deserialize a state previously serialized by [FormState::serialize], if it's valid.
+    pub fn deserialize(serialized: &str) -> Option<Self> {
+        let mut lines = serialized.lines();
+        if lines.next()? != STATE_HEADER {
+            return None;
+        }
+
+        let mut active_step = 0;
+        let mut values = ValueStore::new();
+
+        for line in lines {
+            let fields: Vec<String> = line.split('\t').map(unescape).collect();
+            match fields.first()?.as_str() {
+                "step" => active_step = fields.get(1)?.parse().ok()?,
+                "text" => values.set(fields.get(1)?, ControlValue::Text(fields.get(2)?.clone())),
+                "bool" => {
+                    let value = match fields.get(2)?.as_str() {
+                        "true" => true,
+                        "false" => false,
+                        _ => return None,
+                    };
+
+                    values.set(fields.get(1)?, ControlValue::Bool(value));
+                }
+                "pairs" => {
+                    let pairs = fields
+                        .get(2..)?
+                        .chunks(2)
+                        .map(|pair| (pair[0].clone(), pair.get(1).cloned().unwrap_or_default()))
+                        .collect();
+
+                    values.set(fields.get(1)?, ControlValue::Pairs(pairs));
+                }
+                _ => return None,
+            }
+        }
+
+        Some(Self::new(active_step, values))
+    }
+}
+
+/// Escape a field's tabs, newlines, and backslashes for serialization.
+pub(crate) fn escape(field: &str) -> String {
+    field
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+
+/// Reverse a field's serialization escaping.
+pub(crate) fn unescape(field: &str) -> String {
+    let mut unescaped = String::new();
+
+    let mut chars = field.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            unescaped.push(ch);
+            continue;
+        }
+
+        match chars.next() {
+            Some('t') => unescaped.push('\t'),
+            Some('n') => unescaped.push('\n'),
+            Some(ch) => unescaped.push(ch),
+            None => unescaped.push('\\'),
+        }
+    }
+
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::value::{ControlValue, ValueStore};
+
+    use super::FormState;
+
+    #[test]
+    fn test_round_trip() {
+        let mut values = ValueStore::new();
+        values.set(
+            "summary",
+            ControlValue::Text("Fix\tthe \\build\n".to_string()),
+        );
+        values.set("breaking", ControlValue::Bool(true));
+        values.set(
+            "trailers",
+            ControlValue::Pairs(vec![
+                ("Refs".to_string(), "#1".to_string()),
+                ("Note".to_string(), String::new()),
+            ]),
+        );
+        values.set("empty", ControlValue::Pairs(Vec::new()));
+
+        let restored = FormState::deserialize(&FormState::new(2, values.clone()).serialize());
+        let restored = restored.unwrap();
+
+        assert_eq!(2, restored.active_step());
+        for (name, value) in values.iter() {
+            assert_eq!(Some(value), restored.values().get(name));
+        }
+    }
+
+    #[test]
+    fn test_malformed_state() {
+        for serialized in [
+            "",
+            "tty-form-state 2\nstep\t0",
+            "tty-form-state 1\nstep",
+            "tty-form-state 1\nstep\tfirst",
+            "tty-form-state 1\ntext\tsummary",
+            "tty-form-state 1\nbool\tbreaking\tmaybe",
+            "tty-form-state 1\npairs",
+            "tty-form-state 1\nnumber\tcount\t1",
+        ] {
+            assert!(
+                FormState::deserialize(serialized).is_none(),
+                "{:?}",
+                serialized
+            );
+        }
+    }
+}
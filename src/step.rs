@@ -1,10 +1,20 @@
-use crossterm::event::KeyEvent;
-use tty_interface::{Interface, Position};
+use std::{any::Any, time::Duration};
+
+use tty_interface::Position;
 
 use crate::{
-    dependency::DependencyState,
+    backend::RenderBackend,
+    context::Context,
+    control::ControlMeta,
+    dependency::{DependencyId, DependencyState},
+    device::InputDevice,
+    history::History,
+    input::{KeyCode, KeyEvent},
+    navigation::NavigationPolicy,
     text::{DrawerContents, Segment},
-    Form,
+    utility::describe_element,
+    value::{ControlValue, ValueStore},
+    ConfigIssue, Form, Result,
 };
 
 mod compound;
@@ -16,19 +26,30 @@ pub use keyvalue::*;
 mod textblock;
 pub use textblock::*;
 
+mod trailer;
+pub use trailer::*;
+
 mod yesno;
 pub use yesno::*;
 
 /// A distinct, vertically-separated phase of the form.
-pub trait Step {
-    /// Perform any post-configuration initialization actions for this step.
-    fn initialize(&mut self, dependency_state: &mut DependencyState, index: usize);
+pub trait Step: Any {
+    /// Perform any post-configuration initialization actions for this step. The form's context is
+    /// provided, which may be cloned and kept to reach application data while updating or
+    /// rendering.
+    fn initialize(
+        &mut self,
+        dependency_state: &mut DependencyState,
+        context: &Context,
+        index: usize,
+    );
 
     /// Render this step at the specified position and return the height of the rendered content.
     fn render(
         &self,
-        interface: &mut Interface,
+        interface: &mut dyn RenderBackend,
         dependency_state: &DependencyState,
+        values: &ValueStore,
         position: Position,
         is_focused: bool,
     ) -> u16;
@@ -37,26 +58,487 @@ pub trait Step {
     fn update(
         &mut self,
         dependency_state: &mut DependencyState,
+        values: &ValueStore,
         input: KeyEvent,
     ) -> Option<InputResult>;
 
+    /// Update this step's state as time passes, called each tick of the form's interval while this
+    /// step is focused, such as to animate a spinner. May return an instruction for the form, such
+    /// as to retreat once a cancelled task acknowledges.
+    fn tick(&mut self) -> Option<InputResult> {
+        None
+    }
+
+    /// The problems with this step's current values which prevent the form's submission, each with
+    /// the index of the control it concerns.
+    fn validation_errors(
+        &self,
+        _dependency_state: &DependencyState,
+        _values: &ValueStore,
+    ) -> Vec<(usize, String)> {
+        Vec::new()
+    }
+
+    /// The index of this step's focused control, if it has controls.
+    fn focused_control_index(&self) -> Option<usize> {
+        None
+    }
+
+    /// Focus the control at the specified index, such as to correct its validation error.
+    fn focus_control(&mut self, _index: usize) {}
+
+    /// A problem with this step's current values which needs attention before the form is
+    /// submitted, such as a failed validation, if there is one.
+    fn warning(&self, _dependency_state: &DependencyState, _values: &ValueStore) -> Option<String> {
+        None
+    }
+
+    /// A description of this step's structure: a header line followed by a line for each of its
+    /// controls, if it has any.
+    fn describe(&self) -> Vec<String> {
+        let prompt: String = self.help().iter().map(|text| text.content()).collect();
+        vec![describe_element("Step", None, &prompt, &[])]
+    }
+
+    /// Whether this step consumes the specified Up or Down key as input in its current state. If
+    /// not, the key moves focus between steps in a form's batch navigation mode.
+    fn captures_arrow(&self, _code: KeyCode) -> bool {
+        false
+    }
+
+    /// Whether this step accepts no input at all, such as purely static output, and so is skipped
+    /// while navigating in either direction.
+    fn skippable(&self) -> bool {
+        false
+    }
+
+    /// Whether this step rejected input since last asked, such as a disallowed character or an
+    /// attempt to advance past an invalid value, clearing the rejection.
+    fn take_rejection(&mut self) -> bool {
+        false
+    }
+
+    /// Whether this step only displays content and accepts no text input, allowing the form's
+    /// read-only cancel keys to apply.
+    fn read_only(&self) -> bool {
+        false
+    }
+
     /// Retrieve this step's current help text.
     fn help(&self) -> Segment;
 
     /// Retrieve this step's current drawer contents, if applicable.
     fn drawer(&self) -> Option<DrawerContents>;
 
-    /// Retrieves this step's final WYSIWYG result.
-    fn result(&self, dependency_state: &DependencyState) -> String;
+    /// Retrieve this step's current extended documentation, if available, shown in place of its
+    /// drawer when toggled.
+    fn long_help(&self) -> Option<DrawerContents> {
+        None
+    }
+
+    /// Whether this step may be changed from outside its form through a handle, such as one of its
+    /// controls', such that the form periodically re-renders to reflect changes.
+    fn has_handle(&self) -> bool {
+        false
+    }
+
+    /// How often this step should be ticked and re-rendered without input while focused, such as to
+    /// rotate example hints, if it should be.
+    fn refresh_interval(&self) -> Option<Duration> {
+        None
+    }
+
+    /// This step's override of the form's navigation policy, if any.
+    fn navigation_policy(&self) -> Option<NavigationPolicy> {
+        None
+    }
+
+    /// Whether this step may be skipped with the form's skip keys, leaving it empty.
+    fn optional(&self) -> bool {
+        false
+    }
+
+    /// A description of this step's internal focus state, shown by the form's debug overlay.
+    fn debug_info(&self) -> Option<String> {
+        None
+    }
+
+    /// Retrieves this step's final WYSIWYG result. The form's current values are provided so the
+    /// result may be tailored to other steps' data.
+    fn result(&self, dependency_state: &DependencyState, values: &ValueStore) -> String;
+
+    /// This step's result with its sensitive values masked, safe to share outside the form such as
+    /// in drafts. The provided values have other steps' sensitive values masked. Defaults to the
+    /// unmasked result, for steps without sensitive controls.
+    fn redacted_result(&self, dependency_state: &DependencyState, values: &ValueStore) -> String {
+        self.result(dependency_state, values)
+    }
+
+    /// A single-line summary of this step's result, shown in place of the step once it's completed
+    /// if the form collapses completed steps.
+    fn summary(&self, dependency_state: &DependencyState, values: &ValueStore) -> String {
+        let result = self.result(dependency_state, values);
+        result.lines().next().unwrap_or_default().to_string()
+    }
+
+    /// Parse a result previously produced by this step from the start of the specified text, the
+    /// inverse of [Step::result]. Returns each possible parse, preferred first, as the values of
+    /// this step and its named controls with the length of text consumed.
+    fn parse_result(&self, _text: &str) -> Vec<ParsedResult> {
+        Vec::new()
+    }
+
+    /// The dependency evaluations this step and its controls provide, by identifier.
+    fn evaluation_ids(&self) -> Vec<DependencyId> {
+        Vec::new()
+    }
+
+    /// The dependencies this step and its controls react to, by identifier.
+    fn dependency_ids(&self) -> Vec<DependencyId> {
+        Vec::new()
+    }
+
+    /// Problems with this step's configuration, given its index in the form.
+    fn configuration_issues(&self, _index: usize) -> Vec<ConfigIssue> {
+        Vec::new()
+    }
+
+    /// Metadata identifying each of this step's controls, if it has any.
+    fn controls_meta(&self) -> Vec<ControlMeta> {
+        Vec::new()
+    }
+
+    /// Metadata identifying this step's focused control, if it has one.
+    fn focused_control_meta(&self) -> Option<ControlMeta> {
+        None
+    }
+
+    /// The current values of this step and its controls which have been named.
+    fn form_values(&self) -> Vec<(String, ControlValue)> {
+        Vec::new()
+    }
+
+    /// Restore this step's state from the values chosen in its form's previous executions. Called
+    /// as the form begins, if it has history enabled.
+    fn recall(&mut self, _history: &History) {}
+
+    /// Record this step's values in its form's history, called once the form is submitted if it
+    /// has history enabled.
+    fn remember(&self, _history: &mut History) {}
+
+    /// Replace the value of this step or its control with the specified name, returning whether it
+    /// was applied.
+    fn set_form_value(
+        &mut self,
+        _dependency_state: &mut DependencyState,
+        _name: &str,
+        _value: &ControlValue,
+    ) -> bool {
+        false
+    }
+
+    /// Lock the value of this step or its control with the specified name against editing, so
+    /// that focus passes over it, returning whether it was locked.
+    fn lock_form_value(&mut self, _name: &str) -> bool {
+        false
+    }
 
     /// Complete configuration and add this step to the form.
     fn add_to(self, form: &mut Form);
+
+    /// Execute this step alone as a quick prompt, without constructing a form, returning its
+    /// result once it's completed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use tty_interface::{Interface, test::VirtualDevice};
+    /// # use tty_form::{Error, test::VirtualInputDevice};
+    /// # let mut device = VirtualDevice::new();
+    /// # let mut interface = Interface::new_relative(&mut device)?;
+    /// # let mut stdin = VirtualInputDevice;
+    /// use tty_form::step::{Step, TextBlockStep};
+    ///
+    /// let notes = TextBlockStep::new("Enter release notes:")
+    ///     .execute_standalone(&mut interface, &mut stdin)?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    fn execute_standalone<B: RenderBackend, D: InputDevice>(
+        self,
+        interface: &mut B,
+        input_device: &mut D,
+    ) -> Result<String>
+    where
+        Self: Sized,
+    {
+        let mut form = Form::new();
+        self.add_to(&mut form);
+        form.execute(interface, input_device)
+    }
+}
+
+impl dyn Step {
+    /// Retrieve this step as its concrete type, if it is of the specified type.
+    pub fn downcast_ref<T: Step>(&self) -> Option<&T> {
+        (self as &dyn Any).downcast_ref()
+    }
+
+    /// Retrieve this step mutably as its concrete type, if it is of the specified type.
+    pub fn downcast_mut<T: Step>(&mut self) -> Option<&mut T> {
+        (self as &mut dyn Any).downcast_mut()
+    }
 }
 
+/// A step's parsed result: the values of the step and its named controls, and the length of text
+/// consumed.
+pub type ParsedResult = (Vec<(String, ControlValue)>, usize);
+
 /// After processing an input event, an action may be returned to the form from the step.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum InputResult {
     /// Advance the form to the next step.
     AdvanceForm,
     /// Retreat the form to the previous step.
     RetreatForm,
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tty_interface::Position;
+
+    use crate::{
+        backend::RenderBackend,
+        context::Context,
+        control::{Control, SelectInput},
+        dependency::DependencyState,
+        device::ChannelInputDevice,
+        input::{InputEvent, KeyCode, KeyEvent, KeyModifiers},
+        test::{press, SizedBackend},
+        text::{DrawerContents, Segment, Text},
+        value::ValueStore,
+        Form,
+    };
+
+    use super::{CompoundStep, InputResult, Step};
+
+    /// A step whose result describes another step's chosen type.
+    struct TypeFooter;
+
+    impl Step for TypeFooter {
+        fn initialize(&mut self, _: &mut DependencyState, _: &Context, _: usize) {}
+
+        fn render(
+            &self,
+            _: &mut dyn RenderBackend,
+            _: &DependencyState,
+            _: &ValueStore,
+            _: Position,
+            _: bool,
+        ) -> u16 {
+            0
+        }
+
+        fn update(
+            &mut self,
+            _: &mut DependencyState,
+            _: &ValueStore,
+            _: KeyEvent,
+        ) -> Option<InputResult> {
+            None
+        }
+
+        fn help(&self) -> Segment {
+            Text::new(String::new()).as_segment()
+        }
+
+        fn drawer(&self) -> Option<DrawerContents> {
+            None
+        }
+
+        fn result(&self, _dependency_state: &DependencyState, values: &ValueStore) -> String {
+            match values.get_text("type") {
+                Some("feat") => String::from("Adds a feature.\n"),
+                _ => String::from("Changes behavior.\n"),
+            }
+        }
+
+        fn add_to(self, form: &mut Form) {
+            form.add_step(Box::new(self));
+        }
+    }
+
+    #[test]
+    fn test_result_reads_form_values() {
+        let mut form = Form::new();
+
+        let mut step = CompoundStep::new();
+        let mut kind = SelectInput::new("Select a type.", vec![("feat", ""), ("fix", "")]);
+        kind.set_name("type");
+        kind.add_to(&mut step);
+        step.add_to(&mut form);
+
+        TypeFooter.add_to(&mut form);
+
+        assert_eq!("feat\nAdds a feature.", form.result());
+
+        press(&mut form, KeyCode::Down);
+        assert_eq!("fix\nChanges behavior.", form.result());
+    }
+
+    /// A step which counts its ticks, advancing the form after the specified number.
+    struct Countdown {
+        ticks: usize,
+        until: usize,
+    }
+
+    impl Step for Countdown {
+        fn initialize(&mut self, _: &mut DependencyState, _: &Context, _: usize) {}
+
+        fn render(
+            &self,
+            _: &mut dyn RenderBackend,
+            _: &DependencyState,
+            _: &ValueStore,
+            _: Position,
+            _: bool,
+        ) -> u16 {
+            0
+        }
+
+        fn update(
+            &mut self,
+            _: &mut DependencyState,
+            _: &ValueStore,
+            _: KeyEvent,
+        ) -> Option<InputResult> {
+            None
+        }
+
+        fn tick(&mut self) -> Option<InputResult> {
+            self.ticks += 1;
+            (self.ticks == self.until).then_some(InputResult::AdvanceForm)
+        }
+
+        fn help(&self) -> Segment {
+            Text::new(String::new()).as_segment()
+        }
+
+        fn drawer(&self) -> Option<DrawerContents> {
+            None
+        }
+
+        fn result(&self, _dependency_state: &DependencyState, _values: &ValueStore) -> String {
+            format!("{} ticks\n", self.ticks)
+        }
+
+        fn add_to(self, form: &mut Form) {
+            form.add_step(Box::new(self));
+        }
+    }
+
+    #[test]
+    fn test_ticks_focused_step() {
+        let countdown_form = || {
+            let mut form = Form::new();
+            Countdown { ticks: 0, until: 3 }.add_to(&mut form);
+            Countdown { ticks: 0, until: 2 }.add_to(&mut form);
+            form
+        };
+
+        // Ticks may be delivered as events, reaching only the focused step
+        let mut form = countdown_form();
+        form.update(InputEvent::Tick);
+        assert_eq!("1 ticks\n0 ticks", form.result());
+
+        // A form's tick interval ticks its focused step between input events, which may advance it
+        let mut form = countdown_form();
+        form.set_tick(Duration::from_millis(1));
+
+        let (_sender, mut device) = ChannelInputDevice::channel();
+        let mut backend = SizedBackend::new(80, 24);
+        let result = form.execute(&mut backend, &mut device).unwrap();
+        assert_eq!("3 ticks\n2 ticks", result);
+    }
+
+    /// A step which greets the user named by its form's context.
+    struct Greeting(Context);
+
+    impl Step for Greeting {
+        fn initialize(&mut self, _: &mut DependencyState, context: &Context, _: usize) {
+            self.0 = context.clone();
+        }
+
+        fn render(
+            &self,
+            _: &mut dyn RenderBackend,
+            _: &DependencyState,
+            _: &ValueStore,
+            _: Position,
+            _: bool,
+        ) -> u16 {
+            0
+        }
+
+        fn update(
+            &mut self,
+            _: &mut DependencyState,
+            _: &ValueStore,
+            _: KeyEvent,
+        ) -> Option<InputResult> {
+            None
+        }
+
+        fn help(&self) -> Segment {
+            Text::new(String::new()).as_segment()
+        }
+
+        fn drawer(&self) -> Option<DrawerContents> {
+            None
+        }
+
+        fn result(&self, _dependency_state: &DependencyState, _values: &ValueStore) -> String {
+            match self.0.get::<String>() {
+                Some(name) => format!("Hello, {}\n", name),
+                None => String::from("Hello\n"),
+            }
+        }
+
+        fn add_to(self, form: &mut Form) {
+            form.add_step(Box::new(self));
+        }
+    }
+
+    #[test]
+    fn test_steps_receive_context() {
+        let mut form = Form::new();
+        Greeting(Context::new()).add_to(&mut form);
+        form.set_context(String::from("Daniel"));
+        assert_eq!(
+            Some("Daniel"),
+            form.context().get::<String>().map(String::as_str)
+        );
+
+        // The context is provided as the form initializes its steps
+        assert_eq!("Hello", form.result());
+        form.update(InputEvent::Tick);
+        assert_eq!("Hello, Daniel", form.result());
+    }
+
+    #[test]
+    fn test_execute_standalone() {
+        let mut step = CompoundStep::new();
+        SelectInput::new("Select a type.", vec![("feat", ""), ("fix", "")]).add_to(&mut step);
+
+        let (sender, mut device) = ChannelInputDevice::channel();
+        for code in [KeyCode::Down, KeyCode::Enter] {
+            sender
+                .send(InputEvent::Key(KeyEvent::new(code, KeyModifiers::NONE)))
+                .unwrap();
+        }
+
+        let mut backend = SizedBackend::new(80, 24);
+        let result = step.execute_standalone(&mut backend, &mut device).unwrap();
+        assert_eq!("fix", result);
+    }
+}
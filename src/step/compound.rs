@@ -1,19 +1,48 @@
-use crossterm::event::{KeyCode, KeyEvent};
-use tty_interface::{pos, Interface, Position};
+use std::time::Duration;
+
+use tty_interface::{pos, Position, Style};
 
 use crate::{
-    control::Control,
-    dependency::{Action, DependencyState},
+    backend::RenderBackend,
+    context::Context,
+    control::{Control, ControlHandle, ControlId, ControlKind, ControlMeta},
+    dependency::{Action, DependencyId, DependencyState, Evaluation},
+    history::History,
+    input::{KeyCode, KeyEvent},
+    navigation::NavigationPolicy,
     style::{error_style, muted_style},
     text::{
-        get_segment_length, set_segment_style, set_segment_subset_style, DrawerContents, Segment,
-        Text,
+        overlay_segment_style, split_segment_lines,
+        util::{
+            get_segment_index_at_column, get_segment_length, get_segment_width, set_segment_style,
+            set_segment_subset_style, split_segment_at,
+        },
+        DrawerContents, Segment, Text,
     },
-    utility::render_segment,
-    Form,
+    utility::{describe_element, render_segment},
+    value::{ControlValue, ValueStore},
+    ConfigIssue, Form,
 };
 
-use super::{InputResult, Step};
+use super::{InputResult, ParsedResult, Step};
+
+/// The marker indenting a compound step's wrapped continuation rows.
+const WRAP_MARKER: &str = "↪ ";
+
+/// The marker indicating content scrolled out of view at either edge of a compound step's line.
+const SCROLL_MARKER: &str = "…";
+
+/// How a compound step's line is displayed when it exceeds the terminal's width.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum OverflowMode {
+    /// Wrap the line onto indented continuation rows.
+    #[default]
+    Wrap,
+    /// Scroll the line horizontally to follow the cursor, marking hidden content with ellipses.
+    Scroll,
+    /// Let the line extend beyond the terminal's width.
+    Visible,
+}
 
 /// A single-line step which controls multple controls including static and input elements.
 ///
@@ -32,10 +61,37 @@ use super::{InputResult, Step};
 /// TextInput::new("Enter your name:", false).add_to(&mut step);
 /// step.add_to(&mut form);
 /// ```
+///
+/// Controls may instead be added by [CompoundStep::add], returning a handle by which they may
+/// be updated later:
+/// ```
+/// use tty_form::{
+///     step::CompoundStep,
+///     control::{StaticText, TextInput},
+///     dependency::{Action, Evaluation},
+/// };
+///
+/// let mut step = CompoundStep::new();
+/// let greeting = step.add(StaticText::new("Welcome, "));
+/// let name = step.add(TextInput::new("Enter your name:", false));
+///
+/// let empty_name = step.get_mut(&name).unwrap().set_evaluation(Evaluation::IsEmpty);
+/// step.get_mut(&greeting).unwrap().set_dependency(empty_name, Action::Hide);
+/// ```
 pub struct CompoundStep {
     index: Option<usize>,
     controls: Vec<Box<dyn Control>>,
+    control_ids: Vec<ControlId>,
+    evaluations: Vec<(ControlId, DependencyId, Evaluation)>,
+    dependencies: Vec<(ControlId, DependencyId, Action)>,
     max_line_length: Option<u16>,
+    enforce_max_line_length: bool,
+    overflow_mode: OverflowMode,
+    navigation_policy: Option<NavigationPolicy>,
+    optional: bool,
+    focus_style: Option<Style>,
+    rejected: bool,
+    error: Option<String>,
     active_control: usize,
     max_control: usize,
 }
@@ -46,15 +102,136 @@ impl CompoundStep {
         Self {
             index: None,
             controls: Vec::new(),
+            control_ids: Vec::new(),
+            evaluations: Vec::new(),
+            dependencies: Vec::new(),
             max_line_length: None,
+            enforce_max_line_length: false,
+            overflow_mode: OverflowMode::default(),
+            navigation_policy: None,
+            optional: false,
+            focus_style: None,
+            rejected: false,
+            error: None,
             active_control: 0,
             max_control: 0,
         }
     }
 
+    /// Highlight the focused control's contents with the specified style, such as
+    /// [crate::style::focus_style], so it's clear which of several inline inputs receives input.
+    pub fn set_focus_style(&mut self, style: impl Into<Style>) {
+        self.focus_style = Some(style.into());
+    }
+
     /// Append the specified control to this step.
     pub fn add_control(&mut self, control: Box<dyn Control>) {
         self.controls.push(control);
+        self.control_ids.push(ControlId::new());
+    }
+
+    /// Append the specified control to this step, returning a handle by which it may be retrieved.
+    pub fn add<T: Control>(&mut self, control: T) -> ControlHandle<T> {
+        self.add_control(Box::new(control));
+
+        let id = self.control_ids[self.control_ids.len() - 1];
+        ControlHandle::new(id)
+    }
+
+    /// The control referenced by the specified handle, if it belongs to this step.
+    pub fn get<T: Control>(&self, handle: &ControlHandle<T>) -> Option<&T> {
+        let control_index = self.control_index(handle.id())?;
+        self.controls[control_index].downcast_ref()
+    }
+
+    /// The control referenced by the specified handle mutably, if it belongs to this step.
+    pub fn get_mut<T: Control>(&mut self, handle: &ControlHandle<T>) -> Option<&mut T> {
+        let control_index = self.control_index(handle.id())?;
+        self.controls[control_index].downcast_mut()
+    }
+
+    /// The index of the control with the specified identifier, if it belongs to this step.
+    pub(crate) fn control_index(&self, id: ControlId) -> Option<usize> {
+        self.control_ids
+            .iter()
+            .position(|control_id| *control_id == id)
+    }
+
+    /// Wire an additional dependency evaluation to the specified control in this step.
+    pub(crate) fn add_evaluation(
+        &mut self,
+        control: ControlId,
+        id: DependencyId,
+        evaluation: Evaluation,
+    ) {
+        self.evaluations.push((control, id, evaluation));
+    }
+
+    /// Wire an additional dependency to the specified control in this step.
+    pub(crate) fn add_dependency(&mut self, control: ControlId, id: DependencyId, action: Action) {
+        self.dependencies.push((control, id, action));
+    }
+
+    /// All dependency evaluations sourced from the specified control, including those wired after
+    /// the control's construction.
+    fn control_evaluations(&self, control_index: usize) -> Vec<(DependencyId, Evaluation)> {
+        let control_id = self.control_ids[control_index];
+
+        let mut evaluations: Vec<_> = self.controls[control_index]
+            .evaluation()
+            .into_iter()
+            .collect();
+        for (evaluation_control, id, evaluation) in &self.evaluations {
+            if *evaluation_control == control_id {
+                evaluations.push((*id, evaluation.clone()));
+            }
+        }
+
+        evaluations
+    }
+
+    /// All dependencies the specified control reacts to, including those wired after the
+    /// control's construction.
+    fn control_dependencies(&self, control_index: usize) -> Vec<(DependencyId, Action)> {
+        let control_id = self.control_ids[control_index];
+
+        let mut dependencies: Vec<_> = self.controls[control_index]
+            .dependency()
+            .into_iter()
+            .collect();
+        for (dependency_control, id, action) in &self.dependencies {
+            if *dependency_control == control_id {
+                dependencies.push((*id, *action));
+            }
+        }
+
+        dependencies
+    }
+
+    /// The control at the specified index, if one exists.
+    pub fn control(&self, index: usize) -> Option<&dyn Control> {
+        self.controls.get(index).map(|control| control.as_ref())
+    }
+
+    /// The control at the specified index mutably, if one exists.
+    pub fn control_mut(&mut self, index: usize) -> Option<&mut dyn Control> {
+        self.controls.get_mut(index).map(|control| control.as_mut())
+    }
+
+    /// The control with the specified name, if one exists and is of the specified type.
+    pub fn get_control<T: Control>(&self, name: &str) -> Option<&T> {
+        self.controls
+            .iter()
+            .find(|control| control.name() == Some(name))
+            .and_then(|control| control.downcast_ref())
+    }
+
+    /// The control with the specified name mutably, if one exists and is of the specified type.
+    pub fn get_control_mut<T: Control>(&mut self, name: &str) -> Option<&mut T> {
+        self.controls
+            .iter_mut()
+            .find(|control| control.name() == Some(name))
+            .and_then(|control| control.downcast_mut())
     }
 
     /// Set this step's maximum total line length.
@@ -62,9 +239,99 @@ impl CompoundStep {
         self.max_line_length = Some(max_length);
     }
 
+    /// Override the form's navigation policy while this step is active.
+    pub fn set_navigation_policy(&mut self, policy: NavigationPolicy) {
+        self.navigation_policy = Some(policy);
+    }
+
+    /// Set whether this step may be skipped with the form's skip keys, leaving it empty.
+    pub fn set_optional(&mut self, optional: bool) {
+        self.optional = optional;
+    }
+
+    /// Set how this step's line is displayed when it exceeds the terminal's width.
+    pub fn set_overflow_mode(&mut self, overflow_mode: OverflowMode) {
+        self.overflow_mode = overflow_mode;
+    }
+
+    /// Set whether the form is prevented from advancing past this step while its line exceeds the
+    /// maximum length.
+    pub fn set_enforce_max_line_length(&mut self, enforce: bool) {
+        self.enforce_max_line_length = enforce;
+    }
+
+    /// Describe why this step's line may not be submitted, if it exceeds an enforced maximum.
+    fn line_length_error(
+        &self,
+        dependency_state: &DependencyState,
+        values: &ValueStore,
+    ) -> Option<String> {
+        if !self.enforce_max_line_length {
+            return None;
+        }
+
+        self.line_length_warning(dependency_state, values)
+    }
+
+    /// Describe how this step's line exceeds its maximum length, if it does, enforced or not.
+    fn line_length_warning(
+        &self,
+        dependency_state: &DependencyState,
+        values: &ValueStore,
+    ) -> Option<String> {
+        let max_length = self.max_line_length?;
+
+        let line = self.result(dependency_state, values);
+        let line_width = get_segment_width(&Text::new(line.trim_end().to_string()).as_segment());
+        if line_width > max_length.into() {
+            return Some(format!(
+                "This line may not exceed {} characters.",
+                max_length
+            ));
+        }
+
+        None
+    }
+
+    /// The error preventing the specified control from being advanced past, if any: its own
+    /// validation error, or a missing value while a dependency requires one.
+    fn control_error(
+        &self,
+        control_index: usize,
+        dependency_state: &DependencyState,
+    ) -> Option<String> {
+        let control = self.control(control_index)?;
+        if let Some(error) = control.validation_error() {
+            return Some(error);
+        }
+
+        let is_required = self
+            .control_dependencies(control_index)
+            .iter()
+            .any(|(id, action)| *action == Action::Require && dependency_state.get_evaluation(id));
+
+        (is_required && control.result_text().trim().is_empty())
+            .then(|| "A value is required.".to_string())
+    }
+
+    /// Whether the focused control's background task has been cancelled but hasn't yet returned.
+    fn is_active_cancelling(&self) -> bool {
+        self.control(self.active_control)
+            .is_some_and(|control| control.is_cancelling())
+    }
+
+    /// Notify the focused control, if any, that it's losing focus.
+    fn blur_active_control(&mut self) {
+        if let Some(control) = self.controls.get_mut(self.active_control) {
+            control.blur();
+        }
+    }
+
     /// Advance the step's state to the next control. Returns true if we've reached the end of this
     /// step and the form should advance to the next.
     fn advance_control(&mut self) -> bool {
+        self.blur_active_control();
+
         let mut reached_last_control = false;
         loop {
             if self.active_control + 1 >= self.controls.len() {
@@ -75,6 +342,7 @@ impl CompoundStep {
             self.active_control += 1;
 
             if self.controls[self.active_control].focusable() {
+                self.controls[self.active_control].open_drawer();
                 break;
             }
         }
@@ -101,6 +369,8 @@ impl CompoundStep {
     /// Retreat the step's state to the previous control. Returns true if we've reached the start
     /// of this step and the form should retreat to the previous.
     fn retreat_control(&mut self) -> bool {
+        self.blur_active_control();
+
         loop {
             if self.active_control == 0 {
                 return true;
@@ -109,26 +379,73 @@ impl CompoundStep {
             self.active_control -= 1;
 
             if self.controls[self.active_control].focusable() {
+                self.controls[self.active_control].open_drawer();
                 break;
             }
         }
 
         false
     }
+
+    /// This step's result, with each visible control contributing the specified text.
+    fn result_of(
+        &self,
+        dependency_state: &DependencyState,
+        control_text: impl Fn(&dyn Control) -> String,
+    ) -> String {
+        let mut result = String::new();
+
+        for (control_index, control) in self.controls.iter().enumerate() {
+            let is_hidden = self
+                .control_dependencies(control_index)
+                .iter()
+                .any(|(id, action)| {
+                    let evaluation_result = dependency_state.get_evaluation(id);
+                    match action {
+                        Action::Hide => evaluation_result,
+                        Action::Show => !evaluation_result,
+                        Action::Require => false,
+                    }
+                });
+
+            if is_hidden {
+                continue;
+            }
+
+            result.push_str(&control_text(control.as_ref()));
+        }
+
+        result.push('\n');
+
+        result
+    }
 }
 
 impl Step for CompoundStep {
-    fn initialize(&mut self, dependency_state: &mut DependencyState, index: usize) {
+    fn initialize(
+        &mut self,
+        dependency_state: &mut DependencyState,
+        context: &Context,
+        index: usize,
+    ) {
         self.index = Some(index);
 
+        for control in &mut self.controls {
+            control.initialize(context);
+        }
+
         // Advance to the first focusable control, since the first might be a static element
-        if !self.controls[0].focusable() {
+        if self
+            .controls
+            .first()
+            .is_some_and(|control| !control.focusable())
+        {
             self.advance_control();
         }
 
         // Register any evaluations in state for this step
         for (control_index, control) in self.controls.iter().enumerate() {
-            for (id, evaluation) in control.evaluation() {
+            for (id, evaluation) in self.control_evaluations(control_index) {
                 dependency_state.register_evaluation(&id, index, control_index);
 
                 let value = control.evaluate(&evaluation);
@@ -139,27 +456,44 @@ impl Step for CompoundStep {
 
     fn render(
         &self,
-        interface: &mut Interface,
+        interface: &mut dyn RenderBackend,
         dependency_state: &DependencyState,
+        _values: &ValueStore,
         mut position: Position,
         is_focused: bool,
     ) -> u16 {
-        interface.clear_line(position.y());
+        interface.clear_rest_of_line(position);
 
-        let mut cursor_position = None;
+        let line_start = position;
+
+        // The column within the unwrapped line, against which its maximum length is checked
+        let mut line_column = position.x();
+
+        // Collect the step's rows of content and the cursor's column within its row, if any
+        let mut rows: Vec<(Segment, Option<u16>)> = vec![(Segment::new(), None)];
         for (control_index, control) in self.controls.iter().enumerate() {
             let (mut segment, cursor_offset) = control.text();
+            if let Some(focus_style) = self.focus_style {
+                if is_focused && control_index == self.active_control {
+                    overlay_segment_style(&mut segment, focus_style);
+                }
+            }
+
+            if let Some(indicator) = control.indicator() {
+                segment.extend(indicator);
+            }
 
             // If this is the focused control, let it drive the overall cursor position
             if control_index == self.active_control {
                 if let Some(offset) = cursor_offset {
-                    cursor_position = Some(pos!(position.x() + offset, position.y()));
+                    let (row, row_cursor) = rows.last_mut().unwrap();
+                    *row_cursor = Some(get_segment_width(row) as u16 + offset);
                 }
             }
 
-            // Resolve this control's dependency and update rendering accordingly
+            // Resolve this control's dependencies and update rendering accordingly
             let mut should_hide = false;
-            if let Some((id, action)) = control.dependency() {
+            for (id, action) in self.control_dependencies(control_index) {
                 let control_touched = control_index <= self.max_control;
                 let evaluation_result = dependency_state.get_evaluation(&id);
 
@@ -179,26 +513,70 @@ impl Step for CompoundStep {
                             }
                         }
                     }
-                    Action::Show => should_hide = !evaluation_result,
+                    Action::Show => should_hide |= !evaluation_result,
+                    Action::Require => {}
                 }
             }
 
-            // If this step is too-long, render the tail as an error
-            if let Some(max_length) = self.max_line_length {
-                let segment_length = get_segment_length(&segment) as u16;
-                if position.x() + segment_length > max_length {
-                    let error_starts_at = max_length - position.x();
-                    set_segment_subset_style(
-                        &mut segment,
-                        error_starts_at.into(),
-                        segment_length.into(),
-                        error_style(),
-                    );
+            if should_hide {
+                continue;
+            }
+
+            // Controls' content may span multiple lines, each continuing from the step's left edge
+            for (line_index, mut line) in split_segment_lines(segment).into_iter().enumerate() {
+                if line_index > 0 {
+                    rows.push((Segment::new(), None));
+                    line_column = line_start.x();
+                }
+
+                let line_width = get_segment_width(&line) as u16;
+
+                // If this line is too-long, render the tail as an error
+                if let Some(max_length) = self.max_line_length {
+                    if line_column + line_width > max_length {
+                        let available_columns = max_length.saturating_sub(line_column);
+                        let error_starts_at =
+                            get_segment_index_at_column(&line, available_columns.into());
+                        let line_length = get_segment_length(&line);
+
+                        set_segment_subset_style(
+                            &mut line,
+                            error_starts_at,
+                            line_length,
+                            error_style(),
+                        );
+                    }
                 }
+
+                line_column += line_width;
+                rows.last_mut().unwrap().0.extend(line);
+            }
+        }
+
+        let width = interface.width();
+
+        let mut cursor_position = None;
+        for (row_index, (row, row_cursor)) in rows.into_iter().enumerate() {
+            if row_index > 0 {
+                position = pos!(line_start.x(), position.y() + 1);
+                interface.clear_rest_of_line(position);
             }
 
-            if !should_hide {
-                position = render_segment(interface, position, segment);
+            let (end_position, row_cursor_position) = match self.overflow_mode {
+                OverflowMode::Wrap => {
+                    render_wrapped(interface, position, row, line_start.x(), width, row_cursor)
+                }
+                OverflowMode::Scroll => {
+                    render_scrolled(interface, position, row, width, row_cursor)
+                }
+                OverflowMode::Visible => {
+                    render_wrapped(interface, position, row, line_start.x(), None, row_cursor)
+                }
+            };
+
+            position = end_position;
+            if row_cursor_position.is_some() {
+                cursor_position = row_cursor_position;
             }
         }
 
@@ -206,31 +584,58 @@ impl Step for CompoundStep {
             interface.set_cursor(cursor_position);
         }
 
-        1
+        position.y() - line_start.y() + 1
     }
 
     fn update(
         &mut self,
         dependency_state: &mut DependencyState,
+        values: &ValueStore,
         input: KeyEvent,
     ) -> Option<InputResult> {
+        self.error = None;
+
+        // Controls capturing Enter receive it as input, leaving only Tab to advance
+        let captures_enter = self
+            .control(self.active_control)
+            .is_some_and(|control| control.captures_enter());
+
         match input.code {
-            KeyCode::Enter | KeyCode::Tab => {
-                if self.advance_control() {
-                    return Some(InputResult::AdvanceForm);
+            KeyCode::Enter | KeyCode::Tab if input.code == KeyCode::Tab || !captures_enter => {
+                // Controls awaiting or failing validation may not be advanced past
+                self.error = self.control_error(self.active_control, dependency_state);
+                if self.error.is_none() && self.advance_control() {
+                    self.error = self.line_length_error(dependency_state, values);
+                    if self.error.is_none() {
+                        return Some(InputResult::AdvanceForm);
+                    }
                 }
+
+                self.rejected |= self.error.is_some();
             }
+            // Retreating is deferred until a cancelled background task acknowledges
+            KeyCode::Esc if self.is_active_cancelling() => {}
+            KeyCode::Esc
+                if self
+                    .control_mut(self.active_control)
+                    .is_some_and(|control| control.cancel()) => {}
+            KeyCode::Esc
+                if self
+                    .control_mut(self.active_control)
+                    .is_some_and(|control| control.close_drawer()) => {}
             KeyCode::Esc | KeyCode::BackTab => {
                 if self.retreat_control() {
                     return Some(InputResult::RetreatForm);
                 }
             }
+            // A step without controls has nothing to receive other input
+            _ if self.controls.is_empty() => {}
             _ => {
-                let control = &mut self.controls[self.active_control];
-                control.update(input);
+                self.controls[self.active_control].update(input);
 
-                // If this control has an evaluation, update its dependency state
-                if let Some((id, evaluation)) = control.evaluation() {
+                // If this control has evaluations, update their dependency state
+                let control = &self.controls[self.active_control];
+                for (id, evaluation) in self.control_evaluations(self.active_control) {
                     let value = control.evaluate(&evaluation);
                     dependency_state.update_evaluation(&id, value);
                 }
@@ -240,48 +645,782 @@ impl Step for CompoundStep {
         None
     }
 
+    fn take_rejection(&mut self) -> bool {
+        let control_rejected = self
+            .control_mut(self.active_control)
+            .is_some_and(|control| control.take_rejection());
+
+        std::mem::take(&mut self.rejected) || control_rejected
+    }
+
     fn help(&self) -> Segment {
-        self.controls[self.active_control]
-            .help()
+        if let Some(error) = &self.error {
+            return Text::new_styled(error.to_string(), error_style()).as_segment();
+        }
+
+        self.control(self.active_control)
+            .and_then(|control| control.help())
             .unwrap_or(Text::new(String::new()).as_segment())
     }
 
     fn drawer(&self) -> Option<DrawerContents> {
-        self.controls[self.active_control].drawer()
+        self.control(self.active_control)
+            .and_then(|control| control.drawer())
     }
 
-    fn result(&self, dependency_state: &DependencyState) -> String {
-        let mut result = String::new();
+    fn long_help(&self) -> Option<DrawerContents> {
+        self.control(self.active_control)
+            .and_then(|control| control.long_help())
+    }
 
-        for control in &self.controls {
-            if let Some((id, action)) = control.dependency() {
-                let evaluation_result = dependency_state.get_evaluation(&id);
-                match action {
-                    Action::Hide => {
-                        if evaluation_result {
-                            continue;
-                        }
-                    }
-                    Action::Show => {
-                        if !evaluation_result {
-                            continue;
-                        }
-                    }
+    fn tick(&mut self) -> Option<InputResult> {
+        let was_cancelling = self.is_active_cancelling();
+
+        for control in &mut self.controls {
+            control.tick();
+        }
+
+        // Once the focused control's cancelled task acknowledges, complete the deferred retreat
+        let acknowledged = was_cancelling && !self.is_active_cancelling();
+        if acknowledged && self.retreat_control() {
+            return Some(InputResult::RetreatForm);
+        }
+
+        None
+    }
+
+    fn has_handle(&self) -> bool {
+        self.controls.iter().any(|control| control.has_handle())
+    }
+
+    fn refresh_interval(&self) -> Option<Duration> {
+        self.control(self.active_control)
+            .and_then(|control| control.refresh_interval())
+    }
+
+    fn validation_errors(
+        &self,
+        dependency_state: &DependencyState,
+        values: &ValueStore,
+    ) -> Vec<(usize, String)> {
+        let mut errors: Vec<_> = (0..self.controls.len())
+            .filter_map(|control_index| {
+                Some((
+                    control_index,
+                    self.control_error(control_index, dependency_state)?,
+                ))
+            })
+            .collect();
+
+        // An overlong line is attributed to the step's last input
+        if let Some(error) = self.line_length_error(dependency_state, values) {
+            let control_index = self
+                .controls
+                .iter()
+                .rposition(|control| control.focusable())
+                .unwrap_or_default();
+            errors.push((control_index, error));
+        }
+
+        errors
+    }
+
+    fn focused_control_index(&self) -> Option<usize> {
+        (!self.controls.is_empty()).then_some(self.active_control)
+    }
+
+    fn focus_control(&mut self, index: usize) {
+        if index >= self.controls.len() {
+            return;
+        }
+
+        if index != self.active_control {
+            self.blur_active_control();
+        }
+
+        self.active_control = index;
+        self.max_control = self.max_control.max(index);
+        self.controls[index].open_drawer();
+    }
+
+    fn warning(&self, dependency_state: &DependencyState, values: &ValueStore) -> Option<String> {
+        (0..self.controls.len())
+            .find_map(|control_index| self.control_error(control_index, dependency_state))
+            .or_else(|| self.line_length_warning(dependency_state, values))
+    }
+
+    fn describe(&self) -> Vec<String> {
+        let mut lines = vec!["CompoundStep".to_string()];
+
+        for (control_index, control) in self.controls.iter().enumerate() {
+            let meta = control.meta();
+
+            // Static text is described by its content, while inputs are described by their prompt
+            let segment = match meta.kind() {
+                ControlKind::Static => control.text().0,
+                _ => control.help().unwrap_or_default(),
+            };
+            let prompt: String = segment.iter().map(|text| text.content()).collect();
+
+            let evaluations = self.control_evaluations(control_index);
+            let dependencies = self.control_dependencies(control_index);
+            let edges: Vec<String> = evaluations
+                .iter()
+                .map(|(id, _)| format!("evaluates {}", id))
+                .chain(dependencies.iter().map(|(id, action)| match action {
+                    Action::Hide => format!("hidden by {}", id),
+                    Action::Show => format!("shown by {}", id),
+                    Action::Require => format!("required by {}", id),
+                }))
+                .collect();
+
+            let kind = format!("{:?}", meta.kind());
+            lines.push(describe_element(&kind, meta.name(), &prompt, &edges));
+        }
+
+        lines
+    }
+
+    fn captures_arrow(&self, _code: KeyCode) -> bool {
+        self.control(self.active_control)
+            .is_some_and(|control| control.captures_arrows())
+    }
+
+    fn skippable(&self) -> bool {
+        !self.controls.iter().any(|control| control.focusable())
+    }
+
+    fn read_only(&self) -> bool {
+        !self.controls.iter().any(|control| control.accepts_text())
+    }
+
+    fn navigation_policy(&self) -> Option<NavigationPolicy> {
+        self.navigation_policy
+    }
+
+    fn optional(&self) -> bool {
+        self.optional
+    }
+
+    fn debug_info(&self) -> Option<String> {
+        let mut info = format!(
+            "control {}/{} max {}",
+            self.active_control,
+            self.controls.len(),
+            self.max_control,
+        );
+
+        if let Some(control) = self.control(self.active_control) {
+            let meta = control.meta();
+            info.push_str(&format!(" {:?}", meta.kind()));
+
+            if let Some(name) = meta.name() {
+                info.push_str(&format!(" {}", name));
+            }
+        }
+
+        Some(info)
+    }
+
+    fn evaluation_ids(&self) -> Vec<DependencyId> {
+        (0..self.controls.len())
+            .flat_map(|control_index| self.control_evaluations(control_index))
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    fn dependency_ids(&self) -> Vec<DependencyId> {
+        (0..self.controls.len())
+            .flat_map(|control_index| self.control_dependencies(control_index))
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    fn configuration_issues(&self, index: usize) -> Vec<ConfigIssue> {
+        let mut issues = Vec::new();
+
+        if !self.controls.iter().any(|control| control.focusable()) {
+            issues.push(ConfigIssue::NoFocusableControls { step: index });
+        }
+
+        if let Some(max_line_length) = self.max_line_length {
+            let width: usize = self
+                .controls
+                .iter()
+                .filter(|control| !control.focusable())
+                .map(|control| get_segment_width(&control.text().0))
+                .sum();
+
+            if width > max_line_length.into() {
+                issues.push(ConfigIssue::StaticTextTooLong {
+                    step: index,
+                    width,
+                    max_line_length,
+                });
+            }
+        }
+
+        issues
+    }
+
+    fn controls_meta(&self) -> Vec<ControlMeta> {
+        self.controls.iter().map(|control| control.meta()).collect()
+    }
+
+    fn focused_control_meta(&self) -> Option<ControlMeta> {
+        self.control(self.active_control)
+            .map(|control| control.meta())
+    }
+
+    fn result(&self, dependency_state: &DependencyState, _values: &ValueStore) -> String {
+        self.result_of(dependency_state, |control| control.result_text())
+    }
+
+    fn redacted_result(&self, dependency_state: &DependencyState, _values: &ValueStore) -> String {
+        self.result_of(dependency_state, |control| control.redacted_result_text())
+    }
+
+    fn parse_result(&self, text: &str) -> Vec<ParsedResult> {
+        let static_contents: Vec<Option<String>> = self
+            .controls
+            .iter()
+            .map(|control| {
+                if control.focusable() {
+                    return None;
                 }
+
+                let (segment, _) = control.text();
+                let content: String = segment.iter().map(|text| text.content()).collect();
+                Some(content).filter(|content| !content.is_empty())
+            })
+            .collect();
+
+        let mut values = Vec::new();
+        let mut rest = text;
+        for (control_index, control) in self.controls.iter().enumerate() {
+            // Static text is matched literally, unless it was hidden
+            if !control.focusable() {
+                if let Some(content) = &static_contents[control_index] {
+                    rest = rest.strip_prefix(content.as_str()).unwrap_or(rest);
+                }
+
+                continue;
             }
 
-            let (segments, _) = control.text();
-            segments
+            // Inputs extend to the first subsequent static text on their line
+            let line = &rest[..rest.find('\n').unwrap_or(rest.len())];
+            let value_length = static_contents[control_index + 1..]
                 .iter()
-                .for_each(|text| result.push_str(text.content()));
+                .flatten()
+                .filter_map(|content| line.find(content.as_str()))
+                .min()
+                .unwrap_or(line.len());
+
+            if let Some(name) = control.name() {
+                let value = ControlValue::Text(rest[..value_length].to_string());
+                values.push((name.to_string(), value));
+            }
+
+            rest = &rest[value_length..];
         }
 
-        result.push('\n');
+        rest = rest.strip_prefix('\n').unwrap_or(rest);
 
-        result
+        vec![(values, text.len() - rest.len())]
+    }
+
+    fn form_values(&self) -> Vec<(String, ControlValue)> {
+        self.controls
+            .iter()
+            .filter_map(|control| Some((control.name()?.to_string(), control.form_value()?)))
+            .collect()
+    }
+
+    fn recall(&mut self, history: &History) {
+        for control in &mut self.controls {
+            control.recall(history);
+        }
+    }
+
+    fn remember(&self, history: &mut History) {
+        for control in &self.controls {
+            control.remember(history);
+        }
+    }
+
+    fn set_form_value(
+        &mut self,
+        dependency_state: &mut DependencyState,
+        name: &str,
+        value: &ControlValue,
+    ) -> bool {
+        let control_index = match self
+            .controls
+            .iter()
+            .position(|control| control.name() == Some(name))
+        {
+            Some(control_index) => control_index,
+            None => return false,
+        };
+
+        if !self.controls[control_index].set_form_value(value) {
+            return false;
+        }
+
+        let control = &self.controls[control_index];
+        for (id, evaluation) in self.control_evaluations(control_index) {
+            dependency_state.update_evaluation(&id, control.evaluate(&evaluation));
+        }
+
+        true
+    }
+
+    fn lock_form_value(&mut self, name: &str) -> bool {
+        self.controls
+            .iter_mut()
+            .find(|control| control.name() == Some(name))
+            .is_some_and(|control| control.lock())
     }
 
     fn add_to(self, form: &mut Form) {
         form.add_step(Box::new(self));
     }
 }
+
+/// Render a row of content at the specified position, wrapping it onto continuation rows starting
+/// from the specified left column if it exceeds the specified width. Returns the position after
+/// the content and, if a cursor column within the content is specified, the cursor's position.
+fn render_wrapped(
+    interface: &mut dyn RenderBackend,
+    mut position: Position,
+    mut line: Segment,
+    left: u16,
+    width: Option<u16>,
+    mut cursor_column: Option<u16>,
+) -> (Position, Option<Position>) {
+    let marker_width = get_segment_width(&Text::new(WRAP_MARKER.to_string()).as_segment()) as u16;
+
+    // Wrapping requires room for the marker and some content on each continuation row
+    let width = width.filter(|width| *width > left + marker_width);
+
+    let mut cursor_position = None;
+    loop {
+        let line_width = get_segment_width(&line) as u16;
+        let available_columns = width.map(|width| width.saturating_sub(position.x()));
+
+        let split_index = match available_columns {
+            Some(available_columns) if line_width > available_columns => {
+                get_segment_index_at_column(&line, available_columns.into())
+            }
+            _ => get_segment_length(&line),
+        };
+
+        // If nothing fits at the start of a row, render the remainder rather than wrap endlessly
+        let is_row_start = position.x() <= left + marker_width;
+        if split_index == get_segment_length(&line) || (split_index == 0 && is_row_start) {
+            if let Some(column) = cursor_column {
+                cursor_position = Some(pos!(position.x() + column, position.y()));
+            }
+
+            position = render_segment(interface, position, line);
+            break;
+        }
+
+        let (head, tail) = split_segment_at(line, split_index);
+        let head_width = get_segment_width(&head) as u16;
+
+        if let Some(column) = cursor_column {
+            if column < head_width {
+                cursor_position = Some(pos!(position.x() + column, position.y()));
+                cursor_column = None;
+            } else {
+                cursor_column = Some(column - head_width);
+            }
+        }
+
+        render_segment(interface, position, head);
+
+        position = pos!(left, position.y() + 1);
+        interface.clear_rest_of_line(position);
+        let marker = Text::new_styled(WRAP_MARKER.to_string(), muted_style()).as_segment();
+        position = render_segment(interface, position, marker);
+
+        line = tail;
+    }
+
+    (position, cursor_position)
+}
+
+/// Render a row of content at the specified position, scrolled horizontally such that the
+/// specified cursor column is visible if the row exceeds the specified width. Returns the position
+/// after the content and, if a cursor column within the content is specified, the cursor's
+/// position.
+fn render_scrolled(
+    interface: &mut dyn RenderBackend,
+    mut position: Position,
+    row: Segment,
+    width: Option<u16>,
+    cursor_column: Option<u16>,
+) -> (Position, Option<Position>) {
+    let row_width = get_segment_width(&row) as u16;
+    let available_columns = width.map(|width| width.saturating_sub(position.x()));
+
+    // Scrolling requires room for a marker at each edge and some content between them
+    let available_columns = match available_columns {
+        Some(available_columns) if row_width > available_columns && available_columns > 2 => {
+            available_columns
+        }
+        _ => {
+            let cursor_position =
+                cursor_column.map(|column| pos!(position.x() + column, position.y()));
+            return (render_segment(interface, position, row), cursor_position);
+        }
+    };
+
+    // Choose the window's first column such that the cursor remains visible, preferring to show
+    // the row's start and otherwise its end
+    let cursor = cursor_column.unwrap_or(0);
+    let content_end = row_width.max(cursor + 1);
+    let trailing_start = content_end.saturating_sub(available_columns - 1);
+    let window_start = if cursor + 1 < available_columns {
+        0
+    } else if cursor >= trailing_start {
+        trailing_start
+    } else {
+        cursor + 3 - available_columns
+    };
+
+    let window_end = if window_start == 0 {
+        available_columns - 1
+    } else {
+        window_start + available_columns - 2
+    };
+
+    let start_index = get_segment_index_at_column(&row, window_start.into());
+    let end_index = get_segment_index_at_column(&row, window_end.min(row_width).into());
+
+    let (hidden, visible) = split_segment_at(row, start_index);
+    let (visible, _) = split_segment_at(visible, end_index - start_index);
+    let hidden_width = get_segment_width(&hidden) as u16;
+
+    if window_start > 0 {
+        let marker = Text::new_styled(SCROLL_MARKER.to_string(), muted_style()).as_segment();
+        position = render_segment(interface, position, marker);
+    }
+
+    let cursor_position = cursor_column.map(|column| {
+        pos!(
+            position.x() + column.saturating_sub(hidden_width),
+            position.y()
+        )
+    });
+
+    position = render_segment(interface, position, visible);
+
+    if window_end < row_width {
+        let marker = Text::new_styled(SCROLL_MARKER.to_string(), muted_style()).as_segment();
+        position = render_segment(interface, position, marker);
+    }
+
+    (position, cursor_position)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use tty_interface::{pos, Position};
+
+    use crate::{
+        context::Context,
+        control::{Control, StaticText, TextInput},
+        dependency::{Action, DependencyId, Evaluation},
+        input::{InputEvent, KeyCode, KeyEvent},
+        step::Step,
+        test::{press, render, type_text, SizedBackend},
+        text::{DrawerContents, Segment, Text},
+        ConfigIssue, Form, FormStatus,
+    };
+
+    use super::{CompoundStep, OverflowMode};
+
+    #[test]
+    fn test_empty_compound_step() {
+        let mut form = Form::new();
+        CompoundStep::new().add_to(&mut form);
+
+        let mut step = CompoundStep::new();
+        TextInput::new("Enter a value.", false).add_to(&mut step);
+        step.add_to(&mut form);
+
+        assert_eq!(
+            vec![ConfigIssue::NoFocusableControls { step: 0 }],
+            form.validate_configuration()
+        );
+
+        // The empty step renders blank and is skipped without panicking
+        render(&mut form);
+
+        type_text(&mut form, "value");
+        assert_eq!(FormStatus::Submitted, press(&mut form, KeyCode::Enter));
+        assert_eq!("value", form.result());
+    }
+
+    #[test]
+    fn test_static_compound_step() {
+        let mut form = Form::new();
+        let mut step = CompoundStep::new();
+        StaticText::new("Nothing ").add_to(&mut step);
+        StaticText::new("to enter.").add_to(&mut step);
+        step.set_max_line_length(10);
+        step.add_to(&mut form);
+
+        assert_eq!(
+            vec![
+                ConfigIssue::NoFocusableControls { step: 0 },
+                ConfigIssue::StaticTextTooLong {
+                    step: 0,
+                    width: 17,
+                    max_line_length: 10,
+                },
+            ],
+            form.validate_configuration()
+        );
+
+        assert_eq!(FormStatus::Active, press(&mut form, KeyCode::Char('a')));
+        assert_eq!(FormStatus::Submitted, press(&mut form, KeyCode::Enter));
+        assert_eq!("Nothing to enter.", form.result());
+    }
+
+    /// A control which records its key presses, capturing Enter as a line break.
+    struct LinesInput(String);
+
+    impl Control for LinesInput {
+        fn focusable(&self) -> bool {
+            true
+        }
+
+        fn captures_enter(&self) -> bool {
+            true
+        }
+
+        fn update(&mut self, input: KeyEvent) {
+            match input.code {
+                KeyCode::Enter => self.0.push('/'),
+                KeyCode::Char(ch) => self.0.push(ch),
+                _ => {}
+            }
+        }
+
+        fn help(&self) -> Option<Segment> {
+            None
+        }
+
+        fn text(&self) -> (Segment, Option<u16>) {
+            (Text::new(self.0.clone()).as_segment(), None)
+        }
+
+        fn drawer(&self) -> Option<DrawerContents> {
+            None
+        }
+
+        fn evaluation(&self) -> Option<(DependencyId, Evaluation)> {
+            None
+        }
+
+        fn dependency(&self) -> Option<(DependencyId, Action)> {
+            None
+        }
+
+        fn evaluate(&self, _evaluation: &Evaluation) -> bool {
+            false
+        }
+
+        fn add_to(self, step: &mut CompoundStep) {
+            step.add_control(Box::new(self));
+        }
+    }
+
+    #[test]
+    fn test_control_captures_enter() {
+        let mut form = Form::new();
+        let mut step = CompoundStep::new();
+        LinesInput(String::new()).add_to(&mut step);
+        StaticText::new(" ").add_to(&mut step);
+        TextInput::new("Enter a value.", false).add_to(&mut step);
+        step.add_to(&mut form);
+
+        // Enter is input to the capturing control, and only Tab advances past it
+        type_text(&mut form, "a");
+        assert_eq!(FormStatus::Active, press(&mut form, KeyCode::Enter));
+        type_text(&mut form, "b");
+        assert_eq!(FormStatus::Active, press(&mut form, KeyCode::Tab));
+
+        // Other controls advance on Enter as usual
+        type_text(&mut form, "c");
+        assert_eq!(FormStatus::Submitted, press(&mut form, KeyCode::Enter));
+        assert_eq!("a/b c", form.result());
+    }
+
+    #[test]
+    fn test_scroll_overflow() {
+        let mut form = Form::new();
+        let mut step = CompoundStep::new();
+        step.set_overflow_mode(OverflowMode::Scroll);
+        StaticText::new("> ").add_to(&mut step);
+        TextInput::new("Enter a value.", false).add_to(&mut step);
+        step.add_to(&mut form);
+
+        type_text(&mut form, "abcdefghijklmnopqrstuvwxyz");
+
+        let render = |form: &mut Form| {
+            let mut backend = SizedBackend::new(12, 10);
+            form.render_at(&mut backend, pos!(0, 0), true);
+            backend
+        };
+
+        // The line scrolls to follow the cursor at its end, marking the hidden start
+        let backend = render(&mut form);
+        assert_eq!("…qrstuvwxyz", backend.row(1));
+        assert_eq!(Some(pos!(11, 1)), backend.cursor);
+
+        // Returning to the start scrolls back, marking the hidden end
+        for _ in 0..26 {
+            press(&mut form, KeyCode::Left);
+        }
+        let backend = render(&mut form);
+        assert_eq!("> abcdefghi…", backend.row(1));
+        assert_eq!(Some(pos!(2, 1)), backend.cursor);
+
+        assert_eq!("> abcdefghijklmnopqrstuvwxyz", form.result());
+    }
+
+    #[test]
+    fn test_cancel_running_validation() {
+        let mut form = Form::new();
+
+        let mut step = CompoundStep::new();
+        TextInput::new("Enter a summary.", false).add_to(&mut step);
+        step.add_to(&mut form);
+
+        // The validator runs until it's cancelled
+        let mut step = CompoundStep::new();
+        let mut ticket = TextInput::new("Enter a ticket.", false);
+        ticket.set_async_validator(Duration::ZERO, |_, token| {
+            while !token.is_cancelled() {
+                thread::sleep(Duration::from_millis(1));
+            }
+
+            Err("Cancelled.".to_string())
+        });
+        ticket.add_to(&mut step);
+        step.add_to(&mut form);
+
+        press(&mut form, KeyCode::Enter);
+        type_text(&mut form, "#1");
+        form.update(InputEvent::Tick);
+
+        // Esc cancels the running validator rather than retreating, ignoring further presses
+        press(&mut form, KeyCode::Esc);
+        render(&mut form).expect_screen_contains("#1 cancelling…");
+        press(&mut form, KeyCode::Esc);
+        assert_eq!(1, form.state().active_step());
+
+        // The form retreats once the validator acknowledges, discarding its result
+        let started_at = Instant::now();
+        while form.state().active_step() != 0 {
+            assert!(
+                started_at.elapsed() < Duration::from_secs(5),
+                "cancel never acknowledged"
+            );
+            thread::sleep(Duration::from_millis(1));
+            form.update(InputEvent::Tick);
+        }
+
+        let step = form
+            .step(1)
+            .and_then(|step| step.downcast_ref::<CompoundStep>());
+        let ticket = step.and_then(|step| step.control(0)).unwrap();
+        let pending = Some("Waiting for validation…".to_string());
+        assert_eq!(pending, ticket.validation_error());
+    }
+
+    /// A control which fills in the scope from its form's context as it's typed in.
+    struct ScopeInput {
+        context: Context,
+        scope: String,
+    }
+
+    impl Control for ScopeInput {
+        fn focusable(&self) -> bool {
+            true
+        }
+
+        fn initialize(&mut self, context: &Context) {
+            self.context = context.clone();
+        }
+
+        fn update(&mut self, _input: KeyEvent) {
+            if let Some(scope) = self.context.get::<String>() {
+                self.scope = scope.clone();
+            }
+        }
+
+        fn help(&self) -> Option<Segment> {
+            None
+        }
+
+        fn text(&self) -> (Segment, Option<u16>) {
+            (Text::new(self.scope.clone()).as_segment(), None)
+        }
+
+        fn drawer(&self) -> Option<DrawerContents> {
+            None
+        }
+
+        fn evaluation(&self) -> Option<(DependencyId, Evaluation)> {
+            None
+        }
+
+        fn dependency(&self) -> Option<(DependencyId, Action)> {
+            None
+        }
+
+        fn evaluate(&self, _evaluation: &Evaluation) -> bool {
+            false
+        }
+
+        fn add_to(self, step: &mut CompoundStep) {
+            step.add_control(Box::new(self));
+        }
+    }
+
+    #[test]
+    fn test_controls_receive_context() {
+        let scope_form = || {
+            let mut form = Form::new();
+            let mut step = CompoundStep::new();
+            ScopeInput {
+                context: Context::new(),
+                scope: String::new(),
+            }
+            .add_to(&mut step);
+            step.add_to(&mut form);
+            form
+        };
+
+        let mut form = scope_form();
+        form.set_context(String::from("api"));
+        type_text(&mut form, "x");
+        assert_eq!("api", form.result());
+
+        // Without context data of the expected type, the control is left to its own defaults
+        let mut form = scope_form();
+        form.set_context(42);
+        type_text(&mut form, "x");
+        assert_eq!("", form.result());
+    }
+}
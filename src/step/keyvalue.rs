@@ -1,50 +1,257 @@
-use crossterm::event::{KeyCode, KeyEvent};
-use tty_interface::{pos, Interface, Position};
+use tty_interface::{pos, Position};
 use tty_text::Key;
 
 use crate::{
-    dependency::{DependencyId, DependencyState, Evaluation},
-    style::help_style,
+    backend::RenderBackend,
+    context::Context,
+    dependency::{evaluate_value, DependencyId, DependencyState, Evaluation},
+    input::{KeyCode, KeyEvent},
+    navigation::NavigationPolicy,
+    style::{error_style, help_style},
     text::{DrawerContents, Segment, Text},
+    utility::{describe_element, entry_constraint_error, text_with_value},
+    value::{ControlValue, ValueStore},
     Form,
 };
 
-use super::{InputResult, Step};
+use super::{InputResult, ParsedResult, Step};
 
 /// A key-value-pair set entry step.
 pub struct KeyValueStep {
+    name: Option<String>,
     prompt: String,
     pairs: Vec<(tty_text::Text, tty_text::Text)>,
     focused_pair: usize,
     key_focused: bool,
     evaluation: Option<(DependencyId, Evaluation)>,
+    required: bool,
+    min_entries: usize,
+    error: Option<String>,
+    navigation_policy: Option<NavigationPolicy>,
+    optional: bool,
+    locked: bool,
 }
 
 impl KeyValueStep {
     pub fn new(prompt: &str) -> Self {
         Self {
+            name: None,
             prompt: prompt.to_string(),
             pairs: vec![(tty_text::Text::new(false), tty_text::Text::new(false))],
             focused_pair: 0,
             key_focused: true,
             evaluation: None,
+            required: false,
+            min_entries: 0,
+            error: None,
+            navigation_policy: None,
+            optional: false,
+            locked: false,
         }
     }
 
+    /// Set this step's name, by which its value may be retrieved from the form.
+    pub fn set_name(&mut self, name: &str) {
+        self.name = Some(name.to_string());
+    }
+
     pub fn set_evaluation(&mut self, evaluation: Evaluation) -> DependencyId {
         let id = DependencyId::new();
         self.evaluation = Some((id, evaluation));
         id
     }
+
+    /// Override the form's navigation policy while this step is active.
+    pub fn set_navigation_policy(&mut self, policy: NavigationPolicy) {
+        self.navigation_policy = Some(policy);
+    }
+
+    /// Set whether this step may be skipped with the form's skip keys, leaving it empty.
+    pub fn set_optional(&mut self, optional: bool) {
+        self.optional = optional;
+    }
+
+    /// Set whether at least one pair must be entered before the form may advance.
+    pub fn set_required(&mut self, required: bool) {
+        self.required = required;
+    }
+
+    /// Set the minimum number of pairs required before the form may advance.
+    pub fn set_min_entries(&mut self, min_entries: usize) {
+        self.min_entries = min_entries;
+    }
+
+    /// Handle the specified input, returning the resulting form action, if any.
+    fn handle_input(&mut self, input: KeyEvent) -> Option<InputResult> {
+        self.error = None;
+
+        let entries = self
+            .pairs
+            .iter()
+            .filter(|(key, _)| !key.value().is_empty())
+            .count();
+
+        let text_is_empty = self.focused_text().value().is_empty();
+
+        match input.code {
+            KeyCode::Enter | KeyCode::Tab => {
+                if self.key_focused && text_is_empty {
+                    self.error = entry_constraint_error(entries, self.required, self.min_entries);
+                    if self.error.is_some() {
+                        return None;
+                    }
+
+                    // Discard the trailing empty pair before advancing past this step
+                    if self.focused_pair > 0 {
+                        self.remove_pair();
+                    }
+
+                    return Some(InputResult::AdvanceForm);
+                }
+
+                self.focus_next();
+            }
+            KeyCode::Esc | KeyCode::BackTab => {
+                let focus_moved = self.focus_prev();
+                if !focus_moved {
+                    return Some(InputResult::RetreatForm);
+                }
+            }
+            KeyCode::Char(ch) => self.focused_text().handle_input(Key::Char(ch)),
+            KeyCode::Backspace => {
+                if !text_is_empty {
+                    self.focused_text().handle_input(Key::Backspace);
+                } else if !self.key_focused {
+                    self.key_focused = true;
+                } else if self.focused_pair > 0 {
+                    self.remove_pair();
+                } else {
+                    return Some(InputResult::RetreatForm);
+                }
+            }
+            KeyCode::Left => self.focused_text().handle_input(Key::Left),
+            KeyCode::Right => self.focused_text().handle_input(Key::Right),
+            _ => {}
+        };
+
+        None
+    }
+
+    /// Each pair's key and value, if it has one, on its own line.
+    fn pairs_text(&self) -> String {
+        let mut result = String::new();
+
+        for (key, value) in &self.pairs {
+            result.push_str(&key.value());
+
+            if !value.value().is_empty() {
+                result.push_str(&format!(": {}", value.value()));
+            }
+
+            result.push('\n');
+        }
+
+        result
+    }
+
+    /// Update this step's dependency evaluation, if it has one, from its entered pairs.
+    fn update_evaluation(&self, dependency_state: &mut DependencyState) {
+        if let Some((id, evaluation)) = &self.evaluation {
+            let value = evaluate_value(self.pairs_text().trim_end(), evaluation);
+            dependency_state.update_evaluation(id, value);
+        }
+    }
+
+    /// The focused pair's key or value text.
+    fn focused_text(&mut self) -> &mut tty_text::Text {
+        let (key, value) = &mut self.pairs[self.focused_pair];
+        if self.key_focused {
+            key
+        } else {
+            value
+        }
+    }
+
+    /// Append an empty pair after the last pair.
+    fn add_pair(&mut self) {
+        self.pairs
+            .push((tty_text::Text::new(false), tty_text::Text::new(false)));
+    }
+
+    /// Remove the focused pair, focusing the previous pair's value. The only pair is instead
+    /// cleared, such that there's always a pair to edit.
+    fn remove_pair(&mut self) {
+        if self.pairs.len() == 1 {
+            self.pairs[0] = (tty_text::Text::new(false), tty_text::Text::new(false));
+            self.focused_pair = 0;
+            self.key_focused = true;
+            return;
+        }
+
+        self.pairs.remove(self.focused_pair);
+
+        if self.focused_pair > 0 {
+            self.focused_pair -= 1;
+            self.key_focused = false;
+        } else {
+            self.key_focused = true;
+        }
+    }
+
+    /// Focus the next field: a pair's value after its key, otherwise the next pair's key, adding
+    /// a pair if the last pair is focused.
+    fn focus_next(&mut self) {
+        if self.key_focused {
+            self.key_focused = false;
+            return;
+        }
+
+        if self.focused_pair + 1 == self.pairs.len() {
+            self.add_pair();
+        }
+
+        self.focused_pair += 1;
+        self.key_focused = true;
+    }
+
+    /// Focus the previous field: a pair's key before its value, otherwise the previous pair's
+    /// value. Returns whether focus moved, being false if the first pair's key is focused.
+    fn focus_prev(&mut self) -> bool {
+        if !self.key_focused {
+            self.key_focused = true;
+            return true;
+        }
+
+        if self.focused_pair == 0 {
+            return false;
+        }
+
+        self.focused_pair -= 1;
+        self.key_focused = false;
+
+        true
+    }
 }
 
 impl Step for KeyValueStep {
-    fn initialize(&mut self, _dependency_state: &mut DependencyState, _index: usize) {}
+    fn initialize(
+        &mut self,
+        dependency_state: &mut DependencyState,
+        _context: &Context,
+        index: usize,
+    ) {
+        if let Some((id, _)) = &self.evaluation {
+            dependency_state.register_evaluation(id, index, 0);
+        }
+
+        self.update_evaluation(dependency_state);
+    }
 
     fn render(
         &self,
-        interface: &mut Interface,
+        interface: &mut dyn RenderBackend,
         _dependency_state: &DependencyState,
+        _values: &ValueStore,
         mut position: Position,
         is_focused: bool,
     ) -> u16 {
@@ -58,14 +265,13 @@ impl Step for KeyValueStep {
             interface.set(position, &line);
 
             if is_focused && pair_index == self.focused_pair {
-                let cursor = pos!(
-                    if self.key_focused {
-                        key.cursor().0
-                    } else {
-                        key.value().len() + 2 + value.cursor().0
-                    } as u16,
-                    position.y()
-                );
+                let cursor_column = if self.key_focused {
+                    key.cursor().0
+                } else {
+                    key.value().len() + 2 + value.cursor().0
+                } as u16;
+
+                let cursor = pos!(position.x() + cursor_column, position.y());
 
                 interface.set_cursor(Some(cursor));
             }
@@ -78,113 +284,284 @@ impl Step for KeyValueStep {
 
     fn update(
         &mut self,
-        _dependency_state: &mut DependencyState,
+        dependency_state: &mut DependencyState,
+        _values: &ValueStore,
         input: KeyEvent,
     ) -> Option<InputResult> {
-        let text = if self.key_focused {
-            &mut self.pairs[self.focused_pair].0
-        } else {
-            &mut self.pairs[self.focused_pair].1
-        };
-
-        match input.code {
-            KeyCode::Enter | KeyCode::Tab => {
-                if text.value().is_empty() {
-                    if self.key_focused {
-                        if self.focused_pair > 0 {
-                            self.pairs.remove(self.focused_pair);
-                            self.focused_pair -= 1;
-                        }
-
-                        // Advance past this step
-                        return Some(InputResult::AdvanceForm);
-                    } else {
-                        // Append a new KVP
-                        self.key_focused = true;
-                        self.focused_pair += 1;
-                        if self.focused_pair == self.pairs.len() {
-                            self.pairs
-                                .push((tty_text::Text::new(false), tty_text::Text::new(false)));
-                        }
-                    }
-                } else {
-                    if self.key_focused {
-                        // Switch to the value entry
-                        self.key_focused = false;
-                    } else {
-                        // Append a new KVP
-                        self.key_focused = true;
-                        self.focused_pair += 1;
-                        if self.focused_pair == self.pairs.len() {
-                            self.pairs
-                                .push((tty_text::Text::new(false), tty_text::Text::new(false)));
-                        }
-                    }
-                }
-            }
-            KeyCode::Esc | KeyCode::BackTab => {
-                if !self.key_focused {
-                    self.key_focused = true;
-                } else {
-                    if self.focused_pair > 0 {
-                        self.focused_pair -= 1;
-                        self.key_focused = false;
-                    } else {
-                        return Some(InputResult::RetreatForm);
-                    }
-                }
-            }
-            KeyCode::Char(ch) => text.handle_input(Key::Char(ch)),
-            KeyCode::Backspace => {
-                if text.value().is_empty() {
-                    if !self.key_focused {
-                        self.key_focused = true;
-                    } else {
-                        if self.focused_pair > 0 {
-                            self.pairs.remove(self.focused_pair);
-                            self.focused_pair -= 1;
-                            self.key_focused = false;
-                        } else {
-                            return Some(InputResult::RetreatForm);
-                        }
-                    }
-                } else {
-                    text.handle_input(Key::Backspace);
-                }
-            }
-            KeyCode::Left => text.handle_input(Key::Left),
-            KeyCode::Right => text.handle_input(Key::Right),
-            _ => {}
-        };
-
-        None
+        let result = self.handle_input(input);
+        self.update_evaluation(dependency_state);
+        result
     }
 
     fn help(&self) -> Segment {
+        if let Some(error) = &self.error {
+            return Text::new_styled(error.to_string(), error_style()).as_segment();
+        }
+
         Text::new_styled(self.prompt.to_string(), help_style()).as_segment()
     }
 
+    fn describe(&self) -> Vec<String> {
+        let edges: Vec<String> = self
+            .evaluation
+            .iter()
+            .map(|(id, _)| format!("evaluates {}", id))
+            .collect();
+
+        vec![describe_element(
+            "KeyValueStep",
+            self.name.as_deref(),
+            &self.prompt,
+            &edges,
+        )]
+    }
+
     fn drawer(&self) -> Option<DrawerContents> {
         None
     }
 
-    fn result(&self, _dependency_state: &DependencyState) -> String {
-        let mut result = String::new();
+    fn navigation_policy(&self) -> Option<NavigationPolicy> {
+        self.navigation_policy
+    }
 
-        for (_, (key, value)) in self.pairs.iter().enumerate() {
-            result.push_str(&key.value());
+    fn skippable(&self) -> bool {
+        self.locked
+    }
 
-            if !value.value().is_empty() {
-                result.push_str(&format!(": {}", value.value()));
+    fn optional(&self) -> bool {
+        self.optional
+    }
+
+    fn result(&self, _dependency_state: &DependencyState, _values: &ValueStore) -> String {
+        self.pairs_text()
+    }
+
+    fn parse_result(&self, text: &str) -> Vec<ParsedResult> {
+        // Each pair is a line, so the step may have consumed any number of consecutive lines. Only
+        // pairs with values are recognized, as others are indistinguishable from plain text.
+        let mut line_ends = vec![0];
+        for line in text.split_inclusive('\n') {
+            if !line.contains(": ") {
+                break;
             }
 
-            result.push('\n');
+            line_ends.push(line_ends[line_ends.len() - 1] + line.len());
         }
 
-        result
+        // Prefer consuming the fewest lines, such that subsequent steps' results aren't consumed
+        line_ends
+            .into_iter()
+            .map(|consumed| {
+                let pairs = text[..consumed]
+                    .lines()
+                    .filter_map(|line| line.split_once(": "))
+                    .map(|(key, value)| (key.to_string(), value.to_string()))
+                    .collect();
+
+                let values = match &self.name {
+                    Some(name) => vec![(name.to_string(), ControlValue::Pairs(pairs))],
+                    None => Vec::new(),
+                };
+
+                (values, consumed)
+            })
+            .collect()
+    }
+
+    fn evaluation_ids(&self) -> Vec<DependencyId> {
+        self.evaluation.iter().map(|(id, _)| *id).collect()
+    }
+
+    fn form_values(&self) -> Vec<(String, ControlValue)> {
+        match &self.name {
+            Some(name) => vec![(
+                name.to_string(),
+                ControlValue::Pairs(
+                    self.pairs
+                        .iter()
+                        .filter(|(key, _)| !key.value().is_empty())
+                        .map(|(key, value)| (key.value(), value.value()))
+                        .collect(),
+                ),
+            )],
+            None => Vec::new(),
+        }
+    }
+
+    fn set_form_value(
+        &mut self,
+        dependency_state: &mut DependencyState,
+        name: &str,
+        value: &ControlValue,
+    ) -> bool {
+        let pairs = match value.as_pairs() {
+            Some(pairs) if self.name.as_deref() == Some(name) => pairs,
+            _ => return false,
+        };
+
+        self.pairs = pairs
+            .iter()
+            .map(|(key, value)| (text_with_value(key, false), text_with_value(value, false)))
+            .collect();
+
+        if self.pairs.is_empty() {
+            self.add_pair();
+        }
+
+        self.focused_pair = 0;
+        self.key_focused = true;
+        self.update_evaluation(dependency_state);
+
+        true
+    }
+
+    fn lock_form_value(&mut self, name: &str) -> bool {
+        if self.name.as_deref() != Some(name) {
+            return false;
+        }
+
+        self.locked = true;
+        true
     }
 
     fn add_to(self, form: &mut Form) {
         form.add_step(Box::new(self));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        dependency::DependencyState,
+        input::{KeyCode, KeyEvent, KeyModifiers},
+        step::{InputResult, Step},
+        value::ValueStore,
+    };
+
+    use super::KeyValueStep;
+
+    fn press(step: &mut KeyValueStep, codes: &[KeyCode]) -> Option<InputResult> {
+        let mut dependency_state = DependencyState::new();
+        let values = ValueStore::new();
+
+        let mut result = None;
+        for code in codes {
+            let input = KeyEvent::new(*code, KeyModifiers::NONE);
+            result = step.update(&mut dependency_state, &values, input);
+        }
+
+        result
+    }
+
+    fn pairs(step: &KeyValueStep) -> Vec<(String, String)> {
+        step.pairs
+            .iter()
+            .map(|(key, value)| (key.value(), value.value()))
+            .collect()
+    }
+
+    #[test]
+    fn test_navigation() {
+        let mut step = KeyValueStep::new("Enter pairs:");
+        press(
+            &mut step,
+            &[
+                KeyCode::Char('a'),
+                KeyCode::Tab,
+                KeyCode::Char('1'),
+                KeyCode::Tab,
+            ],
+        );
+
+        assert_eq!(1, step.focused_pair);
+        assert!(step.key_focused);
+        assert_eq!(
+            vec![
+                ("a".to_string(), "1".to_string()),
+                (String::new(), String::new())
+            ],
+            pairs(&step)
+        );
+
+        assert_eq!(None, press(&mut step, &[KeyCode::Esc, KeyCode::Esc]));
+        assert_eq!(0, step.focused_pair);
+        assert!(step.key_focused);
+
+        assert_eq!(
+            Some(InputResult::RetreatForm),
+            press(&mut step, &[KeyCode::Esc])
+        );
+    }
+
+    #[test]
+    fn test_remove_pair() {
+        let mut step = KeyValueStep::new("Enter pairs:");
+        press(&mut step, &[KeyCode::Char('a'), KeyCode::Tab, KeyCode::Tab]);
+
+        assert_eq!(None, press(&mut step, &[KeyCode::Backspace]));
+        assert_eq!(0, step.focused_pair);
+        assert!(!step.key_focused);
+        assert_eq!(vec![("a".to_string(), String::new())], pairs(&step));
+
+        step.remove_pair();
+        step.remove_pair();
+        assert_eq!(vec![(String::new(), String::new())], pairs(&step));
+        assert_eq!(0, step.focused_pair);
+        assert!(step.key_focused);
+    }
+
+    #[test]
+    fn test_advance_discards_trailing_pair() {
+        let mut step = KeyValueStep::new("Enter pairs:");
+        let result = press(
+            &mut step,
+            &[
+                KeyCode::Char('a'),
+                KeyCode::Tab,
+                KeyCode::Tab,
+                KeyCode::Enter,
+            ],
+        );
+
+        assert_eq!(Some(InputResult::AdvanceForm), result);
+        assert_eq!(vec![("a".to_string(), String::new())], pairs(&step));
+        assert_eq!(0, step.focused_pair);
+
+        // Returning to the step and advancing again mustn't remove the remaining pair
+        let result = press(&mut step, &[KeyCode::Enter, KeyCode::Enter]);
+        assert_eq!(Some(InputResult::AdvanceForm), result);
+        assert_eq!(vec![("a".to_string(), String::new())], pairs(&step));
+    }
+
+    #[test]
+    fn test_entry_constraints() {
+        let mut step = KeyValueStep::new("Enter pairs:");
+        step.set_required(true);
+        step.set_min_entries(2);
+
+        assert_eq!(None, press(&mut step, &[KeyCode::Enter]));
+        assert_eq!(Some("A response is required."), step.error.as_deref());
+
+        let first_pair = [KeyCode::Char('a'), KeyCode::Tab, KeyCode::Tab];
+        assert_eq!(None, press(&mut step, &first_pair));
+        assert_eq!(None, step.error);
+
+        assert_eq!(None, press(&mut step, &[KeyCode::Enter]));
+        assert_eq!(
+            Some("At least 2 entries are required."),
+            step.error.as_deref()
+        );
+
+        let result = press(
+            &mut step,
+            &[
+                KeyCode::Char('b'),
+                KeyCode::Tab,
+                KeyCode::Tab,
+                KeyCode::Enter,
+            ],
+        );
+        assert_eq!(Some(InputResult::AdvanceForm), result);
+        assert_eq!(None, step.error);
+    }
+}
@@ -1,16 +1,107 @@
-use crossterm::event::{KeyCode, KeyEvent};
-use tty_interface::{pos, Interface, Position};
+use tty_interface::{pos, Position};
 use tty_text::Key;
 
 use crate::{
+    backend::RenderBackend,
+    context::Context,
     dependency::DependencyState,
+    input::{KeyCode, KeyEvent},
+    keybinding::KeyBinding,
+    markdown::{continuation_prefix, is_code_fence, normalize_lists, style_line},
+    navigation::NavigationPolicy,
+    postprocess::wrap_lines,
     style::{error_style, help_style},
-    text::{set_segment_subset_style, DrawerContents, Segment, Text},
-    utility::render_segment,
+    text::{
+        expand_tabs,
+        util::{
+            get_segment_index_at_column, get_segment_length, get_segment_width,
+            set_segment_subset_style,
+        },
+        DrawerContents, Segment, Text,
+    },
+    utility::{
+        delete_paired, describe_element, entry_constraint_error, insert_paired, render_segment,
+        text_with_value,
+    },
+    value::{ControlValue, ValueStore},
     Form,
 };
 
-use super::{InputResult, Step};
+use super::{InputResult, ParsedResult, Step};
+
+/// How a text block's entry is submitted to advance the form.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum SubmitPolicy {
+    /// Entering two consecutive blank lines submits, removing them if trailing whitespace is
+    /// trimmed.
+    #[default]
+    DoubleBlankLine,
+    /// Ctrl+D submits, leaving Enter to insert any number of blank lines.
+    CtrlD,
+    /// Ctrl+Enter submits, leaving Enter to insert any number of blank lines. Not all terminals
+    /// report the modifier with Enter.
+    CtrlEnter,
+    /// The specified key submits, leaving Enter to insert any number of blank lines.
+    ExplicitKey(KeyBinding),
+}
+
+impl SubmitPolicy {
+    /// The key which submits under this policy, if it's an explicit chord.
+    fn binding(&self) -> Option<KeyBinding> {
+        match self {
+            SubmitPolicy::DoubleBlankLine => None,
+            SubmitPolicy::CtrlD => Some(KeyBinding::ctrl(KeyCode::Char('d'))),
+            SubmitPolicy::CtrlEnter => Some(KeyBinding::ctrl(KeyCode::Enter)),
+            SubmitPolicy::ExplicitKey(binding) => Some(*binding),
+        }
+    }
+}
+
+/// What pressing Tab does in a text block. Shift+Tab always retreats.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum TabInput {
+    /// Tab only submits the entry, along with Enter, under the double-blank-line submit policy.
+    #[default]
+    Navigate,
+    /// Tab inserts a literal tab character, rendered up to the next stop of the specified width.
+    Literal(u8),
+    /// Tab inserts the specified number of spaces.
+    Spaces(u8),
+}
+
+/// Which trailing whitespace is trimmed from a text block's entry as it's submitted.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum WhitespaceTrim {
+    /// Keep all whitespace as entered, including any blank lines entered to submit.
+    None,
+    /// Trim trailing whitespace from each line, keeping any blank lines.
+    Lines,
+    /// Trim trailing whitespace from the end of the block, including any trailing blank lines.
+    #[default]
+    Block,
+    /// Trim trailing whitespace from each line and from the end of the block.
+    All,
+}
+
+impl WhitespaceTrim {
+    /// Trim the specified text according to this policy.
+    fn apply(&self, text: &str) -> String {
+        let trim_lines = matches!(self, WhitespaceTrim::Lines | WhitespaceTrim::All);
+        let trim_block = matches!(self, WhitespaceTrim::Block | WhitespaceTrim::All);
+
+        let mut trimmed = text.to_string();
+        if trim_lines {
+            let lines: Vec<&str> = text.split('\n').map(str::trim_end).collect();
+            trimmed = lines.join("\n");
+        }
+
+        if trim_block {
+            trimmed.truncate(trimmed.trim_end().len());
+        }
+
+        trimmed
+    }
+}
 
 /// A multi-line text input step.
 ///
@@ -28,27 +119,64 @@ use super::{InputResult, Step};
 /// step.add_to(&mut form);
 /// ```
 pub struct TextBlockStep {
+    name: Option<String>,
     prompt: String,
     text: tty_text::Text,
     top_margin: Option<u16>,
     bottom_margin: Option<u16>,
     max_line_length: Option<u16>,
-    trim_trailing_whitespace: bool,
+    max_visible_lines: Option<u16>,
+    scroll_offset: usize,
+    whitespace_trim: WhitespaceTrim,
+    markdown: bool,
+    normalize_lists: bool,
+    required: bool,
+    min_entries: usize,
+    error: Option<String>,
+    navigation_policy: Option<NavigationPolicy>,
+    optional: bool,
+    locked: bool,
+    submit_policy: SubmitPolicy,
+    tab_input: TabInput,
+    auto_indent: bool,
+    auto_pair: bool,
+    result_wrap: Option<u16>,
 }
 
 impl TextBlockStep {
     /// Create a new, default text block step.
     pub fn new(prompt: &str) -> Self {
         Self {
+            name: None,
             prompt: prompt.to_string(),
             text: tty_text::Text::new(true),
             top_margin: None,
             bottom_margin: None,
             max_line_length: None,
-            trim_trailing_whitespace: true,
+            max_visible_lines: None,
+            scroll_offset: 0,
+            whitespace_trim: WhitespaceTrim::default(),
+            markdown: false,
+            normalize_lists: false,
+            required: false,
+            min_entries: 0,
+            error: None,
+            navigation_policy: None,
+            optional: false,
+            locked: false,
+            submit_policy: SubmitPolicy::default(),
+            tab_input: TabInput::default(),
+            auto_indent: false,
+            auto_pair: false,
+            result_wrap: None,
         }
     }
 
+    /// Set this step's name, by which its value may be retrieved from the form.
+    pub fn set_name(&mut self, name: &str) {
+        self.name = Some(name.to_string());
+    }
+
     /// Set this text block's top and bottom margins.
     pub fn set_margins(&mut self, top_margin: Option<u16>, bottom_margin: Option<u16>) {
         self.top_margin = top_margin;
@@ -60,19 +188,180 @@ impl TextBlockStep {
         self.max_line_length = Some(max_length);
     }
 
-    /// Set whether this text block should trim trailing whitespace.
+    /// Hard-wrap this text block's result at word boundaries to the specified width, leaving its
+    /// on-screen entry unwrapped. Words longer than the width are left intact.
+    pub fn set_result_wrap(&mut self, width: u16) {
+        self.result_wrap = Some(width);
+    }
+
+    /// Set this text block's maximum visible height, beyond which its lines scroll to keep the
+    /// cursor visible.
+    pub fn set_max_visible_lines(&mut self, max_lines: u16) {
+        self.max_visible_lines = Some(max_lines.max(1));
+    }
+
+    /// Scroll the visible lines, if limited, so that the cursor's line remains visible.
+    fn update_scroll_offset(&mut self) {
+        let max_lines = match self.max_visible_lines {
+            Some(max_lines) => max_lines as usize,
+            None => return,
+        };
+
+        let (_, cursor_line) = self.text.cursor();
+        if cursor_line < self.scroll_offset {
+            self.scroll_offset = cursor_line;
+        } else if cursor_line >= self.scroll_offset + max_lines {
+            self.scroll_offset = cursor_line + 1 - max_lines;
+        }
+
+        let line_count = self.text.lines().len();
+        self.scroll_offset = self.scroll_offset.min(line_count.saturating_sub(max_lines));
+    }
+
+    /// Set whether this text block should trim trailing whitespace from the end of the block as
+    /// it's submitted, equivalent to [WhitespaceTrim::Block] or [WhitespaceTrim::None].
     pub fn set_trim_trailing_whitespace(&mut self, trim: bool) {
-        self.trim_trailing_whitespace = trim;
+        self.whitespace_trim = if trim {
+            WhitespaceTrim::Block
+        } else {
+            WhitespaceTrim::None
+        };
+    }
+
+    /// Set which trailing whitespace is trimmed from this text block's entry as it's submitted. By
+    /// default, trailing whitespace is trimmed from the end of the block, removing any blank lines
+    /// entered to submit.
+    pub fn set_whitespace_trim(&mut self, trim: WhitespaceTrim) {
+        self.whitespace_trim = trim;
+    }
+
+    /// Set whether this text block's content is styled as Markdown as it's entered.
+    pub fn set_markdown(&mut self, markdown: bool) {
+        self.markdown = markdown;
+    }
+
+    /// Set whether, in Markdown mode, list bullets and numbering are normalized upon advancing.
+    pub fn set_normalize_lists(&mut self, normalize: bool) {
+        self.normalize_lists = normalize;
+    }
+
+    /// Override the form's navigation policy while this step is active.
+    pub fn set_navigation_policy(&mut self, policy: NavigationPolicy) {
+        self.navigation_policy = Some(policy);
+    }
+
+    /// Set whether this step may be skipped with the form's skip keys, leaving it empty.
+    pub fn set_optional(&mut self, optional: bool) {
+        self.optional = optional;
+    }
+
+    /// Set how this text block's entry is submitted. By default, two consecutive blank lines submit.
+    pub fn set_submit_policy(&mut self, policy: SubmitPolicy) {
+        self.submit_policy = policy;
+    }
+
+    /// Set what pressing Tab does in this text block. By default, Tab doesn't insert text.
+    pub fn set_tab_input(&mut self, tab_input: TabInput) {
+        self.tab_input = tab_input;
+    }
+
+    /// Set whether pressing Enter carries the current line's indentation and list marker onto the
+    /// new line, numbering ordered items sequentially. Pressing Enter on a list item without content
+    /// removes its marker instead.
+    pub fn set_auto_indent(&mut self, auto_indent: bool) {
+        self.auto_indent = auto_indent;
+    }
+
+    /// Set whether typing an opening bracket or quote also inserts its closing character, which is
+    /// typed over rather than duplicated.
+    pub fn set_auto_pair(&mut self, auto_pair: bool) {
+        self.auto_pair = auto_pair;
+    }
+
+    /// Start a new line, continuing the current line's indentation and list marker if enabled.
+    fn insert_line(&mut self) {
+        if !self.auto_indent {
+            self.text.handle_input(Key::Enter);
+            return;
+        }
+
+        let (column, line_index) = self.text.cursor();
+        let line = self.text.lines()[line_index].clone();
+
+        match continuation_prefix(&line) {
+            Some(prefix) => {
+                self.text.handle_input(Key::Enter);
+                for ch in prefix.chars() {
+                    self.text.handle_input(Key::Char(ch));
+                }
+            }
+            // An empty list item's marker is removed rather than continued
+            None => {
+                for _ in 0..column {
+                    self.text.handle_input(Key::Backspace);
+                }
+            }
+        }
+    }
+
+    /// The specified line as displayed, with any literal tabs expanded.
+    fn display_line(&self, line: &str) -> String {
+        match self.tab_input {
+            TabInput::Literal(tab_width) => expand_tabs(line, tab_width.into()),
+            _ => line.to_string(),
+        }
+    }
+
+    /// Validate this text block's entry and tidy it for submission, returning whether it may be
+    /// submitted. Trailing whitespace is trimmed according to the step's trim policy.
+    fn prepare_submission(&mut self) -> bool {
+        let lines = self.text.lines().to_vec();
+        let entries = lines.iter().filter(|line| !line.trim().is_empty()).count();
+        self.error = entry_constraint_error(entries, self.required, self.min_entries);
+        if self.error.is_some() {
+            return false;
+        }
+
+        if self.whitespace_trim != WhitespaceTrim::None {
+            let trimmed = self.whitespace_trim.apply(&self.text.value());
+            self.text = text_with_value(&trimmed, true);
+        }
+
+        if self.markdown && self.normalize_lists {
+            let normalized = normalize_lists(&self.text.value());
+            self.text = text_with_value(&normalized, true);
+        }
+
+        self.update_scroll_offset();
+
+        true
+    }
+
+    /// Set whether this text block must have content before the form may advance.
+    pub fn set_required(&mut self, required: bool) {
+        self.required = required;
+    }
+
+    /// Set the minimum number of non-blank lines required before the form may advance.
+    pub fn set_min_entries(&mut self, min_entries: usize) {
+        self.min_entries = min_entries;
     }
 }
 
 impl Step for TextBlockStep {
-    fn initialize(&mut self, _dependency_state: &mut DependencyState, _index: usize) {}
+    fn initialize(
+        &mut self,
+        _dependency_state: &mut DependencyState,
+        _context: &Context,
+        _index: usize,
+    ) {
+    }
 
     fn render(
         &self,
-        interface: &mut Interface,
+        interface: &mut dyn RenderBackend,
         _dependency_state: &DependencyState,
+        _values: &ValueStore,
         position: Position,
         is_focused: bool,
     ) -> u16 {
@@ -83,74 +372,116 @@ impl Step for TextBlockStep {
         let mut offset_y = 0;
         if let Some(top_margin) = self.top_margin {
             for line in 0..top_margin {
-                interface.clear_line(position.y() + line);
+                interface.clear_rest_of_line(pos!(position.x(), position.y() + line));
             }
 
             offset_y += top_margin;
         }
 
         let lines = self.text.lines();
-        for (line_index, line) in lines.iter().enumerate() {
-            let line_position = pos!(0, position.y() + line_index as u16 + offset_y);
+        let visible_count = match self.max_visible_lines {
+            Some(max_lines) => lines.len().min(max_lines as usize),
+            None => lines.len(),
+        };
+        let scroll_offset = self.scroll_offset.min(lines.len() - visible_count);
+        let visible_lines = &lines[scroll_offset..scroll_offset + visible_count];
+
+        // Determine which lines fall within fenced code blocks for Markdown styling
+        let mut in_code_block = false;
+        let code_block_lines: Vec<bool> = lines
+            .iter()
+            .map(|line| {
+                let line_in_code_block = in_code_block;
+                if self.markdown && is_code_fence(line) {
+                    in_code_block = !in_code_block;
+                }
+
+                line_in_code_block
+            })
+            .collect();
+
+        for (line_index, line) in visible_lines.iter().enumerate() {
+            let line_position = pos!(position.x(), position.y() + line_index as u16 + offset_y);
+            let line = &self.display_line(line);
+
+            let mut segment = if self.markdown {
+                style_line(line, code_block_lines[scroll_offset + line_index])
+            } else {
+                Text::new(line.to_string()).as_segment()
+            };
 
             // If the line exceeds the max length, render the tail as an error
             if let Some(max_length) = self.max_line_length {
-                let line_length = line.len() as u16;
-                if line_length > max_length {
-                    let mut segment = Text::new(line.to_string()).as_segment();
+                if get_segment_width(&segment) > max_length.into() {
+                    let error_starts_at = get_segment_index_at_column(&segment, max_length.into());
+                    let line_length = get_segment_length(&segment);
 
                     set_segment_subset_style(
                         &mut segment,
-                        max_length.into(),
-                        line_length.into(),
+                        error_starts_at,
+                        line_length,
                         error_style(),
                     );
-
-                    render_segment(interface, line_position, segment);
-                    continue;
                 }
             }
 
-            interface.set(line_position, line);
+            render_segment(interface, line_position, segment);
         }
 
         if is_focused {
             let cursor = self.text.cursor();
-            let (x, y) = (cursor.0 as u16, cursor.1 as u16);
-            interface.set_cursor(Some(pos!(x, y + position.y() + offset_y)));
+
+            // The cursor's column accounts for any literal tabs preceding it on its line
+            let line_prefix: String = lines[cursor.1].chars().take(cursor.0).collect();
+            let column = self.display_line(&line_prefix).chars().count();
+
+            let (x, y) = (column as u16, cursor.1.saturating_sub(scroll_offset) as u16);
+            interface.set_cursor(Some(pos!(x + position.x(), y + position.y() + offset_y)));
         }
 
         if let Some(bottom_margin) = self.bottom_margin {
             for line in 0..bottom_margin {
-                interface.clear_line(position.y() + line + offset_y + lines.len() as u16);
+                let line = position.y() + line + offset_y + visible_count as u16;
+                interface.clear_rest_of_line(pos!(position.x(), line));
             }
 
             offset_y += bottom_margin;
         }
 
-        lines.len() as u16 + offset_y
+        visible_count as u16 + offset_y
     }
 
     fn update(
         &mut self,
         _dependency_state: &mut DependencyState,
+        _values: &ValueStore,
         input: KeyEvent,
     ) -> Option<InputResult> {
-        // If there are two empty lines, advance the form
-        if input.code == KeyCode::Enter || input.code == KeyCode::Tab {
-            let lines = self.text.lines().to_vec();
-            if lines.len() >= 2 {
-                let last_lines_empty =
-                    lines[lines.len() - 1].is_empty() && lines[lines.len() - 2].is_empty();
-
-                if last_lines_empty {
-                    // If we're trailing whitespace, delete the last two blank lines
-                    if self.trim_trailing_whitespace {
-                        self.text.handle_input(Key::Backspace);
-                        self.text.handle_input(Key::Backspace);
+        self.error = None;
+
+        match self.submit_policy.binding() {
+            // If the submit chord is pressed, advance the form
+            Some(binding) => {
+                if binding.matches(&input) {
+                    return self
+                        .prepare_submission()
+                        .then_some(InputResult::AdvanceForm);
+                }
+            }
+            // If there are two empty lines, advance the form
+            None => {
+                let tab_navigates = self.tab_input == TabInput::Navigate;
+                if input.code == KeyCode::Enter || (input.code == KeyCode::Tab && tab_navigates) {
+                    let lines = self.text.lines();
+                    let last_lines_empty = lines.len() >= 2
+                        && lines[lines.len() - 1].is_empty()
+                        && lines[lines.len() - 2].is_empty();
+
+                    if last_lines_empty {
+                        return self
+                            .prepare_submission()
+                            .then_some(InputResult::AdvanceForm);
                     }
-
-                    return Some(InputResult::AdvanceForm);
                 }
             }
         }
@@ -160,8 +491,19 @@ impl Step for TextBlockStep {
         }
 
         match input.code {
-            KeyCode::Enter => self.text.handle_input(Key::Enter),
+            KeyCode::Enter => self.insert_line(),
+            KeyCode::Char(ch) if self.auto_pair => insert_paired(&mut self.text, ch),
             KeyCode::Char(ch) => self.text.handle_input(Key::Char(ch)),
+            KeyCode::Tab => match self.tab_input {
+                TabInput::Navigate => {}
+                TabInput::Literal(_) => self.text.handle_input(Key::Char('\t')),
+                TabInput::Spaces(count) => {
+                    for _ in 0..count {
+                        self.text.handle_input(Key::Char(' '));
+                    }
+                }
+            },
+            KeyCode::Backspace if self.auto_pair => delete_paired(&mut self.text),
             KeyCode::Backspace => self.text.handle_input(Key::Backspace),
             KeyCode::Up => self.text.handle_input(Key::Up),
             KeyCode::Down => self.text.handle_input(Key::Down),
@@ -170,18 +512,55 @@ impl Step for TextBlockStep {
             _ => {}
         };
 
+        self.update_scroll_offset();
+
         None
     }
 
     fn help(&self) -> Segment {
+        if let Some(error) = &self.error {
+            return Text::new_styled(error.to_string(), error_style()).as_segment();
+        }
+
         Text::new_styled(self.prompt.to_string(), help_style()).as_segment()
     }
 
+    fn captures_arrow(&self, code: KeyCode) -> bool {
+        // Arrows move between lines, leaving the block only from its first or last line
+        let (_, cursor_line) = self.text.cursor();
+        match code {
+            KeyCode::Up => cursor_line > 0,
+            KeyCode::Down => cursor_line + 1 < self.text.lines().len(),
+            _ => false,
+        }
+    }
+
+    fn describe(&self) -> Vec<String> {
+        vec![describe_element(
+            "TextBlockStep",
+            self.name.as_deref(),
+            &self.prompt,
+            &[],
+        )]
+    }
+
     fn drawer(&self) -> Option<DrawerContents> {
         None
     }
 
-    fn result(&self, _dependency_state: &DependencyState) -> String {
+    fn navigation_policy(&self) -> Option<NavigationPolicy> {
+        self.navigation_policy
+    }
+
+    fn skippable(&self) -> bool {
+        self.locked
+    }
+
+    fn optional(&self) -> bool {
+        self.optional
+    }
+
+    fn result(&self, _dependency_state: &DependencyState, _values: &ValueStore) -> String {
         if self.text.value().is_empty() {
             return "\n".to_string();
         }
@@ -194,18 +573,342 @@ impl Step for TextBlockStep {
             }
         }
 
-        result.push_str(&self.text.value());
+        let mut value = self.text.value();
+        if let Some(width) = self.result_wrap {
+            value = wrap_lines(width.into())(value);
+        }
 
-        if let Some(bottom_margin) = self.bottom_margin {
-            for _ in 0..bottom_margin + 1 {
-                result.push('\n');
-            }
+        result.push_str(&value);
+
+        // The text block's last line ends like any other, so subsequent steps begin on a new line
+        for _ in 0..self.bottom_margin.unwrap_or_default() + 1 {
+            result.push('\n');
         }
 
         result
     }
 
+    fn parse_result(&self, text: &str) -> Vec<ParsedResult> {
+        let named = |value: &str| match &self.name {
+            Some(name) => vec![(name.to_string(), ControlValue::Text(value.to_string()))],
+            None => Vec::new(),
+        };
+
+        // The top margin may have been trimmed from the start of the form's result
+        let top_margin = self.top_margin.unwrap_or_default() as usize;
+        let margin_length = text.len() - text.trim_start_matches('\n').len();
+        let content_start = margin_length.min(top_margin);
+        let content = &text[content_start..];
+
+        // The content may end at any paragraph break, preferring the shortest content such that
+        // subsequent steps' results aren't consumed
+        let mut content_ends: Vec<usize> = content
+            .match_indices("\n\n")
+            .map(|(index, _)| index)
+            .collect();
+        content_ends.push(content.trim_end_matches('\n').len());
+        content_ends.sort_unstable();
+        content_ends.dedup();
+
+        let bottom_margin = self.bottom_margin.unwrap_or_default() as usize + 1;
+
+        let mut parses = Vec::new();
+        for content_end in content_ends.into_iter().filter(|end| *end > 0) {
+            let trailing = &content[content_end..];
+            let newlines = trailing.len() - trailing.trim_start_matches('\n').len();
+            let consumed = content_start + content_end + newlines.min(bottom_margin);
+
+            parses.push((named(&content[..content_end]), consumed));
+        }
+
+        // An empty text block's result is a lone newline
+        let empty_length = usize::from(text.starts_with('\n'));
+        parses.push((named(""), empty_length));
+
+        parses
+    }
+
+    fn form_values(&self) -> Vec<(String, ControlValue)> {
+        match &self.name {
+            Some(name) => vec![(name.to_string(), ControlValue::Text(self.text.value()))],
+            None => Vec::new(),
+        }
+    }
+
+    fn set_form_value(
+        &mut self,
+        _dependency_state: &mut DependencyState,
+        name: &str,
+        value: &ControlValue,
+    ) -> bool {
+        match value.as_text() {
+            Some(text) if self.name.as_deref() == Some(name) => {
+                self.text = text_with_value(text, true);
+                self.update_scroll_offset();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn lock_form_value(&mut self, name: &str) -> bool {
+        if self.name.as_deref() != Some(name) {
+            return false;
+        }
+
+        self.locked = true;
+        true
+    }
+
     fn add_to(self, form: &mut Form) {
         form.add_step(Box::new(self));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use tty_interface::{pos, Position};
+
+    use crate::{
+        dependency::DependencyState,
+        input::{KeyCode, KeyEvent, KeyModifiers},
+        keybinding::KeyBinding,
+        step::{InputResult, Step},
+        test::{cursor, press, render, type_text, SizedBackend},
+        value::ValueStore,
+        Form,
+    };
+
+    use super::{SubmitPolicy, TextBlockStep, WhitespaceTrim};
+
+    /// Enter "a  ", a blank line, and "b " followed by two blank lines, then submit with either a
+    /// third Enter or Ctrl+D.
+    fn submit(policy: SubmitPolicy, trim: WhitespaceTrim) -> String {
+        let mut step = TextBlockStep::new("Enter text:");
+        step.set_submit_policy(policy);
+        step.set_whitespace_trim(trim);
+
+        let mut dependency_state = DependencyState::new();
+        let values = ValueStore::new();
+
+        let mut keys: Vec<KeyEvent> = "a  \n\nb \n\n"
+            .chars()
+            .map(|ch| match ch {
+                '\n' => KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
+                ch => KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE),
+            })
+            .collect();
+        keys.push(match policy {
+            SubmitPolicy::DoubleBlankLine => KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
+            _ => KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL),
+        });
+
+        let mut result = None;
+        for key in keys {
+            result = step.update(&mut dependency_state, &values, key);
+        }
+
+        assert_eq!(Some(InputResult::AdvanceForm), result);
+        step.text.value()
+    }
+
+    #[test]
+    fn test_double_blank_line_trim() {
+        let policy = SubmitPolicy::DoubleBlankLine;
+        assert_eq!("a  \n\nb \n\n", submit(policy, WhitespaceTrim::None));
+        assert_eq!("a\n\nb\n\n", submit(policy, WhitespaceTrim::Lines));
+        assert_eq!("a  \n\nb", submit(policy, WhitespaceTrim::Block));
+        assert_eq!("a\n\nb", submit(policy, WhitespaceTrim::All));
+    }
+
+    #[test]
+    fn test_explicit_submit_trim() {
+        let policy = SubmitPolicy::CtrlD;
+        assert_eq!("a  \n\nb \n\n", submit(policy, WhitespaceTrim::None));
+        assert_eq!("a\n\nb\n\n", submit(policy, WhitespaceTrim::Lines));
+        assert_eq!("a  \n\nb", submit(policy, WhitespaceTrim::Block));
+        assert_eq!("a\n\nb", submit(policy, WhitespaceTrim::All));
+    }
+
+    #[test]
+    fn test_submit_policies() {
+        let a = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE);
+        let f2 = KeyEvent::new(KeyCode::F(2), KeyModifiers::NONE);
+        let ctrl_d = KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL);
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+
+        let policies = [
+            (SubmitPolicy::CtrlD, ctrl_d),
+            (
+                SubmitPolicy::ExplicitKey(KeyBinding::key(KeyCode::F(2))),
+                f2,
+            ),
+        ];
+
+        for (policy, submit_key) in policies {
+            let mut step = TextBlockStep::new("Enter text:");
+            step.set_submit_policy(policy);
+
+            let mut dependency_state = DependencyState::new();
+            let values = ValueStore::new();
+            let mut update =
+                |step: &mut TextBlockStep, key| step.update(&mut dependency_state, &values, key);
+
+            // Enter inserts any number of blank lines rather than submitting
+            update(&mut step, a);
+            for _ in 0..3 {
+                assert_ne!(Some(InputResult::AdvanceForm), update(&mut step, enter));
+            }
+
+            // Trailing whitespace is only trimmed once submitted
+            assert_eq!("a\n\n\n", step.text.value());
+            assert_eq!(
+                Some(InputResult::AdvanceForm),
+                update(&mut step, submit_key)
+            );
+            assert_eq!("a", step.text.value());
+        }
+    }
+
+    #[test]
+    fn test_result_wrap() {
+        let mut step = TextBlockStep::new("Enter text:");
+        step.set_result_wrap(10);
+
+        let mut dependency_state = DependencyState::new();
+        let values = ValueStore::new();
+        for ch in "the quick brown fox jumps".chars() {
+            let key = KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE);
+            step.update(&mut dependency_state, &values, key);
+        }
+
+        // The result is wrapped, while the entry is rendered as typed
+        assert_eq!(
+            "the quick\nbrown fox\njumps\n",
+            step.result(&dependency_state, &values)
+        );
+
+        let mut backend = SizedBackend::new(40, 5);
+        let height = step.render(&mut backend, &dependency_state, &values, pos!(0, 0), true);
+        let rows: Vec<String> = (0..height).map(|row| backend.row(row)).collect();
+        assert!(
+            rows.contains(&"the quick brown fox jumps".to_string()),
+            "{:?}",
+            rows
+        );
+    }
+
+    #[test]
+    fn test_auto_indent() {
+        let mut step = TextBlockStep::new("Enter text:");
+        step.set_auto_indent(true);
+
+        let mut dependency_state = DependencyState::new();
+        let values = ValueStore::new();
+
+        for ch in "  1. one\ntwo\n\n".chars() {
+            let code = match ch {
+                '\n' => KeyCode::Enter,
+                ch => KeyCode::Char(ch),
+            };
+
+            let key = KeyEvent::new(code, KeyModifiers::NONE);
+            step.update(&mut dependency_state, &values, key);
+        }
+
+        assert_eq!("  1. one\n  2. two\n", step.text.value());
+    }
+
+    #[test]
+    fn test_auto_pair() {
+        let mut step = TextBlockStep::new("Enter text:");
+        step.set_auto_pair(true);
+
+        let mut dependency_state = DependencyState::new();
+        let values = ValueStore::new();
+
+        let mut press = |code| {
+            let key = KeyEvent::new(code, KeyModifiers::NONE);
+            step.update(&mut dependency_state, &values, key);
+        };
+
+        // Closing characters are typed over, and apostrophes in words aren't paired
+        "f(don't) [".chars().for_each(|ch| press(KeyCode::Char(ch)));
+        press(KeyCode::Backspace);
+        "\"".chars().for_each(|ch| press(KeyCode::Char(ch)));
+
+        assert_eq!("f(don't) \"\"", step.text.value());
+    }
+
+    #[test]
+    fn test_entry_constraints() {
+        let mut step = TextBlockStep::new("Enter text:");
+        step.set_min_entries(2);
+
+        let mut dependency_state = DependencyState::new();
+        let values = ValueStore::new();
+
+        let mut type_keys = |step: &mut TextBlockStep, codes: &[KeyCode]| {
+            let mut result = None;
+            for code in codes {
+                let key = KeyEvent::new(*code, KeyModifiers::NONE);
+                result = step.update(&mut dependency_state, &values, key);
+            }
+
+            result
+        };
+
+        // Blank lines don't count towards the minimum
+        let one_line = [
+            KeyCode::Char('a'),
+            KeyCode::Enter,
+            KeyCode::Enter,
+            KeyCode::Enter,
+        ];
+        assert_eq!(None, type_keys(&mut step, &one_line));
+        assert_eq!(
+            Some("At least 2 entries are required."),
+            step.error.as_deref()
+        );
+
+        let second_line = [KeyCode::Backspace, KeyCode::Char('b'), KeyCode::Enter];
+        assert_eq!(None, type_keys(&mut step, &second_line));
+        assert_eq!(None, step.error);
+
+        let result = type_keys(&mut step, &[KeyCode::Enter, KeyCode::Enter]);
+        assert_eq!(Some(InputResult::AdvanceForm), result);
+        assert_eq!("a\nb", step.text.value());
+    }
+
+    #[test]
+    fn test_max_visible_lines() {
+        let mut form = Form::new();
+        let mut step = TextBlockStep::new("Enter text:");
+        step.set_max_visible_lines(2);
+        step.add_to(&mut form);
+
+        for (index, line) in ["one", "two", "three"].iter().enumerate() {
+            if index > 0 {
+                press(&mut form, KeyCode::Enter);
+            }
+
+            type_text(&mut form, line);
+        }
+
+        // The viewport follows the cursor to the last lines
+        let terminal = render(&mut form);
+        let screen = terminal.screen_contents();
+        assert!(!screen.contains("one"), "{}", screen);
+        terminal.expect_screen_contains("Enter text:\ntwo\nthree");
+        assert_eq!(Some(pos!(5, 2)), cursor(&mut form));
+
+        // Moving above the viewport scrolls it back up
+        press(&mut form, KeyCode::Up);
+        press(&mut form, KeyCode::Up);
+        let terminal = render(&mut form);
+        terminal.expect_screen_contains("Enter text:\none\ntwo");
+        assert!(!terminal.screen_contents().contains("three"));
+        assert_eq!(Some(pos!(3, 1)), cursor(&mut form));
+        assert_eq!("one\ntwo\nthree", form.result());
+    }
+}
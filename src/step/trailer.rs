@@ -0,0 +1,288 @@
+use tty_interface::{pos, Position};
+
+use crate::{
+    backend::RenderBackend,
+    context::Context,
+    dependency::DependencyState,
+    input::{KeyCode, KeyEvent},
+    navigation::NavigationPolicy,
+    style::{help_style, muted_style},
+    text::{DrawerContents, Segment, Text},
+    utility::describe_element,
+    value::ValueStore,
+    Form,
+};
+
+use super::{InputResult, ParsedResult, Step};
+
+/// A computed trailer's key and the provider of its value.
+type Trailer = (String, Box<dyn Fn() -> Option<String>>);
+
+/// A read-only step which appends trailers whose values are computed by providers, such as a
+/// `Signed-off-by` trailer from the user's Git configuration. The providers are called as the form
+/// begins, to preview the trailers while it executes, and again as the step is completed, rather
+/// than on each render. Any trailers duplicating a pair entered into a named key-value step are
+/// omitted.
+///
+/// # Examples
+/// ```
+/// use tty_form::{
+///     Form,
+///     step::{Step, KeyValueStep, TrailerStep},
+/// };
+///
+/// let mut form = Form::new();
+///
+/// let mut trailers = KeyValueStep::new("Enter any trailers:");
+/// trailers.set_name("trailers");
+/// trailers.add_to(&mut form);
+///
+/// let mut sign_off = TrailerStep::new();
+/// sign_off.add_trailer("Signed-off-by", || Some("Jane Doe <jane@example.com>".to_string()));
+/// sign_off.set_deduplicate_with("trailers");
+/// sign_off.add_to(&mut form);
+/// ```
+pub struct TrailerStep {
+    prompt: String,
+    trailers: Vec<Trailer>,
+    values: Vec<Option<String>>,
+    deduplicate_with: Option<String>,
+    navigation_policy: Option<NavigationPolicy>,
+}
+
+impl TrailerStep {
+    /// Create a new trailer step without any trailers.
+    pub fn new() -> Self {
+        Self {
+            prompt: "These trailers will be added. Press Enter to continue.".to_string(),
+            trailers: Vec::new(),
+            values: Vec::new(),
+            deduplicate_with: None,
+            navigation_policy: None,
+        }
+    }
+
+    /// Append a trailer with the specified key, whose value is computed by the specified provider.
+    /// If the provider returns no value, the trailer is omitted.
+    pub fn add_trailer(&mut self, key: &str, value: impl Fn() -> Option<String> + 'static) {
+        self.trailers.push((key.to_string(), Box::new(value)));
+    }
+
+    /// Update the help text shown while this step is focused.
+    pub fn set_prompt(&mut self, prompt: &str) {
+        self.prompt = prompt.to_string();
+    }
+
+    /// Omit any trailers matching a pair entered into the key-value step with the specified name.
+    /// Keys are matched without regard to case.
+    pub fn set_deduplicate_with(&mut self, name: &str) {
+        self.deduplicate_with = Some(name.to_string());
+    }
+
+    /// Override the form's navigation policy while this step is active.
+    pub fn set_navigation_policy(&mut self, policy: NavigationPolicy) {
+        self.navigation_policy = Some(policy);
+    }
+
+    /// Call each trailer's provider, caching its value until the next computation.
+    fn compute(&mut self) {
+        self.values = self.trailers.iter().map(|(_, value)| value()).collect();
+    }
+
+    /// This step's trailer lines from their computed values, omitting any duplicated by the form's
+    /// values.
+    fn lines(&self, values: &ValueStore) -> Vec<String> {
+        let entered_pairs = self
+            .deduplicate_with
+            .as_deref()
+            .and_then(|name| values.get_pairs(name))
+            .unwrap_or_default();
+
+        self.trailers
+            .iter()
+            .zip(&self.values)
+            .filter_map(|((key, _), value)| {
+                let value = value.as_ref()?;
+
+                let is_duplicate = entered_pairs.iter().any(|(entered_key, entered_value)| {
+                    entered_key.eq_ignore_ascii_case(key) && entered_value == value
+                });
+
+                if is_duplicate {
+                    None
+                } else {
+                    Some(format!("{}: {}", key, value))
+                }
+            })
+            .collect()
+    }
+}
+
+impl Default for TrailerStep {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Step for TrailerStep {
+    fn initialize(
+        &mut self,
+        _dependency_state: &mut DependencyState,
+        _context: &Context,
+        _index: usize,
+    ) {
+        self.compute();
+    }
+
+    fn render(
+        &self,
+        interface: &mut dyn RenderBackend,
+        _dependency_state: &DependencyState,
+        values: &ValueStore,
+        position: Position,
+        is_focused: bool,
+    ) -> u16 {
+        let lines = self.lines(values);
+        for (line_index, line) in lines.iter().enumerate() {
+            let line_position = pos!(position.x(), position.y() + line_index as u16);
+            interface.set_styled(line_position, line, muted_style());
+        }
+
+        if is_focused {
+            interface.set_cursor(None);
+        }
+
+        lines.len() as u16
+    }
+
+    fn update(
+        &mut self,
+        _dependency_state: &mut DependencyState,
+        _values: &ValueStore,
+        input: KeyEvent,
+    ) -> Option<InputResult> {
+        match input.code {
+            KeyCode::Enter | KeyCode::Tab => {
+                self.compute();
+                Some(InputResult::AdvanceForm)
+            }
+            KeyCode::Esc | KeyCode::BackTab => Some(InputResult::RetreatForm),
+            _ => None,
+        }
+    }
+
+    fn read_only(&self) -> bool {
+        true
+    }
+
+    fn help(&self) -> Segment {
+        Text::new_styled(self.prompt.to_string(), help_style()).as_segment()
+    }
+
+    fn describe(&self) -> Vec<String> {
+        vec![describe_element("TrailerStep", None, &self.prompt, &[])]
+    }
+
+    fn drawer(&self) -> Option<DrawerContents> {
+        None
+    }
+
+    fn navigation_policy(&self) -> Option<NavigationPolicy> {
+        self.navigation_policy
+    }
+
+    fn parse_result(&self, text: &str) -> Vec<ParsedResult> {
+        // Any number of consecutive lines may be this step's trailers, having been deduplicated
+        let mut line_ends = vec![0];
+        for line in text.split_inclusive('\n') {
+            let is_trailer = self.trailers.iter().any(|(key, _)| {
+                line.strip_prefix(key.as_str())
+                    .is_some_and(|rest| rest.starts_with(": "))
+            });
+
+            if !is_trailer {
+                break;
+            }
+
+            line_ends.push(line_ends[line_ends.len() - 1] + line.len());
+        }
+
+        // Prefer consuming the fewest lines, such that subsequent steps' results aren't consumed
+        line_ends
+            .into_iter()
+            .map(|consumed| (Vec::new(), consumed))
+            .collect()
+    }
+
+    fn result(&self, _dependency_state: &DependencyState, values: &ValueStore) -> String {
+        let mut result = String::new();
+
+        for line in self.lines(values) {
+            result.push_str(&line);
+            result.push('\n');
+        }
+
+        result
+    }
+
+    fn add_to(self, form: &mut Form) {
+        form.add_step(Box::new(self));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use crate::{
+        control::{Control, TextInput},
+        input::KeyCode,
+        step::{CompoundStep, Step},
+        test::{press, render},
+        Form, FormStatus,
+    };
+
+    use super::TrailerStep;
+
+    #[test]
+    fn test_trailer_values_computed_once() {
+        let calls = Rc::new(Cell::new(0));
+
+        let mut form = Form::new();
+
+        let mut step = CompoundStep::new();
+        TextInput::new("Enter a summary.", false).add_to(&mut step);
+        step.add_to(&mut form);
+
+        let mut trailers = TrailerStep::new();
+        let provider_calls = calls.clone();
+        trailers.add_trailer("Signed-off-by", move || {
+            provider_calls.set(provider_calls.get() + 1);
+            Some(format!(
+                "Jane Doe <jane{}@example.com>",
+                provider_calls.get()
+            ))
+        });
+        trailers.add_trailer("Reviewed-by", || None);
+        trailers.add_to(&mut form);
+
+        // The providers are called once as the form begins, however often it's shown
+        press(&mut form, KeyCode::Char('a'));
+        press(&mut form, KeyCode::Enter);
+        render(&mut form).expect_screen_contains("Signed-off-by: Jane Doe <jane1@example.com>");
+        render(&mut form);
+        assert_eq!(
+            "a\nSigned-off-by: Jane Doe <jane1@example.com>",
+            form.result()
+        );
+        assert_eq!(1, calls.get());
+
+        // Completing the step computes the submitted values
+        assert_eq!(FormStatus::Submitted, press(&mut form, KeyCode::Enter));
+        assert_eq!(
+            "a\nSigned-off-by: Jane Doe <jane2@example.com>",
+            form.result()
+        );
+        assert_eq!(2, calls.get());
+    }
+}
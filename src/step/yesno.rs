@@ -1,18 +1,24 @@
-use crossterm::event::{KeyCode, KeyEvent};
-use tty_interface::{pos, Interface, Position};
+use tty_interface::{pos, Position};
 use tty_text::Key;
 
 use crate::{
-    dependency::{DependencyId, DependencyState, Evaluation},
+    backend::RenderBackend,
+    context::Context,
+    dependency::{evaluate_value, DependencyId, DependencyState, Evaluation},
+    input::{KeyCode, KeyEvent},
+    navigation::NavigationPolicy,
     style::{help_style, muted_style},
     text::{DrawerContents, Segment, Text},
+    utility::{describe_element, text_with_value},
+    value::{ControlValue, ValueStore},
     Form,
 };
 
-use super::{InputResult, Step};
+use super::{InputResult, ParsedResult, Step};
 
 /// A boolean input which, if true, accepts a text description.
 pub struct YesNoStep {
+    name: Option<String>,
     prompt: String,
     prefix: String,
     omit_if_no: bool,
@@ -20,11 +26,15 @@ pub struct YesNoStep {
     text_prompt: String,
     text: tty_text::Text,
     evaluation: Option<(DependencyId, Evaluation)>,
+    navigation_policy: Option<NavigationPolicy>,
+    optional: bool,
+    locked: bool,
 }
 
 impl YesNoStep {
     pub fn new(prompt: &str, description_prompt: &str, prefix: &str) -> Self {
         Self {
+            name: None,
             prompt: prompt.to_string(),
             prefix: prefix.to_string(),
             omit_if_no: true,
@@ -32,9 +42,27 @@ impl YesNoStep {
             text_prompt: description_prompt.to_string(),
             text: tty_text::Text::new(false),
             evaluation: None,
+            navigation_policy: None,
+            optional: false,
+            locked: false,
         }
     }
 
+    /// Set this step's name, by which its value may be retrieved from the form.
+    pub fn set_name(&mut self, name: &str) {
+        self.name = Some(name.to_string());
+    }
+
+    /// Override the form's navigation policy while this step is active.
+    pub fn set_navigation_policy(&mut self, policy: NavigationPolicy) {
+        self.navigation_policy = Some(policy);
+    }
+
+    /// Set whether this step may be skipped with the form's skip keys, leaving it empty.
+    pub fn set_optional(&mut self, optional: bool) {
+        self.optional = optional;
+    }
+
     pub fn set_omit_if_no(&mut self, omit: bool) {
         self.omit_if_no = omit;
     }
@@ -45,6 +73,26 @@ impl YesNoStep {
         id
     }
 
+    /// Whether this step is answered yes.
+    pub fn is_yes(&self) -> bool {
+        self.toggle_value
+    }
+
+    /// The description entered for a yes answer, if any.
+    pub fn description(&self) -> Option<String> {
+        let description = self.text.value();
+        (self.toggle_value && !description.trim().is_empty())
+            .then(|| description.trim().to_string())
+    }
+
+    /// Update this step's dependency evaluation, if it has one, from its current value.
+    fn update_evaluation(&self, dependency_state: &mut DependencyState) {
+        if let Some((id, evaluation)) = &self.evaluation {
+            let value = evaluate_value(&self.get_display_value(), evaluation);
+            dependency_state.update_evaluation(id, value);
+        }
+    }
+
     fn get_display_value(&self) -> String {
         if !self.text.value().is_empty() {
             self.text.value()
@@ -57,12 +105,24 @@ impl YesNoStep {
 }
 
 impl Step for YesNoStep {
-    fn initialize(&mut self, _dependency_state: &mut DependencyState, _index: usize) {}
+    fn initialize(
+        &mut self,
+        dependency_state: &mut DependencyState,
+        _context: &Context,
+        index: usize,
+    ) {
+        if let Some((id, _)) = &self.evaluation {
+            dependency_state.register_evaluation(id, index, 0);
+        }
+
+        self.update_evaluation(dependency_state);
+    }
 
     fn render(
         &self,
-        interface: &mut Interface,
+        interface: &mut dyn RenderBackend,
         _dependency_state: &DependencyState,
+        _values: &ValueStore,
         position: Position,
         is_focused: bool,
     ) -> u16 {
@@ -79,7 +139,8 @@ impl Step for YesNoStep {
                 // Render a white prefix with muted value
                 interface.set(position, &format!("{}:", self.prefix));
 
-                let value_position = pos!(self.prefix.len() as u16 + 2, position.y());
+                let value_position =
+                    pos!(position.x() + self.prefix.len() as u16 + 2, position.y());
                 interface.set_styled(value_position, &display_value, muted_style());
             } else if is_focused || self.toggle_value {
                 // Render white prompt and value
@@ -88,7 +149,8 @@ impl Step for YesNoStep {
 
             if is_focused && self.toggle_value {
                 let (cursor_column, _) = self.text.cursor();
-                let cursor = pos!((self.prefix.len() + 2 + cursor_column) as u16, position.y());
+                let cursor_column = (self.prefix.len() + 2 + cursor_column) as u16;
+                let cursor = pos!(position.x() + cursor_column, position.y());
                 interface.set_cursor(Some(cursor));
             }
 
@@ -101,6 +163,7 @@ impl Step for YesNoStep {
     fn update(
         &mut self,
         dependency_state: &mut DependencyState,
+        _values: &ValueStore,
         input: KeyEvent,
     ) -> Option<InputResult> {
         match input.code {
@@ -125,15 +188,7 @@ impl Step for YesNoStep {
             };
         }
 
-        if let Some((id, evaluation)) = &self.evaluation {
-            let value = match evaluation {
-                Evaluation::Equal(value) => value == &self.get_display_value(),
-                Evaluation::NotEqual(value) => value != &self.get_display_value(),
-                Evaluation::IsEmpty => false,
-            };
-
-            dependency_state.update_evaluation(&id, value);
-        }
+        self.update_evaluation(dependency_state);
 
         None
     }
@@ -150,11 +205,38 @@ impl Step for YesNoStep {
         .as_segment()
     }
 
+    fn describe(&self) -> Vec<String> {
+        let edges: Vec<String> = self
+            .evaluation
+            .iter()
+            .map(|(id, _)| format!("evaluates {}", id))
+            .collect();
+
+        vec![describe_element(
+            "YesNoStep",
+            self.name.as_deref(),
+            &self.prompt,
+            &edges,
+        )]
+    }
+
     fn drawer(&self) -> Option<DrawerContents> {
         None
     }
 
-    fn result(&self, _dependency_state: &DependencyState) -> String {
+    fn navigation_policy(&self) -> Option<NavigationPolicy> {
+        self.navigation_policy
+    }
+
+    fn skippable(&self) -> bool {
+        self.locked
+    }
+
+    fn optional(&self) -> bool {
+        self.optional
+    }
+
+    fn result(&self, _dependency_state: &DependencyState, _values: &ValueStore) -> String {
         if self.omit_if_no && !self.toggle_value {
             return String::new();
         }
@@ -162,6 +244,77 @@ impl Step for YesNoStep {
         format!("{}: {}\n", self.prefix, self.get_display_value())
     }
 
+    fn parse_result(&self, text: &str) -> Vec<ParsedResult> {
+        let named = |value: &str| match &self.name {
+            Some(name) => vec![(name.to_string(), ControlValue::Text(value.to_string()))],
+            None => Vec::new(),
+        };
+
+        let mut parses = Vec::new();
+
+        let line = text.split_inclusive('\n').next().unwrap_or_default();
+        if let Some(value) = line.strip_prefix(&format!("{}: ", self.prefix)) {
+            parses.push((named(value.trim_end_matches('\n')), line.len()));
+        }
+
+        if self.omit_if_no {
+            parses.push((named("No"), 0));
+        }
+
+        parses
+    }
+
+    fn captures_arrow(&self, _code: KeyCode) -> bool {
+        self.text.value().is_empty()
+    }
+
+    fn evaluation_ids(&self) -> Vec<DependencyId> {
+        self.evaluation.iter().map(|(id, _)| *id).collect()
+    }
+
+    fn form_values(&self) -> Vec<(String, ControlValue)> {
+        match &self.name {
+            Some(name) => vec![(
+                name.to_string(),
+                ControlValue::Text(self.get_display_value()),
+            )],
+            None => Vec::new(),
+        }
+    }
+
+    fn set_form_value(
+        &mut self,
+        dependency_state: &mut DependencyState,
+        name: &str,
+        value: &ControlValue,
+    ) -> bool {
+        let text = match value.as_text() {
+            Some(text) if self.name.as_deref() == Some(name) => text,
+            _ => return false,
+        };
+
+        match text {
+            "Yes" => (self.toggle_value, self.text) = (true, tty_text::Text::new(false)),
+            "No" => (self.toggle_value, self.text) = (false, tty_text::Text::new(false)),
+            description => {
+                (self.toggle_value, self.text) = (true, text_with_value(description, false))
+            }
+        }
+
+        self.update_evaluation(dependency_state);
+
+        true
+    }
+
+    fn lock_form_value(&mut self, name: &str) -> bool {
+        if self.name.as_deref() != Some(name) {
+            return false;
+        }
+
+        self.locked = true;
+        true
+    }
+
     fn add_to(self, form: &mut Form) {
         form.add_step(Box::new(self));
     }
@@ -0,0 +1,251 @@
+//! Pluggable persistence for form data such as autosaved drafts.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use crate::{Error, Result};
+
+/// A key-value store in which forms persist data such as autosaved drafts. Implement this to
+/// store data in an application's own location, such as a database or keychain.
+pub trait StorageBackend {
+    /// Load the contents stored under the specified key, if any.
+    fn load(&self, key: &str) -> Result<Option<String>>;
+
+    /// Store the specified contents under the specified key, replacing any existing contents.
+    fn save(&mut self, key: &str, contents: &str) -> Result<()>;
+
+    /// Remove any contents stored under the specified key.
+    fn remove(&mut self, key: &str) -> Result<()>;
+
+    /// List the keys with stored contents.
+    fn list(&self) -> Result<Vec<String>>;
+}
+
+/// Storage in a filesystem directory, with each key's contents stored in a file of the same name.
+///
+/// # Examples
+/// ```
+/// use tty_form::storage::{FileStorage, StorageBackend};
+///
+/// let directory = std::env::temp_dir().join("tty-form-file-storage-example");
+/// let mut storage = FileStorage::new(&directory);
+///
+/// storage.save("draft", "Hello, world!")?;
+/// assert_eq!(Some("Hello, world!".to_string()), storage.load("draft")?);
+///
+/// storage.remove("draft")?;
+/// assert_eq!(None, storage.load("draft")?);
+/// # Ok::<(), tty_form::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct FileStorage {
+    directory: PathBuf,
+}
+
+impl FileStorage {
+    /// Create a new storage in the specified directory, which is created when first saved to.
+    pub fn new(directory: impl AsRef<Path>) -> Self {
+        Self {
+            directory: directory.as_ref().to_path_buf(),
+        }
+    }
+
+    /// This storage's directory.
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+}
+
+impl StorageBackend for FileStorage {
+    fn load(&self, key: &str) -> Result<Option<String>> {
+        match fs::read_to_string(self.directory.join(key)) {
+            Ok(contents) => Ok(Some(contents)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(Error::Storage(Box::new(err))),
+        }
+    }
+
+    fn save(&mut self, key: &str, contents: &str) -> Result<()> {
+        fs::create_dir_all(&self.directory).map_err(|err| Error::Storage(Box::new(err)))?;
+        fs::write(self.directory.join(key), contents).map_err(|err| Error::Storage(Box::new(err)))
+    }
+
+    fn remove(&mut self, key: &str) -> Result<()> {
+        match fs::remove_file(self.directory.join(key)) {
+            Err(err) if err.kind() != ErrorKind::NotFound => Err(Error::Storage(Box::new(err))),
+            _ => Ok(()),
+        }
+    }
+
+    fn list(&self) -> Result<Vec<String>> {
+        let entries = match fs::read_dir(&self.directory) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(Error::Storage(Box::new(err))),
+        };
+
+        let mut keys = Vec::new();
+        for entry in entries {
+            let entry = entry.map_err(|err| Error::Storage(Box::new(err)))?;
+            if entry.path().is_file() {
+                keys.push(entry.file_name().to_string_lossy().to_string());
+            }
+        }
+
+        keys.sort();
+
+        Ok(keys)
+    }
+}
+
+/// Storage held in memory for the lifetime of the storage, such as for tests.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStorage {
+    contents: BTreeMap<String, String>,
+}
+
+impl MemoryStorage {
+    /// Create a new, empty in-memory storage.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl StorageBackend for MemoryStorage {
+    fn load(&self, key: &str) -> Result<Option<String>> {
+        Ok(self.contents.get(key).cloned())
+    }
+
+    fn save(&mut self, key: &str, contents: &str) -> Result<()> {
+        self.contents.insert(key.to_string(), contents.to_string());
+        Ok(())
+    }
+
+    fn remove(&mut self, key: &str) -> Result<()> {
+        self.contents.remove(key);
+        Ok(())
+    }
+
+    fn list(&self) -> Result<Vec<String>> {
+        Ok(self.contents.keys().cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc, time::Duration};
+
+    use tty_interface::Interface;
+
+    use crate::{
+        control::{Control, TextInput},
+        device::ChannelInputDevice,
+        input::{InputEvent, KeyCode, KeyEvent, KeyModifiers},
+        step::{CompoundStep, Step},
+        test::VirtualTerminal,
+        Form, Result,
+    };
+
+    use super::{FileStorage, MemoryStorage, StorageBackend};
+
+    /// In-memory storage shared with the test after being moved into a form.
+    #[derive(Clone, Default)]
+    struct SharedStorage(Rc<RefCell<MemoryStorage>>);
+
+    impl StorageBackend for SharedStorage {
+        fn load(&self, key: &str) -> Result<Option<String>> {
+            self.0.borrow().load(key)
+        }
+
+        fn save(&mut self, key: &str, contents: &str) -> Result<()> {
+            self.0.borrow_mut().save(key, contents)
+        }
+
+        fn remove(&mut self, key: &str) -> Result<()> {
+            self.0.borrow_mut().remove(key)
+        }
+
+        fn list(&self) -> Result<Vec<String>> {
+            self.0.borrow().list()
+        }
+    }
+
+    #[test]
+    fn test_file_storage() {
+        let directory = std::env::temp_dir().join("tty-form-file-storage-test");
+        let _ = std::fs::remove_dir_all(&directory);
+
+        // The directory is only created once saved to
+        let mut storage = FileStorage::new(&directory);
+        assert!(storage.list().unwrap().is_empty());
+        assert_eq!(None, storage.load("draft").unwrap());
+
+        storage.save("draft", "second").unwrap();
+        storage.save("backup", "first").unwrap();
+        storage.save("draft", "replaced").unwrap();
+        assert_eq!(vec!["backup", "draft"], storage.list().unwrap());
+        assert_eq!(Some("replaced".to_string()), storage.load("draft").unwrap());
+
+        storage.remove("draft").unwrap();
+        storage.remove("draft").unwrap();
+        assert_eq!(vec!["backup"], storage.list().unwrap());
+
+        let _ = std::fs::remove_dir_all(&directory);
+    }
+
+    #[test]
+    fn test_autosave_storage() {
+        let storage = SharedStorage::default();
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+        let build_form = || {
+            let mut form = Form::new();
+            for name in ["scope", "summary"] {
+                let mut step = CompoundStep::new();
+                let mut input = TextInput::new("Enter a value.", false);
+                input.set_name(name);
+                input.add_to(&mut step);
+                step.add_to(&mut form);
+            }
+
+            form.set_autosave_storage(storage.clone(), "draft", Duration::ZERO);
+            form
+        };
+
+        let execute = |form: Form, keys: &[KeyEvent]| {
+            let (sender, mut device) = ChannelInputDevice::channel();
+            for key in keys {
+                sender.send(InputEvent::Key(*key)).unwrap();
+            }
+
+            let mut terminal = VirtualTerminal::new();
+            let mut interface = Interface::new_relative(&mut terminal).unwrap();
+            form.execute(&mut interface, &mut device)
+        };
+
+        // The draft is saved as the form is edited, and remains when it's abandoned
+        let keys = [
+            key(KeyCode::Char('u')),
+            key(KeyCode::Char('i')),
+            KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL),
+        ];
+        assert!(execute(build_form(), &keys).is_err());
+        let draft = storage.load("draft").unwrap().unwrap();
+        assert!(draft.contains("ui"), "{}", draft);
+
+        // Accepting the draft restores it, and it's removed once submitted
+        let keys = [
+            key(KeyCode::Enter),
+            key(KeyCode::Enter),
+            key(KeyCode::Char('x')),
+            key(KeyCode::Enter),
+        ];
+        assert_eq!("ui\nx", execute(build_form(), &keys).unwrap());
+        assert_eq!(None, storage.load("draft").unwrap());
+        assert!(storage.list().unwrap().is_empty());
+    }
+}
@@ -1,4 +1,114 @@
-use tty_interface::{Color, Style};
+//! Styling for form content, re-exported such that consumers needn't depend on `tty_interface`.
+//!
+//! # Examples
+//! ```
+//! use tty_form::{
+//!     control::StaticText,
+//!     style::{style, Color},
+//! };
+//!
+//! let mut warning = StaticText::new("Careful!");
+//! warning.set_style(style().fg(Color::Red).bold());
+//! ```
+
+pub use tty_interface::{Color, Style};
+
+/// Begin building a style with no colors or attributes.
+pub fn style() -> StyleBuilder {
+    StyleBuilder::default()
+}
+
+/// A builder for a [Style], chaining its colors and attributes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StyleBuilder {
+    style: Style,
+}
+
+impl StyleBuilder {
+    /// Set the foreground color.
+    pub fn fg(self, color: Color) -> Self {
+        Self {
+            style: self.style.set_foreground(color),
+        }
+    }
+
+    /// Set the background color.
+    pub fn bg(self, color: Color) -> Self {
+        Self {
+            style: self.style.set_background(color),
+        }
+    }
+
+    /// Render text in bold.
+    pub fn bold(self) -> Self {
+        Self {
+            style: self.style.set_bold(true),
+        }
+    }
+
+    /// Render text in italics.
+    pub fn italic(self) -> Self {
+        Self {
+            style: self.style.set_italic(true),
+        }
+    }
+
+    /// Underline text.
+    pub fn underline(self) -> Self {
+        Self {
+            style: self.style.set_underline(true),
+        }
+    }
+
+    /// The built style.
+    pub fn build(self) -> Style {
+        self.style
+    }
+}
+
+impl From<StyleBuilder> for Style {
+    fn from(builder: StyleBuilder) -> Self {
+        builder.build()
+    }
+}
+
+/// The style of a compound step's focused control unless overridden: underlined.
+pub fn focus_style() -> Style {
+    Style::new().set_underline(true)
+}
+
+/// Combine two styles, the overlay's colors and attributes taking precedence where it sets them.
+pub(crate) fn overlay_style(base: Style, overlay: Style) -> Style {
+    let mut style = base;
+
+    if let Some(color) = overlay.foreground() {
+        style = style.set_foreground(color);
+    }
+
+    if let Some(color) = overlay.background() {
+        style = style.set_background(color);
+    }
+
+    if overlay.is_bold() {
+        style = style.set_bold(true);
+    }
+
+    if overlay.is_italic() {
+        style = style.set_italic(true);
+    }
+
+    if overlay.is_underlined() {
+        style = style.set_underline(true);
+    }
+
+    style
+}
+
+pub(crate) fn flash_style() -> Style {
+    Style::new()
+        .set_foreground(Color::Black)
+        .set_background(Color::DarkYellow)
+}
 
 pub(crate) fn help_style() -> Style {
     Color::DarkYellow.as_style()
@@ -16,6 +126,75 @@ pub(crate) fn error_style() -> Style {
     Color::Red.as_style()
 }
 
+pub(crate) fn valid_style() -> Style {
+    Color::Green.as_style()
+}
+
 pub(crate) fn muted_style() -> Style {
     Color::DarkGrey.as_style()
 }
+
+pub(crate) fn markdown_heading_style() -> Style {
+    Color::Cyan.as_style().set_bold(true)
+}
+
+pub(crate) fn markdown_marker_style() -> Style {
+    Color::DarkYellow.as_style()
+}
+
+pub(crate) fn markdown_bold_style() -> Style {
+    Style::new().set_bold(true)
+}
+
+pub(crate) fn markdown_code_style() -> Style {
+    Color::DarkGreen.as_style()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::control::{Control, StaticText};
+
+    use super::{overlay_style, style, Color, Style};
+
+    #[test]
+    fn test_style_builder() {
+        assert_eq!(Style::new(), style().build());
+
+        let built = style()
+            .fg(Color::Red)
+            .bg(Color::Black)
+            .bold()
+            .italic()
+            .underline();
+        let expected = Style::new()
+            .set_foreground(Color::Red)
+            .set_background(Color::Black)
+            .set_bold(true)
+            .set_italic(true)
+            .set_underline(true);
+        assert_eq!(expected, built.build());
+        assert_eq!(expected, Style::from(built));
+
+        // Builders may be passed wherever a style is accepted
+        let mut warning = StaticText::new("Careful!");
+        warning.set_style(style().fg(Color::Red).bold());
+        let (segment, _) = warning.text();
+        assert_eq!(
+            Some(&Color::Red.as_style().set_bold(true)),
+            segment[0].style()
+        );
+    }
+
+    #[test]
+    fn test_overlay_style() {
+        let base = style().fg(Color::Red).bg(Color::Black).build();
+        let overlay = style().fg(Color::Blue).underline().build();
+
+        // The overlay's colors and attributes replace the base's only where it sets them
+        assert_eq!(
+            style().fg(Color::Blue).bg(Color::Black).underline().build(),
+            overlay_style(base, overlay)
+        );
+        assert_eq!(base, overlay_style(base, Style::new()));
+    }
+}
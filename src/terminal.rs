@@ -0,0 +1,118 @@
+//! Checks that the environment supports interactive forms, such that callers may fall back to a
+//! non-interactive mode before anything is rendered.
+//!
+//! # Examples
+//! ```no_run
+//! use tty_form::{terminal, Error};
+//!
+//! match terminal::check(Some((40, 10))) {
+//!     Ok(()) => { /* execute the form */ }
+//!     Err(err @ (Error::NoTerminal | Error::UnsupportedTerminal(_))) => {
+//!         eprintln!("falling back to arguments: {}", err.hint().unwrap_or_default());
+//!     }
+//!     Err(err) => return Err(err),
+//! }
+//! # Ok::<(), tty_form::Error>(())
+//! ```
+
+use std::{
+    env,
+    io::{self, IsTerminal},
+};
+
+use crate::{Error, Result};
+
+/// Terminal types which can't position the cursor or style text.
+const UNSUPPORTED_TERMS: [&str; 2] = ["dumb", "unknown"];
+
+/// Check that the standard input and output are a terminal of a supported type whose size may be
+/// queried, and which is at least the specified minimum columns and rows, if any.
+pub fn check(min_size: Option<(u16, u16)>) -> Result<()> {
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        return Err(Error::NoTerminal);
+    }
+
+    check_type(env::var("TERM").ok().as_deref())?;
+
+    let size = crossterm::terminal::size().map_err(Error::TerminalSize)?;
+    check_size(size, min_size)
+}
+
+/// Check that the specified terminal type, from `TERM`, is supported. Unix terminals without a
+/// type lack the terminfo entry describing their capabilities.
+fn check_type(term: Option<&str>) -> Result<()> {
+    let term = term.unwrap_or_default();
+
+    let is_unsupported = UNSUPPORTED_TERMS.contains(&term) || (cfg!(unix) && term.is_empty());
+    if is_unsupported {
+        return Err(Error::UnsupportedTerminal(term.to_string()));
+    }
+
+    Ok(())
+}
+
+/// Check the specified terminal size, which is unknown if either dimension is zero, against the
+/// minimum size, if any.
+fn check_size((columns, rows): (u16, u16), min_size: Option<(u16, u16)>) -> Result<()> {
+    if columns == 0 || rows == 0 {
+        return Err(Error::TerminalSize(io::Error::other(format!(
+            "terminal reported a size of {}x{}",
+            columns, rows
+        ))));
+    }
+
+    match min_size {
+        Some((min_columns, min_rows)) if columns < min_columns || rows < min_rows => {
+            Err(Error::TerminalTooSmall {
+                columns,
+                rows,
+                min_columns,
+                min_rows,
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Error;
+
+    use super::{check_size, check_type};
+
+    #[test]
+    fn test_check_type() {
+        assert!(check_type(Some("xterm-256color")).is_ok());
+        assert!(matches!(
+            check_type(Some("dumb")),
+            Err(Error::UnsupportedTerminal(term)) if term == "dumb"
+        ));
+
+        #[cfg(unix)]
+        assert!(matches!(
+            check_type(None),
+            Err(Error::UnsupportedTerminal(term)) if term.is_empty()
+        ));
+    }
+
+    #[test]
+    fn test_check_size() {
+        assert!(check_size((80, 24), None).is_ok());
+        assert!(check_size((80, 24), Some((80, 24))).is_ok());
+        assert!(matches!(
+            check_size((0, 0), None),
+            Err(Error::TerminalSize(_))
+        ));
+
+        let err = check_size((60, 24), Some((80, 10))).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::TerminalTooSmall {
+                columns: 60,
+                min_columns: 80,
+                ..
+            }
+        ));
+        assert!(err.hint().unwrap().contains("80x10"));
+    }
+}
@@ -1,16 +1,529 @@
-//! A virtual testing device based on the vte/vt100 parser used in functional and documentation tests.
+//! Utilities for testing forms: virtual input, fuzzing, and, with the `testing` feature, a virtual
+//! terminal device based on the vte/vt100 parser.
 
-use crate::device::InputDevice;
+use std::panic::{self, AssertUnwindSafe};
+
+use tty_interface::{pos, Position, Style};
+use unicode_width::UnicodeWidthChar;
+
+use crate::{
+    backend::{CursorStyle, RenderBackend},
+    device::InputDevice,
+    input::{InputEvent, KeyCode, KeyEvent, KeyModifiers},
+    Form, FormStatus, Result,
+};
 
 pub struct VirtualInputDevice;
 
 impl InputDevice for VirtualInputDevice {
-    fn read(&mut self) -> crossterm::Result<crossterm::event::Event> {
-        Ok(crossterm::event::Event::Key(
-            crossterm::event::KeyEvent::new(
-                crossterm::event::KeyCode::Enter,
-                crossterm::event::KeyModifiers::NONE,
-            ),
-        ))
+    fn read(&mut self) -> Result<InputEvent> {
+        Ok(InputEvent::Key(KeyEvent::new(
+            KeyCode::Enter,
+            KeyModifiers::NONE,
+        )))
+    }
+}
+
+/// The default size of a virtual terminal in columns and rows.
+#[cfg(any(test, feature = "testing"))]
+const DEFAULT_TERMINAL_SIZE: (u16, u16) = (80, 24);
+
+/// A virtual terminal output device which parses its output into a screen, against which the
+/// rendered content and cursor position may be asserted.
+#[cfg_attr(
+    feature = "testing",
+    doc = r#"
+# Examples
+```
+# use tty_interface::Interface;
+# use tty_form::{Error, test::VirtualInputDevice};
+# let mut stdin = VirtualInputDevice;
+use tty_form::{
+    Form,
+    step::{Step, CompoundStep},
+    control::{Control, StaticText},
+    test::VirtualTerminal,
+};
+
+let mut terminal = VirtualTerminal::new();
+let mut interface = Interface::new_relative(&mut terminal)?;
+
+let mut form = Form::new();
+let mut step = CompoundStep::new();
+StaticText::new("Hello, world!").add_to(&mut step);
+step.add_to(&mut form);
+
+form.execute(&mut interface, &mut stdin)?;
+drop(interface);
+
+terminal.expect_screen_contains("Hello, world!");
+# Ok::<(), Error>(())
+```
+"#
+)]
+#[cfg(any(test, feature = "testing"))]
+pub struct VirtualTerminal {
+    parser: vt100::Parser,
+}
+
+#[cfg(any(test, feature = "testing"))]
+impl VirtualTerminal {
+    /// Create a new virtual terminal of the default size, 80 columns by 24 rows.
+    pub fn new() -> Self {
+        let (columns, rows) = DEFAULT_TERMINAL_SIZE;
+        Self::with_size(columns, rows)
+    }
+
+    /// Create a new virtual terminal of the specified size.
+    pub fn with_size(columns: u16, rows: u16) -> Self {
+        Self {
+            parser: vt100::Parser::new(rows, columns, 0),
+        }
+    }
+
+    /// The terminal screen's current text contents, with trailing whitespace trimmed from rows.
+    pub fn screen_contents(&self) -> String {
+        self.parser.screen().contents()
+    }
+
+    /// The terminal's current cursor position.
+    pub fn cursor_position(&self) -> Position {
+        let (row, column) = self.parser.screen().cursor_position();
+        pos!(column, row)
+    }
+
+    /// Assert that the terminal's screen contains the specified text.
+    ///
+    /// # Panics
+    /// If the screen doesn't contain the text, displaying the screen's contents.
+    pub fn expect_screen_contains(&self, text: &str) {
+        let contents = self.screen_contents();
+        assert!(
+            contents.contains(text),
+            "expected screen to contain {:?}, but it was:\n{}",
+            text,
+            contents
+        );
+    }
+
+    /// Assert that the terminal's cursor is at the specified position.
+    ///
+    /// # Panics
+    /// If the cursor is elsewhere, displaying its position and the screen's contents.
+    pub fn expect_cursor_at(&self, position: Position) {
+        let cursor_position = self.cursor_position();
+        assert!(
+            cursor_position == position,
+            "expected cursor at ({}, {}), but it was at ({}, {}) on screen:\n{}",
+            position.x(),
+            position.y(),
+            cursor_position.x(),
+            cursor_position.y(),
+            self.screen_contents()
+        );
+    }
+}
+
+#[cfg(any(test, feature = "testing"))]
+impl Default for VirtualTerminal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(any(test, feature = "testing"))]
+impl std::io::Write for VirtualTerminal {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.parser.process(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(any(test, feature = "testing"))]
+impl tty_interface::Device for VirtualTerminal {
+    fn get_terminal_size(&mut self) -> tty_interface::Result<tty_interface::Vector> {
+        let (rows, columns) = self.parser.screen().size();
+        Ok(tty_interface::Vector::new(columns, rows))
+    }
+
+    fn enable_raw_mode(&mut self) -> tty_interface::Result<()> {
+        Ok(())
+    }
+
+    fn disable_raw_mode(&mut self) -> tty_interface::Result<()> {
+        Ok(())
+    }
+}
+
+/// The width of the interface forms are rendered to while fuzzing.
+const FUZZ_WIDTH: u16 = 80;
+
+/// The key codes from which fuzzed input events are drawn.
+const FUZZ_KEYS: [KeyCode; 16] = [
+    KeyCode::Char('a'),
+    KeyCode::Char('b'),
+    KeyCode::Char('🦀'),
+    KeyCode::Char('\u{301}'),
+    KeyCode::Char(' '),
+    KeyCode::Char(':'),
+    KeyCode::Enter,
+    KeyCode::Tab,
+    KeyCode::BackTab,
+    KeyCode::Esc,
+    KeyCode::Backspace,
+    KeyCode::Left,
+    KeyCode::Right,
+    KeyCode::Up,
+    KeyCode::Down,
+    KeyCode::Home,
+];
+
+/// Feed pseudo-random sequences of key events into forms, asserting that they're handled without
+/// panicking, that the cursor remains within each rendered form, and that each line of the form's
+/// result is visible once it's rendered unfocused. Each of the specified number of runs creates a
+/// new form and feeds it up to the specified number of events, or until it's submitted or
+/// canceled. Sequences are generated deterministically from the specified seed, so a failing seed
+/// may be replayed.
+///
+/// # Panics
+/// If a form panics handling an event or rendering, its cursor is rendered out-of-bounds, or its
+/// result doesn't match its rendered text. The panic describes the run's seed and events.
+///
+/// # Examples
+/// ```
+/// use tty_form::{
+///     Form,
+///     step::{Step, KeyValueStep},
+///     test,
+/// };
+///
+/// test::fuzz(
+///     || {
+///         let mut form = Form::new();
+///         KeyValueStep::new("Enter pairs:").add_to(&mut form);
+///         form
+///     },
+///     42,
+///     50,
+///     100,
+/// );
+/// ```
+pub fn fuzz(mut create_form: impl FnMut() -> Form, seed: u64, runs: usize, events_per_run: usize) {
+    let mut random = SplitMix64(seed);
+
+    for run in 0..runs {
+        let mut form = create_form();
+        let mut events = Vec::new();
+
+        let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+            fuzz_run(&mut form, &mut random, &mut events, events_per_run)
+        }));
+
+        let failure = match outcome {
+            Ok(Ok(())) => continue,
+            Ok(Err(failure)) => failure,
+            Err(payload) => match payload.downcast_ref::<&str>() {
+                Some(message) => format!("panicked: {}", message),
+                None => match payload.downcast_ref::<String>() {
+                    Some(message) => format!("panicked: {}", message),
+                    None => String::from("panicked"),
+                },
+            },
+        };
+
+        panic!(
+            "{} in run {} of seed {} after {:?}",
+            failure, run, seed, events
+        );
+    }
+}
+
+/// Feed a form one run of pseudo-random key events, recording each, and describe the first
+/// invariant it violates, if any.
+fn fuzz_run(
+    form: &mut Form,
+    random: &mut SplitMix64,
+    events: &mut Vec<KeyCode>,
+    events_per_run: usize,
+) -> std::result::Result<(), String> {
+    for _ in 0..events_per_run {
+        let code = FUZZ_KEYS[random.next() as usize % FUZZ_KEYS.len()];
+        events.push(code);
+
+        let status = form.update(InputEvent::Key(KeyEvent::new(code, KeyModifiers::NONE)));
+
+        let mut backend = SizedBackend::new(FUZZ_WIDTH, u16::MAX);
+        let height = form.render_at(&mut backend, pos!(0, 0), true);
+
+        if let Some(cursor) = backend.cursor {
+            if cursor.y() >= height || cursor.x() > FUZZ_WIDTH {
+                return Err(format!(
+                    "cursor at ({}, {}) outside form of height {}",
+                    cursor.x(),
+                    cursor.y(),
+                    height
+                ));
+            }
+        }
+
+        if status != FormStatus::Active {
+            break;
+        }
+    }
+
+    // The result is what's shown, so each of its lines appears, in order, once rendered unfocused
+    let mut backend = SizedBackend::new(FUZZ_WIDTH, u16::MAX);
+    let height = form.render_at(&mut backend, pos!(0, 0), false);
+    let mut rows = (0..height).map(|row| visible_text(&backend.row(row)));
+
+    for line in form.result().lines() {
+        let line_text = visible_text(line);
+        if !line_text.trim().is_empty() && !rows.any(|row| row.contains(line_text.trim())) {
+            return Err(format!("result line {:?} isn't visible", line));
+        }
+    }
+
+    Ok(())
+}
+
+/// The specified text without zero-width characters, which have no cell of their own to compare.
+fn visible_text(text: &str) -> String {
+    text.chars()
+        .filter(|ch| ch.width().unwrap_or_default() > 0)
+        .collect()
+}
+
+/// A small, deterministic pseudo-random number generator for fuzzed input sequences.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+
+        let mut value = self.0;
+        value = (value ^ (value >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94d049bb133111eb);
+        value ^ (value >> 31)
+    }
+}
+
+/// Press the specified key, without modifiers, in a form, returning its resulting status.
+#[cfg(test)]
+pub(crate) fn press(form: &mut Form, code: KeyCode) -> FormStatus {
+    form.update(InputEvent::Key(KeyEvent::new(code, KeyModifiers::NONE)))
+}
+
+/// Press the specified character with Control held in a form, returning its resulting status.
+#[cfg(test)]
+pub(crate) fn press_ctrl(form: &mut Form, ch: char) -> FormStatus {
+    form.update(InputEvent::Key(KeyEvent::new(
+        KeyCode::Char(ch),
+        KeyModifiers::CONTROL,
+    )))
+}
+
+/// Type the specified text into a form, one key press per character.
+#[cfg(test)]
+pub(crate) fn type_text(form: &mut Form, text: &str) {
+    for ch in text.chars() {
+        press(form, KeyCode::Char(ch));
+    }
+}
+
+/// Render a focused form to a new virtual terminal, returning the terminal.
+#[cfg(test)]
+pub(crate) fn render(form: &mut Form) -> VirtualTerminal {
+    let mut terminal = VirtualTerminal::new();
+    let mut interface = tty_interface::Interface::new_relative(&mut terminal).unwrap();
+    form.render_at(&mut interface, pos!(0, 0), true);
+    interface.apply().unwrap();
+    drop(interface);
+
+    terminal
+}
+
+/// Render a focused form, returning the position of its cursor, if shown.
+#[cfg(test)]
+pub(crate) fn cursor(form: &mut Form) -> Option<Position> {
+    let mut backend = SizedBackend::new(FUZZ_WIDTH, u16::MAX);
+    form.render_at(&mut backend, pos!(0, 0), true);
+    backend.cursor
+}
+
+/// A render backend of a fixed size which records its rows' text, for asserting layout that
+/// depends on the output's dimensions, or while fuzzing.
+pub(crate) struct SizedBackend {
+    columns: u16,
+    rows: u16,
+    lines: std::collections::BTreeMap<u16, Vec<String>>,
+    pub(crate) cursor: Option<Position>,
+    pub(crate) frames: usize,
+    pub(crate) cursor_styles: Vec<Option<CursorStyle>>,
+    pub(crate) bells: usize,
+}
+
+impl SizedBackend {
+    /// Create a new backend of the specified size.
+    pub(crate) fn new(columns: u16, rows: u16) -> Self {
+        Self {
+            columns,
+            rows,
+            lines: Default::default(),
+            cursor: None,
+            frames: 0,
+            cursor_styles: Vec::new(),
+            bells: 0,
+        }
+    }
+
+    /// The specified row's text, with trailing whitespace trimmed.
+    pub(crate) fn row(&self, row: u16) -> String {
+        let line: String = self
+            .lines
+            .get(&row)
+            .into_iter()
+            .flatten()
+            .cloned()
+            .collect();
+        line.trim_end().to_string()
+    }
+}
+
+impl RenderBackend for SizedBackend {
+    fn set(&mut self, position: Position, text: &str) {
+        // Each cell holds a column's text, with wide characters leaving the next cell empty and
+        // zero-width characters combining with the previous cell
+        let line = self.lines.entry(position.y()).or_default();
+        let mut column = position.x() as usize;
+        for ch in text.chars() {
+            let width = ch.width().unwrap_or_default();
+            if line.len() < column + width {
+                line.resize(column + width, String::from(" "));
+            }
+
+            match width {
+                0 if column > 0 => line[column - 1].push(ch),
+                0 => {}
+                _ => {
+                    line[column] = ch.to_string();
+                    for cell in &mut line[column + 1..column + width] {
+                        cell.clear();
+                    }
+                }
+            }
+
+            column += width;
+        }
+    }
+
+    fn set_styled(&mut self, position: Position, text: &str, _style: Style) {
+        self.set(position, text);
+    }
+
+    fn clear_line(&mut self, line: u16) {
+        self.lines.remove(&line);
+    }
+
+    fn clear_rest_of_line(&mut self, from: Position) {
+        if let Some(line) = self.lines.get_mut(&from.y()) {
+            line.truncate(from.x() as usize);
+        }
+    }
+
+    fn set_cursor(&mut self, position: Option<Position>) {
+        self.cursor = position;
+    }
+
+    fn apply(&mut self) -> crate::Result<()> {
+        self.frames += 1;
+        Ok(())
+    }
+
+    fn width(&self) -> Option<u16> {
+        Some(self.columns)
+    }
+
+    fn height(&self) -> Option<u16> {
+        Some(self.rows)
+    }
+
+    fn set_cursor_style(&mut self, style: Option<CursorStyle>) -> Result<()> {
+        self.cursor_styles.push(style);
+        Ok(())
+    }
+
+    fn bell(&mut self) -> Result<()> {
+        self.bells += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        control::{Control, FunctionalControl, SelectInput, StaticText, TextInput},
+        input::KeyCode,
+        step::{CompoundStep, KeyValueStep, Step, TextBlockStep, YesNoStep},
+        text::Text,
+        Form,
+    };
+
+    use super::fuzz;
+
+    #[test]
+    fn test_fuzz_steps() {
+        fuzz(
+            || {
+                let mut form = Form::new();
+
+                let mut step = CompoundStep::new();
+                StaticText::new("Type: ").add_to(&mut step);
+                SelectInput::new("Select a type.", vec![("feat", ""), ("fix", "")])
+                    .add_to(&mut step);
+                TextInput::new("Enter a description.", false).add_to(&mut step);
+                step.add_to(&mut form);
+
+                TextBlockStep::new("Enter a body.").add_to(&mut form);
+                KeyValueStep::new("Enter trailers.").add_to(&mut form);
+                YesNoStep::new("Breaking?", "Describe it.", "BREAKING CHANGE").add_to(&mut form);
+
+                form
+            },
+            0,
+            500,
+            200,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "panicked: underflow in run 1 of seed 7 after [Right, Char('🦀')]")]
+    fn test_fuzz_reports_failures() {
+        fuzz(
+            || {
+                let mut form = Form::new();
+
+                let mut control = FunctionalControl::new((), |_: &()| {
+                    (Text::new(String::from("Type here.")).as_segment(), None)
+                });
+                control.set_update_fn(|_, input| {
+                    if let KeyCode::Char(_) = input.code {
+                        panic!("underflow");
+                    }
+                });
+
+                let mut step = CompoundStep::new();
+                control.add_to(&mut step);
+                step.add_to(&mut form);
+
+                form
+            },
+            7,
+            10,
+            200,
+        );
     }
 }
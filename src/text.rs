@@ -1,5 +1,13 @@
 use tty_interface::Style;
 
+use crate::style::overlay_style;
+
+pub mod util;
+use util::{get_segment_index_at_column, get_segment_width, split_segment_at};
+
+/// The marker replacing content truncated to fit the terminal's width.
+const ELLIPSIS: &str = "…";
+
 /// A segment of multi-part formatted text content.
 pub type Segment = Vec<Text>;
 
@@ -38,76 +46,99 @@ impl Text {
     }
 }
 
-pub(crate) fn set_segment_style(segment: &mut Segment, style: Style) {
-    let segment_length = get_segment_length(segment);
-    set_segment_subset_style(segment, 0, segment_length, style);
+/// Overlay the specified style onto each of a segment's texts, keeping the colors and attributes
+/// it doesn't set.
+pub(crate) fn overlay_segment_style(segment: &mut Segment, overlay: Style) {
+    for text in segment {
+        text.1 = Some(overlay_style(text.1.unwrap_or_default(), overlay));
+    }
 }
 
-/// Update a segment's style for some subset of its graphemes.
-pub(crate) fn set_segment_subset_style(
-    segment: &mut Segment,
-    start: usize,
-    end: usize,
-    style: Style,
-) {
-    let mut index = 0;
-    let mut i = 0;
-    loop {
-        if i == segment.len() {
-            break;
-        }
-
-        let text = &segment[i];
-
-        let start_intersects = start > index && start < index + text.content().len();
-        let end_intersects = end > index && end < index + text.content().len();
-
-        if start_intersects {
-            let (first, second) = split_text(text, start - index);
+/// Split a segment into lines at its content's newlines, preserving each text's styling.
+pub(crate) fn split_segment_lines(segment: Segment) -> Vec<Segment> {
+    let mut lines = vec![Segment::new()];
 
-            segment[i] = first;
-            segment.insert(i + 1, second);
-        } else if end_intersects {
-            let (first, second) = split_text(text, end - index);
-
-            segment[i] = first;
-            segment.insert(i + 1, second);
+    for text in segment {
+        for (part_index, part) in text.content().split('\n').enumerate() {
+            if part_index > 0 {
+                lines.push(Segment::new());
+            }
+
+            if !part.is_empty() {
+                let line = lines.last_mut().expect("lines are never empty");
+                line.push(Text(part.to_string(), text.1));
+            }
         }
-
-        index += segment[i].content().len();
-        i += 1;
     }
 
-    index = 0;
-    for text in segment {
-        if index >= start && index < end {
-            text.1 = Some(style);
-        }
+    lines
+}
 
-        index += text.content().len();
+/// Truncate a segment to fit within the specified number of display columns, replacing its
+/// hidden content with an ellipsis in the style of the last visible text.
+pub(crate) fn truncate_segment(segment: Segment, columns: usize) -> Segment {
+    if columns == 0 || get_segment_width(&segment) <= columns {
+        return segment;
     }
-}
 
-pub(crate) fn get_segment_length(segment: &Segment) -> usize {
-    segment.iter().map(|text| text.content().len()).sum()
+    let index = get_segment_index_at_column(&segment, columns - 1);
+    let (mut head, tail) = split_segment_at(segment, index);
+
+    let style = head.last().or(tail.first()).and_then(|text| text.1);
+    head.push(Text(ELLIPSIS.to_string(), style));
+    head
 }
 
-fn split_text(text: &Text, index: usize) -> (Text, Text) {
-    let (prefix, suffix) = text.0.split_at(index);
+/// Expand the tab characters in the specified line to spaces, up to each next tab stop.
+pub(crate) fn expand_tabs(line: &str, tab_width: usize) -> String {
+    let tab_width = tab_width.max(1);
+
+    let mut expanded = String::with_capacity(line.len());
+    let mut column = 0;
+    for ch in line.chars() {
+        if ch == '\t' {
+            let spaces = tab_width - column % tab_width;
+            expanded.extend(std::iter::repeat_n(' ', spaces));
+            column += spaces;
+        } else {
+            expanded.push(ch);
+            column += 1;
+        }
+    }
 
-    let first = Text(prefix.to_string(), text.1);
-    let second = Text(suffix.to_string(), text.1);
+    expanded
+}
 
-    (first, second)
+/// Lay out two regions of lines side-by-side, padding each left line to the widest and separating
+/// them with the specified divider. The shorter region is padded with blank lines.
+pub(crate) fn join_columns(left: &[Segment], right: &[Segment], divider: Text) -> Vec<Segment> {
+    let left_width = left.iter().map(get_segment_width).max().unwrap_or(0);
+
+    (0..left.len().max(right.len()))
+        .map(|line_index| {
+            let mut line = left.get(line_index).cloned().unwrap_or_default();
+
+            let padding = left_width - get_segment_width(&line);
+            if padding > 0 {
+                line.push(Text::new(" ".repeat(padding)));
+            }
+
+            line.push(divider.clone());
+            line.extend(right.get(line_index).cloned().unwrap_or_default());
+            line
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
-    use tty_interface::Color;
+    use tty_interface::{Color, Style};
 
     use crate::text::Text;
 
-    use super::set_segment_subset_style;
+    use super::{
+        expand_tabs, join_columns, overlay_segment_style, split_segment_lines, truncate_segment,
+    };
 
     macro_rules! text {
         ($content: expr) => {
@@ -122,73 +153,82 @@ mod tests {
     }
 
     #[test]
-    fn test_set_segment_style_entirely() {
-        let mut segment = vec![
-            text!("TEST1"),
-            text_styled!("TEST2", Color::Red),
-            text_styled!("TEST3", Color::Blue),
-            text!("TEST4"),
-        ];
+    fn test_overlay_segment_style() {
+        let mut segment = vec![text!("TEST1"), text_styled!("TEST2", Color::Red)];
 
-        set_segment_subset_style(&mut segment, 0, 20, Color::Green.as_style());
+        overlay_segment_style(&mut segment, Style::new().set_underline(true));
 
         assert_eq!(
             vec![
-                text_styled!("TEST1", Color::Green),
-                text_styled!("TEST2", Color::Green),
-                text_styled!("TEST3", Color::Green),
-                text_styled!("TEST4", Color::Green),
+                Text::new_styled("TEST1".to_string(), Style::new().set_underline(true)),
+                Text::new_styled(
+                    "TEST2".to_string(),
+                    Color::Red.as_style().set_underline(true)
+                ),
             ],
             segment
         );
     }
 
     #[test]
-    fn test_set_segment_style_neatly() {
-        let mut segment = vec![
-            text!("TEST1"),
-            text_styled!("TEST2", Color::Red),
-            text_styled!("TEST3", Color::Blue),
-            text!("TEST4"),
+    fn test_split_segment_lines() {
+        let segment = vec![
+            text!("Line one\nLine "),
+            text_styled!("two\n", Color::Red),
+            text!("Line three"),
         ];
 
-        set_segment_subset_style(&mut segment, 5, 15, Color::Green.as_style());
-
         assert_eq!(
             vec![
-                text!("TEST1"),
-                text_styled!("TEST2", Color::Green),
-                text_styled!("TEST3", Color::Green),
-                text!("TEST4"),
+                vec![text!("Line one")],
+                vec![text!("Line "), text_styled!("two", Color::Red)],
+                vec![text!("Line three")],
             ],
-            segment
+            split_segment_lines(segment)
         );
     }
 
     #[test]
-    fn test_set_segment_style_split() {
-        let mut segment = vec![
-            text!("TEST1"),
-            text_styled!("TEST2", Color::Red),
-            text_styled!("TEST3", Color::Blue),
-            text!("TEST4"),
-        ];
+    fn test_expand_tabs() {
+        assert_eq!("    a", expand_tabs("\ta", 4));
+        assert_eq!("ab  c", expand_tabs("ab\tc", 4));
+        assert_eq!("abcd    e", expand_tabs("abcd\te", 4));
+        assert_eq!("a       b", expand_tabs("a\t\tb", 4));
+    }
 
-        set_segment_subset_style(&mut segment, 3, 7, Color::Green.as_style());
-        set_segment_subset_style(&mut segment, 11, 14, Color::Magenta.as_style());
+    #[test]
+    fn test_truncate_segment() {
+        let segment = vec![text!("  fix - "), text_styled!("A bug fix.", Color::Grey)];
 
+        assert_eq!(segment.clone(), truncate_segment(segment.clone(), 18));
         assert_eq!(
             vec![
-                text!("TES"),
-                text_styled!("T1", Color::Green),
-                text_styled!("TE", Color::Green),
-                text_styled!("ST2", Color::Red),
-                text_styled!("T", Color::Blue),
-                text_styled!("EST", Color::Magenta),
-                text_styled!("3", Color::Blue),
-                text!("TEST4"),
+                text!("  fix - "),
+                text_styled!("A b", Color::Grey),
+                text_styled!("…", Color::Grey)
             ],
-            segment
+            truncate_segment(segment.clone(), 12)
+        );
+        assert_eq!(vec![text!("  f"), text!("…")], truncate_segment(segment, 4));
+    }
+
+    #[test]
+    fn test_join_columns() {
+        let left = vec![vec![text!("> a")], vec![text!("  bcd")], vec![text!("  e")]];
+        let right = vec![vec![text_styled!("Details", Color::Red)]];
+
+        assert_eq!(
+            vec![
+                vec![
+                    text!("> a"),
+                    text!("  "),
+                    text!(" | "),
+                    text_styled!("Details", Color::Red)
+                ],
+                vec![text!("  bcd"), text!(" | ")],
+                vec![text!("  e"), text!("  "), text!(" | ")],
+            ],
+            join_columns(&left, &right, text!(" | "))
         );
     }
 }
@@ -0,0 +1,489 @@
+//! Utilities for measuring, splitting, and styling segments of text, such as to implement custom
+//! steps and controls. Content is indexed by byte, as with [str]; the display-width-aware
+//! variants index it by terminal column instead, accounting for wide characters.
+//!
+//! # Examples
+//! ```
+//! use tty_form::{
+//!     style::{style, Color},
+//!     text::{util::{get_segment_width, set_segment_column_style}, Text},
+//! };
+//!
+//! let mut segment = vec![Text::new("日本 text".to_string())];
+//! assert_eq!(9, get_segment_width(&segment));
+//!
+//! // Style the two wide characters, which occupy the first four columns
+//! set_segment_column_style(&mut segment, 0, 4, style().fg(Color::Red).build());
+//! assert_eq!("日本", segment[0].content());
+//! ```
+
+use tty_interface::Style;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use super::{Segment, Text};
+
+/// Update a segment's style in its entirety.
+pub fn set_segment_style(segment: &mut Segment, style: Style) {
+    let segment_length = get_segment_length(segment);
+    set_segment_subset_style(segment, 0, segment_length, style);
+}
+
+/// Update a segment's style for the subset of its content between the specified start and end
+/// indices. A range boundary falling within a grapheme is widened to include the whole grapheme.
+pub fn set_segment_subset_style(segment: &mut Segment, start: usize, end: usize, style: Style) {
+    let start = snap_to_grapheme(segment, start, false);
+    let end = snap_to_grapheme(segment, end, true);
+
+    let mut index = 0;
+    let mut i = 0;
+    loop {
+        if i == segment.len() {
+            break;
+        }
+
+        let text = &segment[i];
+
+        let start_intersects = start > index && start < index + text.content().len();
+        let end_intersects = end > index && end < index + text.content().len();
+
+        if start_intersects {
+            let (first, second) = split_text(text, start - index);
+
+            segment[i] = first;
+            segment.insert(i + 1, second);
+        } else if end_intersects {
+            let (first, second) = split_text(text, end - index);
+
+            segment[i] = first;
+            segment.insert(i + 1, second);
+        }
+
+        index += segment[i].content().len();
+        i += 1;
+    }
+
+    index = 0;
+    for text in segment {
+        if index >= start && index < end {
+            text.1 = Some(style);
+        }
+
+        index += text.content().len();
+    }
+}
+
+/// The length of a segment's content in bytes, by which its content is indexed.
+pub fn get_segment_length(segment: &Segment) -> usize {
+    segment.iter().map(|text| text.content().len()).sum()
+}
+
+/// The display width of a segment's content in terminal columns.
+pub fn get_segment_width(segment: &Segment) -> usize {
+    segment.iter().map(|text| text.content().width()).sum()
+}
+
+/// The index within a segment's content of the first grapheme which doesn't fit within the
+/// specified number of display columns, or the segment's length if it fits entirely.
+pub fn get_segment_index_at_column(segment: &Segment, column: usize) -> usize {
+    let mut index = 0;
+    let mut width = 0;
+
+    for text in segment {
+        for (grapheme_index, grapheme) in text.content().grapheme_indices(true) {
+            width += grapheme.width();
+            if width > column {
+                return index + grapheme_index;
+            }
+        }
+
+        index += text.content().len();
+    }
+
+    index
+}
+
+/// The display column at which the specified index within a segment's content begins.
+pub fn get_segment_column_at_index(segment: &Segment, index: usize) -> usize {
+    let mut text_start = 0;
+    let mut column = 0;
+
+    for text in segment {
+        let text_end = text_start + text.content().len();
+        if index < text_end {
+            return column + text.content()[..index - text_start].width();
+        }
+
+        column += text.content().width();
+        text_start = text_end;
+    }
+
+    column
+}
+
+/// Update a segment's style for the subset of its content between the specified start and end
+/// display columns. Wide characters partially within the columns are included.
+pub fn set_segment_column_style(
+    segment: &mut Segment,
+    start_column: usize,
+    end_column: usize,
+    style: Style,
+) {
+    if end_column <= start_column {
+        return;
+    }
+
+    let start = get_segment_index_at_column(segment, start_column);
+    let end = next_grapheme_index(
+        segment,
+        get_segment_index_at_column(segment, end_column - 1),
+    );
+
+    set_segment_subset_style(segment, start, end, style);
+}
+
+/// Split a segment in two at the specified display column, preserving styling. A wide character
+/// straddling the column is kept in the second part.
+pub fn split_segment_at_column(segment: Segment, column: usize) -> (Segment, Segment) {
+    let index = get_segment_index_at_column(&segment, column);
+    split_segment_at(segment, index)
+}
+
+/// The index following the grapheme at the specified index within a segment's content, or the
+/// segment's length if the index is at or past its end.
+fn next_grapheme_index(segment: &Segment, index: usize) -> usize {
+    snap_to_grapheme(segment, index + 1, true).min(get_segment_length(segment))
+}
+
+/// Snap an index within a segment's content to the nearest boundary of the grapheme containing
+/// it, the following boundary if rounding up or the preceding one otherwise. Indices past the
+/// segment's content are returned as-is.
+fn snap_to_grapheme(segment: &Segment, index: usize, round_up: bool) -> usize {
+    let mut text_start = 0;
+
+    for text in segment {
+        let text_end = text_start + text.content().len();
+        if index < text_end {
+            let offset = index - text_start;
+            let boundary = if round_up {
+                ceil_grapheme_boundary(text.content(), offset)
+            } else {
+                floor_grapheme_boundary(text.content(), offset)
+            };
+
+            return text_start + boundary;
+        }
+
+        text_start = text_end;
+    }
+
+    index
+}
+
+/// The start of the grapheme containing the specified index within some content, or the content's
+/// length if the index is past its end.
+fn floor_grapheme_boundary(content: &str, index: usize) -> usize {
+    if index >= content.len() {
+        return content.len();
+    }
+
+    content
+        .grapheme_indices(true)
+        .map(|(grapheme_index, _)| grapheme_index)
+        .take_while(|grapheme_index| *grapheme_index <= index)
+        .last()
+        .unwrap_or(0)
+}
+
+/// The end of the grapheme containing the specified index within some content, unless the index is
+/// already a boundary, or the content's length if the index is past its end.
+fn ceil_grapheme_boundary(content: &str, index: usize) -> usize {
+    let start = floor_grapheme_boundary(content, index);
+    if start == index {
+        return start;
+    }
+
+    content[start..]
+        .graphemes(true)
+        .next()
+        .map_or(content.len(), |grapheme| start + grapheme.len())
+}
+
+/// Split a segment in two at the specified index within its content, preserving styling. An index
+/// falling within a grapheme splits before it.
+pub fn split_segment_at(segment: Segment, index: usize) -> (Segment, Segment) {
+    let index = snap_to_grapheme(&segment, index, false);
+
+    let mut head = Segment::new();
+    let mut tail = Segment::new();
+
+    let mut text_start = 0;
+    for text in segment {
+        let text_end = text_start + text.content().len();
+
+        if text_end <= index {
+            head.push(text);
+        } else if text_start >= index {
+            tail.push(text);
+        } else {
+            let (first, second) = split_text(&text, index - text_start);
+            head.push(first);
+            tail.push(second);
+        }
+
+        text_start = text_end;
+    }
+
+    (head, tail)
+}
+
+/// Split a text in two at the specified index within its content, preserving its styling. An index
+/// falling within a grapheme splits before it.
+pub fn split_text(text: &Text, index: usize) -> (Text, Text) {
+    let index = floor_grapheme_boundary(&text.0, index);
+    let (prefix, suffix) = text.0.split_at(index);
+
+    let first = Text(prefix.to_string(), text.1);
+    let second = Text(suffix.to_string(), text.1);
+
+    (first, second)
+}
+
+#[cfg(test)]
+mod tests {
+    use tty_interface::Color;
+
+    use crate::text::Text;
+
+    use super::{
+        get_segment_column_at_index, get_segment_index_at_column, get_segment_length,
+        get_segment_width, set_segment_column_style, set_segment_subset_style, split_segment_at,
+        split_segment_at_column,
+    };
+
+    macro_rules! text {
+        ($content: expr) => {
+            Text::new($content.to_string())
+        };
+    }
+
+    macro_rules! text_styled {
+        ($content: expr, $color: expr) => {
+            Text::new_styled($content.to_string(), $color.as_style())
+        };
+    }
+
+    #[test]
+    fn test_set_segment_style_entirely() {
+        let mut segment = vec![
+            text!("TEST1"),
+            text_styled!("TEST2", Color::Red),
+            text_styled!("TEST3", Color::Blue),
+            text!("TEST4"),
+        ];
+
+        set_segment_subset_style(&mut segment, 0, 20, Color::Green.as_style());
+
+        assert_eq!(
+            vec![
+                text_styled!("TEST1", Color::Green),
+                text_styled!("TEST2", Color::Green),
+                text_styled!("TEST3", Color::Green),
+                text_styled!("TEST4", Color::Green),
+            ],
+            segment
+        );
+    }
+
+    #[test]
+    fn test_set_segment_style_neatly() {
+        let mut segment = vec![
+            text!("TEST1"),
+            text_styled!("TEST2", Color::Red),
+            text_styled!("TEST3", Color::Blue),
+            text!("TEST4"),
+        ];
+
+        set_segment_subset_style(&mut segment, 5, 15, Color::Green.as_style());
+
+        assert_eq!(
+            vec![
+                text!("TEST1"),
+                text_styled!("TEST2", Color::Green),
+                text_styled!("TEST3", Color::Green),
+                text!("TEST4"),
+            ],
+            segment
+        );
+    }
+
+    #[test]
+    fn test_set_segment_style_split() {
+        let mut segment = vec![
+            text!("TEST1"),
+            text_styled!("TEST2", Color::Red),
+            text_styled!("TEST3", Color::Blue),
+            text!("TEST4"),
+        ];
+
+        set_segment_subset_style(&mut segment, 3, 7, Color::Green.as_style());
+        set_segment_subset_style(&mut segment, 11, 14, Color::Magenta.as_style());
+
+        assert_eq!(
+            vec![
+                text!("TES"),
+                text_styled!("T1", Color::Green),
+                text_styled!("TE", Color::Green),
+                text_styled!("ST2", Color::Red),
+                text_styled!("T", Color::Blue),
+                text_styled!("EST", Color::Magenta),
+                text_styled!("3", Color::Blue),
+                text!("TEST4"),
+            ],
+            segment
+        );
+    }
+
+    #[test]
+    fn test_get_segment_width_wide_characters() {
+        let segment = vec![text!("ab"), text!("日本"), text!("é")];
+
+        assert_eq!(7, get_segment_width(&segment));
+    }
+
+    #[test]
+    fn test_get_segment_index_at_column() {
+        let segment = vec![text!("ab"), text!("日本"), text!("c")];
+
+        assert_eq!(0, get_segment_index_at_column(&segment, 0));
+        assert_eq!(2, get_segment_index_at_column(&segment, 2));
+        assert_eq!(2, get_segment_index_at_column(&segment, 3));
+        assert_eq!(5, get_segment_index_at_column(&segment, 4));
+        assert_eq!(8, get_segment_index_at_column(&segment, 6));
+        assert_eq!(9, get_segment_index_at_column(&segment, 7));
+    }
+
+    #[test]
+    fn test_split_segment_at() {
+        let segment = vec![
+            text!("Hello, "),
+            text_styled!("world", Color::Red),
+            text!("!"),
+        ];
+
+        assert_eq!(
+            (
+                vec![text!("Hello, "), text_styled!("wo", Color::Red)],
+                vec![text_styled!("rld", Color::Red), text!("!")],
+            ),
+            split_segment_at(segment, 9)
+        );
+    }
+
+    #[test]
+    fn test_get_segment_column_at_index() {
+        let segment = vec![text!("ab"), text!("日本"), text!("c")];
+
+        assert_eq!(0, get_segment_column_at_index(&segment, 0));
+        assert_eq!(2, get_segment_column_at_index(&segment, 2));
+        assert_eq!(4, get_segment_column_at_index(&segment, 5));
+        assert_eq!(6, get_segment_column_at_index(&segment, 8));
+        assert_eq!(7, get_segment_column_at_index(&segment, 9));
+    }
+
+    #[test]
+    fn test_set_segment_column_style() {
+        let mut segment = vec![text!("ab"), text!("日本"), text!("c")];
+
+        set_segment_column_style(&mut segment, 1, 3, Color::Green.as_style());
+
+        assert_eq!(
+            vec![
+                text!("a"),
+                text_styled!("b", Color::Green),
+                text_styled!("日", Color::Green),
+                text!("本"),
+                text!("c"),
+            ],
+            segment
+        );
+    }
+
+    #[test]
+    fn test_split_segment_at_column() {
+        let segment = vec![text!("ab"), text_styled!("日本", Color::Red)];
+
+        assert_eq!(
+            (
+                vec![text!("ab"), text_styled!("日", Color::Red)],
+                vec![text_styled!("本", Color::Red)],
+            ),
+            split_segment_at_column(segment, 5)
+        );
+    }
+
+    #[test]
+    fn test_split_segment_mid_grapheme() {
+        let segment = vec![text!("e\u{301}"), text_styled!("🦀!", Color::Red)];
+
+        assert_eq!(
+            (vec![], segment.clone()),
+            split_segment_at(segment.clone(), 1)
+        );
+        assert_eq!(
+            (
+                vec![text!("e\u{301}")],
+                vec![text_styled!("🦀!", Color::Red)]
+            ),
+            split_segment_at(segment, 5)
+        );
+    }
+
+    #[test]
+    fn test_set_segment_style_mid_grapheme() {
+        let mut segment = vec![text!("ae\u{301}b")];
+
+        set_segment_subset_style(&mut segment, 2, 3, Color::Green.as_style());
+
+        assert_eq!(
+            vec![
+                text!("a"),
+                text_styled!("e\u{301}", Color::Green),
+                text!("b")
+            ],
+            segment
+        );
+    }
+
+    #[test]
+    fn test_fuzz_grapheme_ranges() {
+        // Emoji, a ZWJ sequence, flags, and combining marks, each spanning multiple bytes
+        let segment = vec![
+            text!("a🦀"),
+            text_styled!("👩‍💻e\u{301}", Color::Red),
+            text!("🇯🇵 日本x\u{308}\u{301}"),
+        ];
+        let content: String = segment.iter().map(Text::content).collect();
+        let length = get_segment_length(&segment);
+
+        for start in 0..=length + 1 {
+            let (head, tail) = split_segment_at(segment.clone(), start);
+            let rejoined: String = head.iter().chain(&tail).map(Text::content).collect();
+            assert_eq!(content, rejoined, "split at {}", start);
+
+            for end in start..=length + 1 {
+                let mut styled = segment.clone();
+                set_segment_subset_style(&mut styled, start, end, Color::Green.as_style());
+
+                let restyled: String = styled.iter().map(Text::content).collect();
+                assert_eq!(content, restyled, "styled from {} to {}", start, end);
+            }
+        }
+
+        for column in 0..=get_segment_width(&segment) + 1 {
+            let mut styled = segment.clone();
+            set_segment_column_style(&mut styled, 0, column, Color::Green.as_style());
+            split_segment_at_column(styled, column);
+        }
+    }
+}
@@ -1,10 +1,12 @@
-use tty_interface::{pos, Interface, Position};
+use tty_interface::{pos, Position};
+use tty_text::Key;
+use unicode_width::UnicodeWidthStr;
 
-use crate::text::Segment;
+use crate::{backend::RenderBackend, text::Segment};
 
 /// Renders a segment at the specified position, returning the cursor's position after the render.
 pub(crate) fn render_segment(
-    interface: &mut Interface,
+    interface: &mut dyn RenderBackend,
     mut position: Position,
     segment: Segment,
 ) -> Position {
@@ -14,8 +16,128 @@ pub(crate) fn render_segment(
             None => interface.set(position, text.content()),
         };
 
-        position = pos!(position.x() + text.content().len() as u16, position.y());
+        position = pos!(position.x() + text.content().width() as u16, position.y());
     }
 
     position
 }
+
+/// Describe a step or control on a single line for a form's structural description, such as
+/// `Text scope "Enter a scope." evaluates #0`.
+pub(crate) fn describe_element(
+    kind: &str,
+    name: Option<&str>,
+    prompt: &str,
+    edges: &[String],
+) -> String {
+    let mut description = kind.to_string();
+
+    if let Some(name) = name {
+        description.push_str(&format!(" {}", name));
+    }
+
+    if !prompt.is_empty() {
+        description.push_str(&format!(" {:?}", prompt));
+    }
+
+    for edge in edges {
+        description.push_str(&format!(" {}", edge));
+    }
+
+    description
+}
+
+/// Create a new text buffer pre-populated with the specified value.
+pub(crate) fn text_with_value(value: &str, multi_line: bool) -> tty_text::Text {
+    let mut text = tty_text::Text::new(multi_line);
+
+    for ch in value.chars() {
+        match ch {
+            '\n' => text.handle_input(Key::Enter),
+            ch => text.handle_input(Key::Char(ch)),
+        }
+    }
+
+    text
+}
+
+/// Describe why a step with the specified number of entries fails its entry constraints, if so.
+pub(crate) fn entry_constraint_error(
+    entries: usize,
+    required: bool,
+    min_entries: usize,
+) -> Option<String> {
+    if required && entries == 0 {
+        return Some(String::from("A response is required."));
+    }
+
+    match min_entries {
+        _ if entries >= min_entries => None,
+        1 => Some(String::from("At least 1 entry is required.")),
+        _ => Some(format!("At least {} entries are required.", min_entries)),
+    }
+}
+
+/// The closing character auto-paired with the specified opening bracket or quote, if any.
+fn closing_pair(opening: char) -> Option<char> {
+    match opening {
+        '(' => Some(')'),
+        '[' => Some(']'),
+        '{' => Some('}'),
+        '"' => Some('"'),
+        '\'' => Some('\''),
+        _ => None,
+    }
+}
+
+/// The characters immediately before and after the specified text's cursor, if any.
+fn chars_around_cursor(text: &tty_text::Text) -> (Option<char>, Option<char>) {
+    let (column, line_index) = text.cursor();
+    let line = &text.lines()[line_index];
+
+    let before = column
+        .checked_sub(1)
+        .and_then(|index| line.chars().nth(index));
+    let after = line.chars().nth(column);
+
+    (before, after)
+}
+
+/// Enter a character into the specified text, auto-pairing brackets and quotes. Typing a closing
+/// character already after the cursor moves past it rather than inserting another. Apostrophes
+/// following a word, as in contractions, aren't paired.
+pub(crate) fn insert_paired(text: &mut tty_text::Text, ch: char) {
+    let (before, after) = chars_around_cursor(text);
+
+    let is_closing = matches!(ch, ')' | ']' | '}' | '"' | '\'');
+    if is_closing && after == Some(ch) {
+        text.handle_input(Key::Right);
+        return;
+    }
+
+    let follows_word = before.is_some_and(char::is_alphanumeric);
+    match closing_pair(ch) {
+        Some(closing) if !(ch == '\'' && follows_word) => {
+            text.handle_input(Key::Char(ch));
+            text.handle_input(Key::Char(closing));
+            text.handle_input(Key::Left);
+        }
+        _ => text.handle_input(Key::Char(ch)),
+    }
+}
+
+/// Delete the character before the specified text's cursor, along with its auto-paired closing
+/// character if the pair is empty.
+pub(crate) fn delete_paired(text: &mut tty_text::Text) {
+    let (before, after) = chars_around_cursor(text);
+
+    if before
+        .and_then(closing_pair)
+        .is_some_and(|closing| after == Some(closing))
+    {
+        text.handle_input(Key::Right);
+        text.handle_input(Key::Backspace);
+    }
+
+    text.handle_input(Key::Backspace);
+}
@@ -0,0 +1,270 @@
+//! Validation of controls' values on a background thread, such as for remote lookups.
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{channel, Receiver, TryRecvError},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    style::{error_style, muted_style, valid_style},
+    text::{Segment, Text},
+};
+
+/// The error shown when a validator panics rather than returning a result.
+const VALIDATOR_FAILED: &str = "Validation failed.";
+
+/// How often a pending validation is checked for its result.
+const RESULT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// A validator of a control's value, returning an error message if the value is invalid. It
+/// should check the token periodically and return promptly once cancelled.
+pub type Validator = Arc<dyn Fn(&str, &CancellationToken) -> Result<(), String> + Send + Sync>;
+
+/// Signals a background task that it has been cancelled, such as by the user pressing Esc.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Create a new, uncancelled token.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the task has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Signal the task that it has been cancelled.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+}
+
+/// The current state of a control's background validation.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ValidationState {
+    /// The value has changed and is awaiting its debounce interval or its validator's result.
+    Pending,
+    /// The validator accepted the current value.
+    Valid,
+    /// The validator rejected the current value with the specified message.
+    Invalid(String),
+}
+
+impl ValidationState {
+    /// An indicator of this state to render beside its control.
+    pub(crate) fn indicator(&self, cancelling: bool) -> Segment {
+        if cancelling {
+            return Text::new_styled(" cancelling…".to_string(), muted_style()).as_segment();
+        }
+
+        match self {
+            ValidationState::Pending => Text::new_styled(" …".to_string(), muted_style()),
+            ValidationState::Valid => Text::new_styled(" ✓".to_string(), valid_style()),
+            ValidationState::Invalid(_) => Text::new_styled(" ✗".to_string(), error_style()),
+        }
+        .as_segment()
+    }
+}
+
+/// Runs a validator on a background thread once a value has been unchanged for a debounce
+/// interval, collecting its result as the form ticks. Results for outdated values are discarded.
+pub(crate) struct AsyncValidation {
+    validator: Validator,
+    debounce: Duration,
+    state: ValidationState,
+    value: String,
+    changed_at: Option<Instant>,
+    receiver: Option<Receiver<Result<(), String>>>,
+    token: CancellationToken,
+}
+
+impl AsyncValidation {
+    /// Create a background validation of the specified initial value.
+    pub(crate) fn new(validator: Validator, debounce: Duration, value: &str) -> Self {
+        Self {
+            validator,
+            debounce,
+            state: ValidationState::Pending,
+            value: value.to_string(),
+            changed_at: Some(Instant::now()),
+            receiver: None,
+            token: CancellationToken::new(),
+        }
+    }
+
+    /// The validation's current state.
+    pub(crate) fn state(&self) -> &ValidationState {
+        &self.state
+    }
+
+    /// Restart validation for the specified value if it differs from the last.
+    pub(crate) fn changed(&mut self, value: &str) {
+        if value == self.value {
+            return;
+        }
+
+        // Results for the outdated value are discarded, so its validator may stop early
+        self.token.cancel();
+
+        self.value = value.to_string();
+        self.state = ValidationState::Pending;
+        self.changed_at = Some(Instant::now());
+        self.receiver = None;
+    }
+
+    /// Cancel the running validator, if any, returning whether its acknowledgement is awaited.
+    pub(crate) fn cancel(&mut self) -> bool {
+        if self.receiver.is_none() {
+            return false;
+        }
+
+        self.token.cancel();
+        true
+    }
+
+    /// Whether the running validator has been cancelled but hasn't yet returned.
+    pub(crate) fn is_cancelling(&self) -> bool {
+        self.receiver.is_some() && self.token.is_cancelled()
+    }
+
+    /// Start the validator once the debounce interval has elapsed and collect its result, if ready.
+    pub(crate) fn tick(&mut self) {
+        if let Some(changed_at) = self.changed_at {
+            if changed_at.elapsed() >= self.debounce {
+                self.changed_at = None;
+                self.spawn();
+            }
+        }
+
+        let result = match &self.receiver {
+            Some(receiver) => match receiver.try_recv() {
+                Ok(result) => result,
+                Err(TryRecvError::Empty) => return,
+                // A validator which panicked has failed without returning
+                Err(TryRecvError::Disconnected) => Err(VALIDATOR_FAILED.to_string()),
+            },
+            None => return,
+        };
+
+        self.receiver = None;
+
+        // A cancelled validator's result is discarded and the value validated again later
+        if self.token.is_cancelled() {
+            self.changed_at = Some(Instant::now());
+            return;
+        }
+
+        self.state = match result {
+            Ok(()) => ValidationState::Valid,
+            Err(message) => ValidationState::Invalid(message),
+        };
+    }
+
+    /// How often this validation should be ticked, if it's pending.
+    pub(crate) fn refresh_interval(&self) -> Option<Duration> {
+        match self.state {
+            ValidationState::Pending => Some(RESULT_POLL_INTERVAL),
+            _ => None,
+        }
+    }
+
+    /// Run the validator against the current value on a background thread.
+    fn spawn(&mut self) {
+        let (sender, receiver) = channel();
+        let validator = self.validator.clone();
+        let value = self.value.clone();
+
+        self.token = CancellationToken::new();
+        let token = self.token.clone();
+
+        thread::spawn(move || {
+            let _ = sender.send(validator(&value, &token));
+        });
+
+        self.receiver = Some(receiver);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::Arc,
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use super::{AsyncValidation, CancellationToken, ValidationState, VALIDATOR_FAILED};
+
+    /// Tick the validation until the specified condition holds, failing if it takes too long.
+    fn tick_until(validation: &mut AsyncValidation, condition: impl Fn(&AsyncValidation) -> bool) {
+        let started_at = Instant::now();
+        while !condition(validation) {
+            assert!(
+                started_at.elapsed() < Duration::from_secs(5),
+                "validation stalled"
+            );
+            thread::sleep(Duration::from_millis(1));
+            validation.tick();
+        }
+    }
+
+    #[test]
+    fn test_validation_results() {
+        let validator = Arc::new(|value: &str, _: &_| match value {
+            "#1" => Ok(()),
+            _ => Err(format!("No ticket {}.", value)),
+        });
+
+        let mut validation = AsyncValidation::new(validator, Duration::ZERO, "#1");
+        tick_until(&mut validation, |v| v.state() != &ValidationState::Pending);
+        assert_eq!(&ValidationState::Valid, validation.state());
+
+        validation.changed("#2");
+        assert_eq!(&ValidationState::Pending, validation.state());
+        tick_until(&mut validation, |v| v.state() != &ValidationState::Pending);
+        let message = "No ticket #2.".to_string();
+        assert_eq!(&ValidationState::Invalid(message), validation.state());
+    }
+
+    #[test]
+    fn test_panicking_validator() {
+        let validator = Arc::new(|_: &str, _: &_| panic!("lookup failed"));
+
+        let mut validation = AsyncValidation::new(validator, Duration::ZERO, "#1");
+        tick_until(&mut validation, |v| v.state() != &ValidationState::Pending);
+        let message = VALIDATOR_FAILED.to_string();
+        assert_eq!(&ValidationState::Invalid(message), validation.state());
+        assert_eq!(None, validation.refresh_interval());
+    }
+
+    #[test]
+    fn test_cancelled_validator_panics() {
+        let validator = Arc::new(|_: &str, token: &CancellationToken| {
+            while !token.is_cancelled() {
+                thread::sleep(Duration::from_millis(1));
+            }
+
+            panic!("interrupted");
+        });
+
+        let mut validation = AsyncValidation::new(validator, Duration::from_secs(60), "#1");
+        validation.changed_at = Some(Instant::now() - Duration::from_secs(60));
+        validation.tick();
+        assert!(validation.cancel());
+        assert!(validation.is_cancelling());
+
+        // The cancellation is acknowledged even though the validator didn't return
+        tick_until(&mut validation, |v| !v.is_cancelling());
+        assert_eq!(&ValidationState::Pending, validation.state());
+        assert!(!validation.cancel());
+    }
+}
@@ -0,0 +1,205 @@
+//! Named values of a form's controls and steps, available while the form executes.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+/// The current value of a named control or step.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ControlValue {
+    /// A textual value, such as a text input's contents or a selected option.
+    Text(String),
+    /// A boolean value, such as a yes/no toggle.
+    Bool(bool),
+    /// A list of key-value pairs.
+    Pairs(Vec<(String, String)>),
+}
+
+impl ControlValue {
+    /// This value's text, if it is textual.
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            ControlValue::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// This value's boolean, if it is boolean.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            ControlValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// This value's key-value pairs, if it is a list of pairs.
+    pub fn as_pairs(&self) -> Option<&[(String, String)]> {
+        match self {
+            ControlValue::Pairs(pairs) => Some(pairs),
+            _ => None,
+        }
+    }
+}
+
+/// Values answering a form's named controls and steps, such as to preview its result.
+pub type Answers = ValueStore;
+
+/// The current values of a form's named controls and steps, maintained by the form as it executes.
+///
+/// # Examples
+/// ```
+/// use tty_form::{
+///     Form,
+///     step::{Step, CompoundStep},
+///     control::TextInput,
+/// };
+///
+/// let mut form = Form::new();
+///
+/// let mut step = CompoundStep::new();
+/// let mut name = TextInput::new("Enter your name:", false);
+/// name.set_name("name");
+/// name.set_value("Daniel");
+/// step.add(name);
+/// step.add_to(&mut form);
+///
+/// assert_eq!(Some("Daniel"), form.values().get_text("name"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ValueStore {
+    values: HashMap<String, ControlValue>,
+}
+
+impl ValueStore {
+    /// Create a new, empty value store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The value with the specified name, if one exists.
+    pub fn get(&self, name: &str) -> Option<&ControlValue> {
+        self.values.get(name)
+    }
+
+    /// The textual value with the specified name, if one exists and is textual.
+    pub fn get_text(&self, name: &str) -> Option<&str> {
+        self.get(name).and_then(ControlValue::as_text)
+    }
+
+    /// The boolean value with the specified name, if one exists and is boolean.
+    pub fn get_bool(&self, name: &str) -> Option<bool> {
+        self.get(name).and_then(ControlValue::as_bool)
+    }
+
+    /// The key-value pairs with the specified name, if they exist.
+    pub fn get_pairs(&self, name: &str) -> Option<&[(String, String)]> {
+        self.get(name).and_then(ControlValue::as_pairs)
+    }
+
+    /// Update the value with the specified name.
+    pub fn set(&mut self, name: &str, value: ControlValue) {
+        self.values.insert(name.to_string(), value);
+    }
+
+    /// Remove the value with the specified name, returning it if it existed.
+    pub fn remove(&mut self, name: &str) -> Option<ControlValue> {
+        self.values.remove(name)
+    }
+
+    /// Iterate over all named values in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &ControlValue)> {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.as_str(), value))
+    }
+}
+
+/// A handle by which other threads may update a form's named values, such as a file watcher
+/// changing a control's default text. Changes are applied and rendered as the form next ticks.
+///
+/// # Examples
+/// ```
+/// use std::thread;
+/// use tty_form::{
+///     Form,
+///     step::{Step, CompoundStep},
+///     control::TextInput,
+///     value::ControlValue,
+/// };
+///
+/// let mut form = Form::new();
+///
+/// let mut step = CompoundStep::new();
+/// let mut branch = TextInput::new("Enter a branch:", false);
+/// branch.set_name("branch");
+/// step.add(branch);
+/// step.add_to(&mut form);
+///
+/// let handle = form.values_handle();
+/// thread::spawn(move || handle.set("branch", ControlValue::Text("main".to_string())))
+///     .join()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FormValuesHandle {
+    pending: Arc<Mutex<ValueStore>>,
+}
+
+impl FormValuesHandle {
+    /// Create a new handle with no pending changes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the named value, replacing any pending change to it not yet picked up by the form.
+    pub fn set(&self, name: &str, value: ControlValue) {
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.set(name, value);
+        }
+    }
+
+    /// Take the changes made since they were last taken.
+    pub(crate) fn take(&self) -> ValueStore {
+        self.pending
+            .lock()
+            .map(|mut pending| std::mem::take(&mut *pending))
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use crate::{
+        control::{Control, TextInput},
+        form::WATCH_INTERVAL,
+        step::{CompoundStep, Step},
+        Form, FormStatus,
+    };
+
+    use super::ControlValue;
+
+    #[test]
+    fn test_values_handle() {
+        let mut form = Form::new();
+        let mut step = CompoundStep::new();
+        let mut branch = TextInput::new("Enter a branch.", false);
+        branch.set_name("branch");
+        branch.add_to(&mut step);
+        step.add_to(&mut form);
+
+        let handle = form.values_handle();
+        thread::spawn(move || handle.set("branch", ControlValue::Text("main".to_string())))
+            .join()
+            .unwrap();
+
+        // Changes are only picked up once the form ticks
+        assert_eq!(Some(""), form.values().get_text("branch"));
+        thread::sleep(WATCH_INTERVAL);
+        assert_eq!(FormStatus::Active, form.tick_if_due());
+        assert_eq!(Some("main"), form.values().get_text("branch"));
+        assert_eq!("main", form.result());
+    }
+}